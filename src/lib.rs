@@ -0,0 +1,8 @@
+//! The noise library and planet geometry of the game, built as a library target so that the
+//! examples in their documentation run as doctests.
+
+pub mod macro_map {
+    pub mod jungle_noise;
+    pub mod planet;
+    pub mod seed;
+}
@@ -1,4 +1,6 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
 
 /// Create a generator applying an `fbm()`-like effect on the underlying generator.
 ///
@@ -66,6 +68,29 @@ macro_rules! impl_generator {
                 noise * self.normalization_factor
             }
         }
+
+        impl<G: GradientGenerator<$dim>> GradientGenerator<$dim> for Billow<$dim, G> {
+            fn sample_with_gradient(&self, point: [f64; $dim]) -> (f64, [f64; $dim]) {
+                let mut noise = 0.0;
+                let mut gradient = [0.0; $dim];
+                let mut amp = 1.0;
+                let mut freq = self.frequency;
+                for _ in 0..self.octaves {
+                    let (value, layer_gradient) =
+                        self.generator.sample_with_gradient(point.map(|x| x * freq));
+                    noise += amp * value.abs().mul_add(2.0, -1.0);
+                    for (d, layer_d) in gradient.iter_mut().zip(layer_gradient) {
+                        *d += amp * freq * 2.0 * value.signum() * layer_d;
+                    }
+                    freq *= self.lacunarity;
+                    amp *= self.persistence;
+                }
+                (
+                    noise * self.normalization_factor,
+                    gradient.map(|d| d * self.normalization_factor),
+                )
+            }
+        }
     };
 }
 
@@ -1,4 +1,6 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
 
 /// A generator blending the underlying generator with a given other generator based on the
 /// value supplied by a control-generator.
@@ -50,3 +52,22 @@ where
         a + t * (b - a)
     }
 }
+
+impl<const D: usize, GA, GB, GC> GradientGenerator<D> for Blend<D, GA, GB, GC>
+where
+    GA: GradientGenerator<D>,
+    GB: GradientGenerator<D>,
+    GC: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (a, da) = self.generator_a.sample_with_gradient(point);
+        let (b, db) = self.generator_b.sample_with_gradient(point);
+        let (t, dt) = self.generator_control.sample_with_gradient(point);
+        let t = t * 0.5 + 0.5;
+        (
+            a + t * (b - a),
+            std::array::from_fn(|i| da[i] + t * (db[i] - da[i]) + (b - a) * dt[i] * 0.5),
+        )
+    }
+}
@@ -1,4 +1,6 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
 
 /// A generator clamping results of the underlying generator to a given interval.
 ///
@@ -45,3 +47,18 @@ where
         self.generator.sample(point).clamp(self.min, self.max)
     }
 }
+
+impl<const D: usize, G> GradientGenerator<D> for Clamp<D, G>
+where
+    G: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (value, gradient) = self.generator.sample_with_gradient(point);
+        if value < self.min || value > self.max {
+            (value.clamp(self.min, self.max), [0.0; D])
+        } else {
+            (value, gradient)
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
+
+/// A generator remapping results of the underlying generator along a monotone cubic spline.
+///
+/// For details, see the documentation of [`curve()`]. Typically, this struct is not meant
+/// to be used directly. Instead, [`curve()`] implemented by [`Generator`], should be used
+/// to create [`Curve`].
+///
+/// [`curve()`]: Generator::curve
+#[derive(Clone, Debug)]
+pub struct Curve<const D: usize, G> {
+    generator: G,
+    control_points: Vec<(f64, f64)>,
+    tangents: Vec<f64>,
+}
+
+impl<G: Generator<1>> Generator1D for Curve<1, G> {}
+impl<G: Generator<2>> Generator2D for Curve<2, G> {}
+impl<G: Generator<3>> Generator3D for Curve<3, G> {}
+impl<G: Generator<4>> Generator4D for Curve<4, G> {}
+
+impl<const D: usize, G> Curve<D, G>
+where
+    G: Generator<D>,
+{
+    #[inline]
+    pub fn new(generator: G, control_points: &[(f64, f64)]) -> Self {
+        let mut control_points = control_points.to_vec();
+        control_points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        assert!(
+            control_points.len() >= 2,
+            "a curve requires at least two control points"
+        );
+        assert!(
+            control_points.windows(2).all(|pair| pair[0].0 < pair[1].0),
+            "the inputs of the control points of a curve must be distinct"
+        );
+        let tangents = compute_monotone_tangents(&control_points);
+        Self {
+            generator,
+            control_points,
+            tangents,
+        }
+    }
+
+    /// Evaluates the spline and its derivative at `x`.
+    fn evaluate(&self, x: f64) -> (f64, f64) {
+        let points = &self.control_points;
+        let (first, last) = (points[0], points[points.len() - 1]);
+        // NaN fails every comparison below, so it would not fall into any segment
+        if x.is_nan() {
+            return (x, x);
+        }
+        if x <= first.0 {
+            return (first.1, 0.0);
+        }
+        if x >= last.0 {
+            return (last.1, 0.0);
+        }
+        // index of the segment containing x
+        let k = points.partition_point(|point| point.0 <= x) - 1;
+        let ((x0, y0), (x1, y1)) = (points[k], points[k + 1]);
+        let (m0, m1) = (self.tangents[k], self.tangents[k + 1]);
+        let h = x1 - x0;
+        let t = (x - x0) / h;
+        let (t2, t3) = (t * t, t * t * t);
+        // cubic hermite basis functions and their derivatives
+        let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+        let h10 = t3 - 2.0 * t2 + t;
+        let h01 = -2.0 * t3 + 3.0 * t2;
+        let h11 = t3 - t2;
+        let value = h00 * y0 + h10 * h * m0 + h01 * y1 + h11 * h * m1;
+        let dh00 = 6.0 * t2 - 6.0 * t;
+        let dh10 = 3.0 * t2 - 4.0 * t + 1.0;
+        let dh11 = 3.0 * t2 - 2.0 * t;
+        let derivative = (dh00 * (y0 - y1)) / h + dh10 * m0 + dh11 * m1;
+        (value, derivative)
+    }
+}
+
+impl<const D: usize, G> Generator<D> for Curve<D, G>
+where
+    G: Generator<D>,
+{
+    #[inline]
+    fn sample(&self, point: [f64; D]) -> f64 {
+        self.evaluate(self.generator.sample(point)).0
+    }
+}
+
+impl<const D: usize, G> GradientGenerator<D> for Curve<D, G>
+where
+    G: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (value, gradient) = self.generator.sample_with_gradient(point);
+        let (value, derivative) = self.evaluate(value);
+        (value, gradient.map(|d| d * derivative))
+    }
+}
+
+/// Computes tangents at each control point using the Fritsch–Carlson method, such that the
+/// spline is monotone between control points wherever the control points are.
+fn compute_monotone_tangents(points: &[(f64, f64)]) -> Vec<f64> {
+    let secants: Vec<f64> = points
+        .windows(2)
+        .map(|pair| (pair[1].1 - pair[0].1) / (pair[1].0 - pair[0].0))
+        .collect();
+    let n = points.len();
+    let mut tangents = vec![0.0; n];
+    tangents[0] = secants[0];
+    tangents[n - 1] = secants[n - 2];
+    for k in 1..n - 1 {
+        if secants[k - 1] * secants[k] > 0.0 {
+            tangents[k] = (secants[k - 1] + secants[k]) / 2.0;
+        }
+    }
+    for (k, &secant) in secants.iter().enumerate() {
+        if secant == 0.0 {
+            tangents[k] = 0.0;
+            tangents[k + 1] = 0.0;
+            continue;
+        }
+        let alpha = tangents[k] / secant;
+        let beta = tangents[k + 1] / secant;
+        let magnitude = alpha * alpha + beta * beta;
+        if magnitude > 9.0 {
+            let tau = 3.0 / magnitude.sqrt();
+            tangents[k] = tau * alpha * secant;
+            tangents[k + 1] = tau * beta * secant;
+        }
+    }
+    tangents
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::macro_map::jungle_noise::generator::{Generator, GradientGenerator};
+    use crate::macro_map::jungle_noise::source::Source;
+
+    #[test]
+    fn nan_propagates() {
+        let generator = Source::<1>::constant(f64::NAN).curve(&[(-1.0, 0.0), (0.0, 0.5), (1.0, 1.0)]);
+        assert!(generator.sample([0.0]).is_nan());
+        let (value, gradient) = generator.sample_with_gradient([0.0]);
+        assert!(value.is_nan() && gradient[0].is_nan());
+    }
+}
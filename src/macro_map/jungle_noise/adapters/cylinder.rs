@@ -0,0 +1,85 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator2D, Generator3D, GradientGenerator,
+};
+use std::f64::consts::TAU;
+
+/// A generator which wraps the x-axis of input points around a cylinder before passing them to
+/// the underlying generator of one dimension higher.
+///
+/// For details, see the documentation of [`cylinder()`]. Typically, this struct is not meant
+/// to be used directly. Instead, [`cylinder()`] implemented by [`Generator3D`], should be used
+/// to create [`Cylinder`]. Wrapping 3-dimensional input points onto a 4-dimensional generator
+/// requires constructing [`Cylinder`] directly.
+///
+/// [`cylinder()`]: Generator3D::cylinder
+#[derive(Clone, Copy, Debug)]
+pub struct Cylinder<G> {
+    generator: G,
+    period: f64,
+    radius: f64,
+}
+
+impl<G: Generator<3>> Generator2D for Cylinder<G> {}
+impl<G: Generator<4>> Generator3D for Cylinder<G> {}
+
+impl<G> Cylinder<G> {
+    #[inline]
+    pub fn new(generator: G, period: f64) -> Self {
+        Self {
+            generator,
+            period,
+            radius: period / TAU,
+        }
+    }
+
+    /// Lifts the x-coordinate onto a circle whose circumference equals the period, so that
+    /// distances along the x-axis are preserved.
+    #[inline]
+    fn lift(&self, x: f64) -> (f64, f64, f64, f64) {
+        let (sin, cos) = (x * TAU / self.period).sin_cos();
+        (self.radius * cos, self.radius * sin, sin, cos)
+    }
+}
+
+impl<G: Generator<3>> Generator<2> for Cylinder<G> {
+    #[inline]
+    fn sample(&self, point: [f64; 2]) -> f64 {
+        let (u, v, _, _) = self.lift(point[0]);
+        self.generator.sample([u, v, point[1]])
+    }
+}
+
+impl<G: Generator<4>> Generator<3> for Cylinder<G> {
+    #[inline]
+    fn sample(&self, point: [f64; 3]) -> f64 {
+        let (u, v, _, _) = self.lift(point[0]);
+        self.generator.sample([u, v, point[1], point[2]])
+    }
+}
+
+impl<G: GradientGenerator<3>> GradientGenerator<2> for Cylinder<G> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        let (u, v, sin, cos) = self.lift(point[0]);
+        let (value, gradient) = self.generator.sample_with_gradient([u, v, point[1]]);
+        (value, [cos * gradient[1] - sin * gradient[0], gradient[2]])
+    }
+}
+
+impl<G: GradientGenerator<4>> GradientGenerator<3> for Cylinder<G> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 3]) -> (f64, [f64; 3]) {
+        let (u, v, sin, cos) = self.lift(point[0]);
+        let (value, gradient) = self
+            .generator
+            .sample_with_gradient([u, v, point[1], point[2]]);
+        (
+            value,
+            [
+                cos * gradient[1] - sin * gradient[0],
+                gradient[2],
+                gradient[3],
+            ],
+        )
+    }
+}
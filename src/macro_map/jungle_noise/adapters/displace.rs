@@ -1,4 +1,6 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
 
 /// A generator producing the maximum of results of the underlying generator and results of
 /// a given other generator.
@@ -44,3 +46,20 @@ where
         self.generator.sample(point)
     }
 }
+
+impl<const D: usize, const A: usize, G, GA> GradientGenerator<D> for Displace<D, A, G, GA>
+where
+    G: GradientGenerator<D>,
+    GA: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, mut point: [f64; D]) -> (f64, [f64; D]) {
+        let (displacement, ddisplacement) = self.displacement_generator.sample_with_gradient(point);
+        point[A] += displacement;
+        let (value, gradient) = self.generator.sample_with_gradient(point);
+        (
+            value,
+            std::array::from_fn(|i| gradient[i] + gradient[A] * ddisplacement[i]),
+        )
+    }
+}
@@ -0,0 +1,91 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator2D, Generator3D, GradientGenerator,
+};
+
+/// A generator warping the input of the underlying generator by a vector of generators.
+///
+/// For details, see the documentation of [`domain_warp()`]. Typically, this struct is not meant
+/// to be used directly. Instead, [`domain_warp()`] implemented by [`Generator2D`] and
+/// [`Generator3D`], should be used to create [`DomainWarp`].
+///
+/// [`domain_warp()`]: Generator2D::domain_warp
+#[derive(Clone, Debug)]
+pub struct DomainWarp<const D: usize, G, GW> {
+    generator: G,
+    warp: [GW; D],
+    strength: f64,
+    iterations: u32,
+}
+
+impl<G: Generator<2>, GW: Generator<2>> Generator2D for DomainWarp<2, G, GW> {}
+impl<G: Generator<3>, GW: Generator<3>> Generator3D for DomainWarp<3, G, GW> {}
+
+impl<const D: usize, G, GW> DomainWarp<D, G, GW>
+where
+    G: Generator<D>,
+    GW: Generator<D>,
+{
+    #[inline]
+    pub fn new(generator: G, warp: [GW; D], strength: f64, iterations: u32) -> Self {
+        Self {
+            generator,
+            warp,
+            strength,
+            iterations,
+        }
+    }
+}
+
+impl<const D: usize, G, GW> Generator<D> for DomainWarp<D, G, GW>
+where
+    G: Generator<D>,
+    GW: Generator<D>,
+{
+    #[inline]
+    fn sample(&self, point: [f64; D]) -> f64 {
+        let mut offset = [0.0; D];
+        for _ in 0..self.iterations {
+            let warped: [f64; D] = std::array::from_fn(|i| point[i] + offset[i]);
+            offset = std::array::from_fn(|i| self.strength * self.warp[i].sample(warped));
+        }
+        self.generator
+            .sample(std::array::from_fn(|i| point[i] + offset[i]))
+    }
+}
+
+impl<const D: usize, G, GW> GradientGenerator<D> for DomainWarp<D, G, GW>
+where
+    G: GradientGenerator<D>,
+    GW: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let mut offset = [0.0; D];
+        // jacobian of the offset with respect to the input point
+        let mut jacobian = [[0.0; D]; D];
+        for _ in 0..self.iterations {
+            let warped: [f64; D] = std::array::from_fn(|i| point[i] + offset[i]);
+            let mut next_jacobian = [[0.0; D]; D];
+            for i in 0..D {
+                let (value, gradient) = self.warp[i].sample_with_gradient(warped);
+                offset[i] = self.strength * value;
+                // chain rule through the warped point, whose jacobian is the identity plus
+                // the jacobian of the previous offset
+                for j in 0..D {
+                    let chained: f64 = (0..D).map(|k| gradient[k] * jacobian[k][j]).sum();
+                    next_jacobian[i][j] = self.strength * (gradient[j] + chained);
+                }
+            }
+            jacobian = next_jacobian;
+        }
+        let (value, gradient) = self
+            .generator
+            .sample_with_gradient(std::array::from_fn(|i| point[i] + offset[i]));
+        (
+            value,
+            std::array::from_fn(|j| {
+                gradient[j] + (0..D).map(|k| gradient[k] * jacobian[k][j]).sum::<f64>()
+            }),
+        )
+    }
+}
@@ -1,4 +1,6 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
 
 /// A generator applying the exponential function on results of the underlying generator.
 ///
@@ -36,3 +38,15 @@ where
         self.generator.sample(point).exp()
     }
 }
+
+impl<const D: usize, G> GradientGenerator<D> for Exp<D, G>
+where
+    G: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (value, gradient) = self.generator.sample_with_gradient(point);
+        let value = value.exp();
+        (value, gradient.map(|d| d * value))
+    }
+}
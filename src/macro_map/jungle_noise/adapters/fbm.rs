@@ -1,4 +1,7 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GeneratorF32,
+    GradientGenerator,
+};
 
 /// A generator applying fractal brownian motion on the underlying generator.
 ///
@@ -61,6 +64,43 @@ macro_rules! impl_generator {
                 noise * self.normalization_factor
             }
         }
+
+        impl<G: GradientGenerator<$dim>> GradientGenerator<$dim> for Fbm<$dim, G> {
+            fn sample_with_gradient(&self, point: [f64; $dim]) -> (f64, [f64; $dim]) {
+                let mut noise = 0.0;
+                let mut gradient = [0.0; $dim];
+                let mut amp = 1.0;
+                let mut freq = self.frequency;
+                for _ in 0..self.octaves {
+                    let (value, layer_gradient) =
+                        self.generator.sample_with_gradient(point.map(|x| x * freq));
+                    noise += amp * value;
+                    for (d, layer_d) in gradient.iter_mut().zip(layer_gradient) {
+                        *d += amp * freq * layer_d;
+                    }
+                    freq *= self.lacunarity;
+                    amp *= self.persistence;
+                }
+                (
+                    noise * self.normalization_factor,
+                    gradient.map(|d| d * self.normalization_factor),
+                )
+            }
+        }
+
+        impl<G: GeneratorF32<$dim>> GeneratorF32<$dim> for Fbm<$dim, G> {
+            fn sample_f32(&self, point: [f32; $dim]) -> f32 {
+                let mut noise = 0.0;
+                let mut amp = 1.0;
+                let mut freq = self.frequency as f32;
+                for _ in 0..self.octaves {
+                    noise += amp * self.generator.sample_f32(point.map(|x| x * freq));
+                    freq *= self.lacunarity as f32;
+                    amp *= self.persistence as f32;
+                }
+                noise * self.normalization_factor as f32
+            }
+        }
     };
 }
 
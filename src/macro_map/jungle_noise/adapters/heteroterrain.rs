@@ -0,0 +1,105 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
+
+/// A generator applying heterogeneous terrain noise on the underlying generator.
+///
+/// For details, see the documentation of [`heteroterrain()`]. Typically, this struct is not meant
+/// to be used directly. Instead, [`heteroterrain()`] implemented by [`Generator`], should be used
+/// to create [`HeteroTerrain`].
+///
+/// [`heteroterrain()`]: Generator::heteroterrain
+#[derive(Clone, Copy, Debug)]
+pub struct HeteroTerrain<const D: usize, G> {
+    generator: G,
+    octaves: u32,
+    frequency: f64,
+    lacunarity: f64,
+    persistence: f64,
+}
+
+impl<G: Generator<1>> Generator1D for HeteroTerrain<1, G> {}
+impl<G: Generator<2>> Generator2D for HeteroTerrain<2, G> {}
+impl<G: Generator<3>> Generator3D for HeteroTerrain<3, G> {}
+impl<G: Generator<4>> Generator4D for HeteroTerrain<4, G> {}
+
+impl<const D: usize, G> HeteroTerrain<D, G>
+where
+    G: Generator<D>,
+{
+    #[inline]
+    pub fn new(
+        generator: G,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    ) -> Self {
+        Self {
+            generator,
+            octaves,
+            frequency,
+            lacunarity,
+            persistence,
+        }
+    }
+}
+
+macro_rules! impl_generator {
+    ($dim:literal) => {
+        impl<G: Generator<$dim>> Generator<$dim> for HeteroTerrain<$dim, G> {
+            fn sample(&self, point: [f64; $dim]) -> f64 {
+                if self.octaves == 0 {
+                    return 0.0;
+                }
+                let mut freq = self.frequency;
+                let mut noise = self.generator.sample(point.map(|x| x * freq));
+                let mut total_amp = 1.0;
+                let mut amp = 1.0;
+                for _ in 1..self.octaves {
+                    freq *= self.lacunarity;
+                    amp *= self.persistence;
+                    // the normalized altitude so far, rescaled to the [0, 1] range
+                    let altitude = (noise / total_amp + 1.0) * 0.5;
+                    noise += amp * altitude * self.generator.sample(point.map(|x| x * freq));
+                    total_amp += amp;
+                }
+                noise / total_amp
+            }
+        }
+
+        impl<G: GradientGenerator<$dim>> GradientGenerator<$dim> for HeteroTerrain<$dim, G> {
+            fn sample_with_gradient(&self, point: [f64; $dim]) -> (f64, [f64; $dim]) {
+                if self.octaves == 0 {
+                    return (0.0, [0.0; $dim]);
+                }
+                let mut freq = self.frequency;
+                let (mut noise, first_gradient) =
+                    self.generator.sample_with_gradient(point.map(|x| x * freq));
+                let mut gradient = first_gradient.map(|d| d * freq);
+                let mut total_amp = 1.0;
+                let mut amp = 1.0;
+                for _ in 1..self.octaves {
+                    freq *= self.lacunarity;
+                    amp *= self.persistence;
+                    let altitude = (noise / total_amp + 1.0) * 0.5;
+                    let (value, layer_gradient) =
+                        self.generator.sample_with_gradient(point.map(|x| x * freq));
+                    for i in 0..$dim {
+                        let altitude_d = gradient[i] / total_amp * 0.5;
+                        gradient[i] +=
+                            amp * (altitude_d * value + altitude * freq * layer_gradient[i]);
+                    }
+                    noise += amp * altitude * value;
+                    total_amp += amp;
+                }
+                (noise / total_amp, gradient.map(|d| d / total_amp))
+            }
+        }
+    };
+}
+
+impl_generator!(1);
+impl_generator!(2);
+impl_generator!(3);
+impl_generator!(4);
@@ -0,0 +1,158 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
+
+/// A generator applying hybrid multifractal noise on the underlying generator.
+///
+/// For details, see the documentation of [`hybridmulti()`]. Typically, this struct is not meant
+/// to be used directly. Instead, [`hybridmulti()`] implemented by [`Generator`], should be used
+/// to create [`HybridMulti`].
+///
+/// [`hybridmulti()`]: Generator::hybridmulti
+#[derive(Clone, Copy, Debug)]
+pub struct HybridMulti<const D: usize, G> {
+    generator: G,
+    octaves: u32,
+    frequency: f64,
+    lacunarity: f64,
+    persistence: f64,
+    normalization_factor: f64,
+}
+
+impl<G: Generator<1>> Generator1D for HybridMulti<1, G> {}
+impl<G: Generator<2>> Generator2D for HybridMulti<2, G> {}
+impl<G: Generator<3>> Generator3D for HybridMulti<3, G> {}
+impl<G: Generator<4>> Generator4D for HybridMulti<4, G> {}
+
+impl<const D: usize, G> HybridMulti<D, G>
+where
+    G: Generator<D>,
+{
+    #[inline]
+    pub fn new(
+        generator: G,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    ) -> Self {
+        let normalization_factor = compute_normalization_factor(octaves, persistence);
+        Self {
+            generator,
+            octaves,
+            frequency,
+            lacunarity,
+            persistence,
+            normalization_factor,
+        }
+    }
+}
+
+macro_rules! impl_generator {
+    ($dim:literal) => {
+        impl<G: Generator<$dim>> Generator<$dim> for HybridMulti<$dim, G> {
+            fn sample(&self, point: [f64; $dim]) -> f64 {
+                if self.octaves == 0 {
+                    return 0.0;
+                }
+                let mut freq = self.frequency;
+                let mut noise = self.generator.sample(point.map(|x| x * freq));
+                let mut weight = (noise + 1.0) * 0.5;
+                let mut amp = 1.0;
+                for _ in 1..self.octaves {
+                    freq *= self.lacunarity;
+                    amp *= self.persistence;
+                    let value = self.generator.sample(point.map(|x| x * freq));
+                    noise += amp * weight * value;
+                    weight = (weight * (value + 1.0)).min(1.0);
+                }
+                noise * self.normalization_factor
+            }
+        }
+
+        impl<G: GradientGenerator<$dim>> GradientGenerator<$dim> for HybridMulti<$dim, G> {
+            fn sample_with_gradient(&self, point: [f64; $dim]) -> (f64, [f64; $dim]) {
+                if self.octaves == 0 {
+                    return (0.0, [0.0; $dim]);
+                }
+                let mut freq = self.frequency;
+                let (mut noise, first_gradient) =
+                    self.generator.sample_with_gradient(point.map(|x| x * freq));
+                let mut gradient = first_gradient.map(|d| d * freq);
+                let mut weight = (noise + 1.0) * 0.5;
+                let mut weight_gradient = gradient.map(|d| d * 0.5);
+                let mut amp = 1.0;
+                for _ in 1..self.octaves {
+                    freq *= self.lacunarity;
+                    amp *= self.persistence;
+                    let (value, layer_gradient) =
+                        self.generator.sample_with_gradient(point.map(|x| x * freq));
+                    let layer_gradient = layer_gradient.map(|d| d * freq);
+                    noise += amp * weight * value;
+                    for i in 0..$dim {
+                        gradient[i] +=
+                            amp * (weight_gradient[i] * value + weight * layer_gradient[i]);
+                    }
+                    let next_weight = weight * (value + 1.0);
+                    if next_weight < 1.0 {
+                        for i in 0..$dim {
+                            weight_gradient[i] =
+                                weight_gradient[i] * (value + 1.0) + weight * layer_gradient[i];
+                        }
+                        weight = next_weight;
+                    } else {
+                        // the weight is saturated and no longer depends on the input
+                        weight_gradient = [0.0; $dim];
+                        weight = 1.0;
+                    }
+                }
+                (
+                    noise * self.normalization_factor,
+                    gradient.map(|d| d * self.normalization_factor),
+                )
+            }
+        }
+    };
+}
+
+impl_generator!(1);
+impl_generator!(2);
+impl_generator!(3);
+impl_generator!(4);
+
+#[inline]
+fn compute_normalization_factor(octaves: u32, persistence: f64) -> f64 {
+    1.0 / (0..octaves).fold(0.0, |acc, octave| acc + persistence.powi(octave as i32))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::macro_map::jungle_noise::generator::{Generator, GradientGenerator};
+    use crate::macro_map::jungle_noise::source::Source;
+
+    #[test]
+    fn gradient_matches_finite_differences() {
+        let generator = Source::<2>::simplex(3).hybridmulti(6, 0.1, 2.0, 0.5);
+        let h = 1e-6;
+        for i in 0..200 {
+            let point = [i as f64 * 0.37 - 30.0, (i % 17) as f64 * 1.3 - 5.0];
+            let (_, gradient) = generator.sample_with_gradient(point);
+            for axis in 0..2 {
+                let (mut ahead, mut behind) = (point, point);
+                ahead[axis] += h;
+                behind[axis] -= h;
+                let expected = (generator.sample(ahead) - generator.sample(behind)) / (2.0 * h);
+                assert!((expected - gradient[axis]).abs() < 1e-4 * (1.0 + expected.abs()));
+            }
+        }
+    }
+
+    #[test]
+    fn results_stay_within_bounds() {
+        let generator = Source::<2>::simplex(1).hybridmulti(8, 0.05, 2.0, 0.6);
+        for i in 0..10000 {
+            let value = generator.sample([(i % 100) as f64 * 0.77, (i / 100) as f64 * 0.91]);
+            assert!((-1.0..=1.0).contains(&value));
+        }
+    }
+}
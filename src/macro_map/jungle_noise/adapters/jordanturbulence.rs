@@ -0,0 +1,112 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
+
+/// The amplitude of the second octave, relative to the first.
+const GAIN1: f64 = 0.8;
+/// The strength with which the gradient of the first octave displaces subsequent octaves.
+const WARP0: f64 = 0.4;
+/// The strength with which the gradients of further octaves displace subsequent octaves.
+const WARP: f64 = 0.35;
+/// The weight of the gradient of the first octave in the damping of subsequent octaves.
+const DAMP0: f64 = 1.0;
+/// The weight of the gradients of further octaves in the damping of subsequent octaves.
+const DAMP: f64 = 0.8;
+/// The maximum fraction by which octaves are damped on flat terrain.
+const DAMP_SCALE: f64 = 1.0;
+
+/// A generator applying jordan turbulence on the underlying generator.
+///
+/// For details, see the documentation of [`jordanturbulence()`]. Typically, this struct is not
+/// meant to be used directly. Instead, [`jordanturbulence()`] implemented by [`Generator`], should
+/// be used to create [`JordanTurbulence`].
+///
+/// [`jordanturbulence()`]: Generator::jordanturbulence
+#[derive(Clone, Copy, Debug)]
+pub struct JordanTurbulence<const D: usize, G> {
+    generator: G,
+    octaves: u32,
+    frequency: f64,
+    lacunarity: f64,
+    persistence: f64,
+    normalization_factor: f64,
+}
+
+impl<G: GradientGenerator<1>> Generator1D for JordanTurbulence<1, G> {}
+impl<G: GradientGenerator<2>> Generator2D for JordanTurbulence<2, G> {}
+impl<G: GradientGenerator<3>> Generator3D for JordanTurbulence<3, G> {}
+impl<G: GradientGenerator<4>> Generator4D for JordanTurbulence<4, G> {}
+
+impl<const D: usize, G> JordanTurbulence<D, G>
+where
+    G: GradientGenerator<D>,
+{
+    #[inline]
+    pub fn new(
+        generator: G,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    ) -> Self {
+        let normalization_factor = compute_normalization_factor(octaves, persistence);
+        Self {
+            generator,
+            octaves,
+            frequency,
+            lacunarity,
+            persistence,
+            normalization_factor,
+        }
+    }
+}
+
+macro_rules! impl_generator {
+    ($dim:literal) => {
+        impl<G: GradientGenerator<$dim>> Generator<$dim> for JordanTurbulence<$dim, G> {
+            fn sample(&self, point: [f64; $dim]) -> f64 {
+                if self.octaves == 0 {
+                    return -1.0;
+                }
+                let point = point.map(|x| x * self.frequency);
+                let (value, gradient) = self.generator.sample_with_gradient(point);
+                let mut noise = value * value;
+                let mut warp = gradient.map(|d| WARP0 * value * d);
+                let mut damp = gradient.map(|d| DAMP0 * value * d);
+                let mut amp = GAIN1;
+                let mut damped_amp = amp * self.persistence;
+                let mut freq = self.lacunarity;
+                for _ in 1..self.octaves {
+                    let warped: [f64; $dim] = std::array::from_fn(|i| point[i] * freq + warp[i]);
+                    let (value, gradient) = self.generator.sample_with_gradient(warped);
+                    noise += damped_amp * value * value;
+                    for i in 0..$dim {
+                        warp[i] += WARP * value * gradient[i];
+                        damp[i] += DAMP * value * gradient[i];
+                    }
+                    freq *= self.lacunarity;
+                    amp *= self.persistence;
+                    // dampen the following octaves where the terrain so far is flat
+                    let slope = damp.iter().map(|d| d * d).sum::<f64>();
+                    damped_amp = amp * (1.0 - DAMP_SCALE / (1.0 + slope));
+                }
+                noise * self.normalization_factor * 2.0 - 1.0
+            }
+        }
+    };
+}
+
+impl_generator!(1);
+impl_generator!(2);
+impl_generator!(3);
+impl_generator!(4);
+
+#[inline]
+fn compute_normalization_factor(octaves: u32, persistence: f64) -> f64 {
+    // the second and third octave are bounded by GAIN1 times the persistence, every further
+    // octave by one more power of the persistence
+    let bound = (1..octaves).fold(1.0, |acc, octave| {
+        acc + GAIN1 * persistence.powi(octave.max(2) as i32 - 1)
+    });
+    1.0 / bound
+}
@@ -3,42 +3,68 @@ mod add;
 mod billow;
 mod blend;
 mod clamp;
+mod curve;
+mod cylinder;
 mod displace;
+mod domainwarp;
 mod exp;
 mod fbm;
+mod heteroterrain;
+mod hybridmulti;
+mod jordanturbulence;
 mod lambda;
 mod max;
 mod min;
+mod modulate;
 mod mul;
 mod neg;
 mod pow;
 mod power;
 mod product;
+mod remap;
 mod ridgedmulti;
 mod rotate;
 mod scale;
 mod select;
+mod sphere;
 mod sum;
+mod swissturbulence;
+mod terrace;
+mod torus;
 mod translate;
+mod turbulence;
 pub use abs::Abs;
 pub use add::Add;
 pub use billow::Billow;
 pub use blend::Blend;
 pub use clamp::Clamp;
+pub use curve::Curve;
+pub use cylinder::Cylinder;
 pub use displace::Displace;
+pub use domainwarp::DomainWarp;
 pub use exp::Exp;
 pub use fbm::Fbm;
+pub use heteroterrain::HeteroTerrain;
+pub use hybridmulti::HybridMulti;
+pub use jordanturbulence::JordanTurbulence;
 pub use lambda::Lambda;
 pub use max::Max;
 pub use min::Min;
+pub use modulate::Modulate;
 pub use mul::Mul;
 pub use neg::Neg;
 pub use pow::Pow;
 pub use power::Power;
 pub use product::Product;
+pub use remap::Remap;
 pub use ridgedmulti::RidgedMulti;
 pub use rotate::Rotate;
 pub use scale::Scale;
 pub use select::Select;
+pub use sphere::Sphere;
 pub use sum::Sum;
+pub use swissturbulence::SwissTurbulence;
+pub use terrace::Terrace;
+pub use torus::Torus;
 pub use translate::Translate;
+pub use turbulence::Turbulence;
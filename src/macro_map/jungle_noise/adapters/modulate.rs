@@ -0,0 +1,74 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D,
+};
+
+/// A generator multiplying results of the underlying generator by a function of one of the
+/// coordinates of the input point.
+///
+/// For details, see the documentation of [`modulate_x()`] and analogously for other axes.
+/// Typically, this struct is not meant to be used directly. Instead, [`modulate_x()`]
+/// implemented by [`Generator`], should be used to create [`Modulate`].
+///
+/// [`modulate_x()`]: Generator2D::modulate_x
+#[derive(Clone, Copy, Debug)]
+pub struct Modulate<const D: usize, const A: usize, G, M> {
+    generator: G,
+    modulation: M,
+}
+
+impl<const A: usize, G: Generator<1>, M: Fn(f64) -> f64> Generator1D for Modulate<1, A, G, M> {}
+impl<const A: usize, G: Generator<2>, M: Fn(f64) -> f64> Generator2D for Modulate<2, A, G, M> {}
+impl<const A: usize, G: Generator<3>, M: Fn(f64) -> f64> Generator3D for Modulate<3, A, G, M> {}
+impl<const A: usize, G: Generator<4>, M: Fn(f64) -> f64> Generator4D for Modulate<4, A, G, M> {}
+
+impl<const D: usize, const A: usize, G, M> Modulate<D, A, G, M>
+where
+    G: Generator<D>,
+    M: Fn(f64) -> f64,
+{
+    #[inline]
+    pub fn new(generator: G, modulation: M) -> Self {
+        Self {
+            generator,
+            modulation,
+        }
+    }
+}
+
+impl<const D: usize, const A: usize, G, M> Generator<D> for Modulate<D, A, G, M>
+where
+    G: Generator<D>,
+    M: Fn(f64) -> f64,
+{
+    #[inline]
+    fn sample(&self, point: [f64; D]) -> f64 {
+        self.generator.sample(point) * (self.modulation)(point[A])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::macro_map::jungle_noise::generator::{Generator, Generator2D, Generator4D};
+    use crate::macro_map::jungle_noise::source::Source;
+
+    #[test]
+    fn modulation_only_depends_on_its_axis() {
+        let bands = |latitude: f64| (latitude * 0.1).cos();
+        let generator = Source::<2>::constant(2.0).modulate_y(bands);
+        for i in 0..100 {
+            let y = i as f64 * 0.7 - 35.0;
+            assert_eq!(generator.sample([-13.0, y]), 2.0 * bands(y));
+            assert_eq!(generator.sample([-13.0, y]), generator.sample([42.0, y]));
+        }
+    }
+
+    #[test]
+    fn modulations_compose_across_axes() {
+        let generator = Source::<4>::simplex(5)
+            .modulate_x(|x| x * 0.5)
+            .modulate_w(|w| 1.0 - w);
+        let point = [0.3, 1.7, -2.1, 0.25];
+        let expected = Source::<4>::simplex(5).sample(point) * 0.15 * 0.75;
+        assert!((generator.sample(point) - expected).abs() < 1e-12);
+    }
+}
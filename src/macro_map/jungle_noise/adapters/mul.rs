@@ -1,4 +1,7 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GeneratorF32,
+    GradientGenerator,
+};
 
 /// A generator multiplying `scale` to results of the underlying generator.
 ///
@@ -37,3 +40,24 @@ where
         self.generator.sample(point) * self.scale
     }
 }
+
+impl<const D: usize, G> GradientGenerator<D> for Mul<D, G>
+where
+    G: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (value, gradient) = self.generator.sample_with_gradient(point);
+        (value * self.scale, gradient.map(|d| d * self.scale))
+    }
+}
+
+impl<const D: usize, G> GeneratorF32<D> for Mul<D, G>
+where
+    G: GeneratorF32<D>,
+{
+    #[inline]
+    fn sample_f32(&self, point: [f32; D]) -> f32 {
+        self.generator.sample_f32(point) * self.scale as f32
+    }
+}
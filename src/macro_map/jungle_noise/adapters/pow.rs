@@ -1,4 +1,6 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
 
 /// A generator raising results of the underlying generator to the power of `exponent`.
 ///
@@ -56,3 +58,27 @@ where
         self.generator.sample(point).powf(self.exponent)
     }
 }
+
+impl<const D: usize, G> GradientGenerator<D> for Pow<D, G, i32>
+where
+    G: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (value, gradient) = self.generator.sample_with_gradient(point);
+        let derivative = self.exponent as f64 * value.powi(self.exponent - 1);
+        (value.powi(self.exponent), gradient.map(|d| d * derivative))
+    }
+}
+
+impl<const D: usize, G> GradientGenerator<D> for Pow<D, G, f64>
+where
+    G: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (value, gradient) = self.generator.sample_with_gradient(point);
+        let derivative = self.exponent * value.powf(self.exponent - 1.0);
+        (value.powf(self.exponent), gradient.map(|d| d * derivative))
+    }
+}
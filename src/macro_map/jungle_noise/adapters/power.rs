@@ -1,4 +1,6 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
 
 /// A generator raising results of the underlying generator to the power of results of a
 /// given other generator.
@@ -45,3 +47,20 @@ where
             .powf(self.generator_b.sample(point))
     }
 }
+
+impl<const D: usize, GA, GB> GradientGenerator<D> for Power<D, GA, GB>
+where
+    GA: GradientGenerator<D>,
+    GB: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (a, da) = self.generator_a.sample_with_gradient(point);
+        let (b, db) = self.generator_b.sample_with_gradient(point);
+        let value = a.powf(b);
+        (
+            value,
+            std::array::from_fn(|i| b * a.powf(b - 1.0) * da[i] + value * a.ln() * db[i]),
+        )
+    }
+}
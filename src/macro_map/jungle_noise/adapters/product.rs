@@ -1,4 +1,6 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
 
 /// A generator multiplying results of the underlying generator to results of a given other
 /// generator.
@@ -43,3 +45,16 @@ where
         self.generator_a.sample(point) * self.generator_b.sample(point)
     }
 }
+
+impl<const D: usize, GA, GB> GradientGenerator<D> for Product<D, GA, GB>
+where
+    GA: GradientGenerator<D>,
+    GB: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (a, da) = self.generator_a.sample_with_gradient(point);
+        let (b, db) = self.generator_b.sample_with_gradient(point);
+        (a * b, std::array::from_fn(|i| da[i] * b + a * db[i]))
+    }
+}
@@ -0,0 +1,82 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
+
+/// A generator linearly remapping results of the underlying generator from one interval onto
+/// another.
+///
+/// For details, see the documentation of [`remap()`]. Typically, this struct is not meant
+/// to be used directly. Instead, [`remap()`] or [`normalize()`] implemented by [`Generator`],
+/// should be used to create [`Remap`].
+///
+/// [`remap()`]: Generator::remap
+/// [`normalize()`]: Generator::normalize
+#[derive(Clone, Copy, Debug)]
+pub struct Remap<const D: usize, G> {
+    generator: G,
+    from_start: f64,
+    to_start: f64,
+    factor: f64,
+    min: f64,
+    max: f64,
+}
+
+impl<G: Generator<1>> Generator1D for Remap<1, G> {}
+impl<G: Generator<2>> Generator2D for Remap<2, G> {}
+impl<G: Generator<3>> Generator3D for Remap<3, G> {}
+impl<G: Generator<4>> Generator4D for Remap<4, G> {}
+
+impl<const D: usize, G> Remap<D, G>
+where
+    G: Generator<D>,
+{
+    #[inline]
+    pub fn new(generator: G, from: [f64; 2], to: [f64; 2]) -> Self {
+        // an empty source interval maps everything onto the start of the target interval
+        let factor = if from[1] != from[0] {
+            (to[1] - to[0]) / (from[1] - from[0])
+        } else {
+            0.0
+        };
+        Self {
+            generator,
+            from_start: from[0],
+            to_start: to[0],
+            factor,
+            min: to[0].min(to[1]),
+            max: to[0].max(to[1]),
+        }
+    }
+
+    #[inline]
+    fn remap(&self, value: f64) -> f64 {
+        self.to_start + (value - self.from_start) * self.factor
+    }
+}
+
+impl<const D: usize, G> Generator<D> for Remap<D, G>
+where
+    G: Generator<D>,
+{
+    #[inline]
+    fn sample(&self, point: [f64; D]) -> f64 {
+        self.remap(self.generator.sample(point))
+            .clamp(self.min, self.max)
+    }
+}
+
+impl<const D: usize, G> GradientGenerator<D> for Remap<D, G>
+where
+    G: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (value, gradient) = self.generator.sample_with_gradient(point);
+        let value = self.remap(value);
+        if value < self.min || value > self.max {
+            (value.clamp(self.min, self.max), [0.0; D])
+        } else {
+            (value, gradient.map(|d| d * self.factor))
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
 
 /// Create a generator applying an `fbm()`-like effect on the underlying generator.
 ///
@@ -65,6 +67,44 @@ macro_rules! impl_generator {
                 (noise * self.normalization_factor).mul_add(2.0, -1.0)
             }
         }
+
+        impl<G: GradientGenerator<$dim>> GradientGenerator<$dim> for RidgedMulti<$dim, G> {
+            fn sample_with_gradient(&self, point: [f64; $dim]) -> (f64, [f64; $dim]) {
+                let mut noise = 0.0;
+                let mut gradient = [0.0; $dim];
+                let mut amp = 1.0;
+                let mut amp_gradient = [0.0; $dim];
+                let mut freq = self.frequency;
+                for _ in 0..self.octaves {
+                    let (value, value_gradient) =
+                        self.generator.sample_with_gradient(point.map(|x| x * freq));
+                    let ridge = 1.0 - value.abs();
+                    let mut layer = ridge * ridge;
+                    // d/dx (amp * (1 - |n(freq * x)|)^2)
+                    let layer_gradient: [f64; $dim] = std::array::from_fn(|i| {
+                        let ridge_d = -value.signum() * value_gradient[i] * freq;
+                        amp * 2.0 * ridge * ridge_d + layer * amp_gradient[i]
+                    });
+                    layer *= amp;
+                    noise += layer;
+                    for (d, layer_d) in gradient.iter_mut().zip(layer_gradient) {
+                        *d += layer_d;
+                    }
+                    freq *= self.lacunarity;
+                    let next_amp = layer / self.attenuation;
+                    amp_gradient = if (0.0..=1.0).contains(&next_amp) {
+                        layer_gradient.map(|d| d / self.attenuation)
+                    } else {
+                        [0.0; $dim]
+                    };
+                    amp = next_amp.clamp(0.0, 1.0);
+                }
+                (
+                    (noise * self.normalization_factor).mul_add(2.0, -1.0),
+                    gradient.map(|d| d * self.normalization_factor * 2.0),
+                )
+            }
+        }
     };
 }
 
@@ -1,4 +1,7 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GeneratorF32,
+    GradientGenerator,
+};
 
 /// A generator which scales input points before passing them to the underlying generator.
 ///
@@ -38,3 +41,27 @@ where
             .sample(std::array::from_fn(|i| point[i] * self.scale[i]))
     }
 }
+
+impl<const D: usize, G> GradientGenerator<D> for Scale<D, G>
+where
+    G: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (value, gradient) = self
+            .generator
+            .sample_with_gradient(std::array::from_fn(|i| point[i] * self.scale[i]));
+        (value, std::array::from_fn(|i| gradient[i] * self.scale[i]))
+    }
+}
+
+impl<const D: usize, G> GeneratorF32<D> for Scale<D, G>
+where
+    G: GeneratorF32<D>,
+{
+    #[inline]
+    fn sample_f32(&self, point: [f32; D]) -> f32 {
+        self.generator
+            .sample_f32(std::array::from_fn(|i| point[i] * self.scale[i] as f32))
+    }
+}
@@ -1,4 +1,6 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
 
 /// Create a generator selecting the result of either the underlying generator or that of a given
 /// other generator based on whether the value supplied by a control-generator lies within the
@@ -63,3 +65,20 @@ where
         }
     }
 }
+
+impl<const D: usize, GA, GB, GC> GradientGenerator<D> for Select<D, GA, GB, GC>
+where
+    GA: GradientGenerator<D>,
+    GB: GradientGenerator<D>,
+    GC: Generator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        match self.generator_control.sample(point) {
+            t if self.selection_min <= t && t <= self.selection_max => {
+                self.generator_a.sample_with_gradient(point)
+            }
+            _ => self.generator_b.sample_with_gradient(point),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::macro_map::jungle_noise::generator::{Generator, Generator2D, GradientGenerator};
+use crate::macro_map::planet::LatLon;
+
+/// A generator which maps longitude and latitude onto the unit sphere before passing the
+/// resulting point to the underlying 3-dimensional generator.
+///
+/// For details, see the documentation of [`sphere()`]. Typically, this struct is not meant
+/// to be used directly. Instead, [`sphere()`] implemented by [`Generator3D`], should be used
+/// to create [`Sphere`].
+///
+/// [`sphere()`]: crate::macro_map::jungle_noise::generator::Generator3D::sphere
+/// [`Generator3D`]: crate::macro_map::jungle_noise::generator::Generator3D
+#[derive(Clone, Copy, Debug)]
+pub struct Sphere<G> {
+    generator: G,
+}
+
+impl<G: Generator<3>> Generator2D for Sphere<G> {}
+
+impl<G: Generator<3>> Sphere<G> {
+    #[inline]
+    pub fn new(generator: G) -> Self {
+        Self { generator }
+    }
+}
+
+impl<G: Generator<3>> Generator<2> for Sphere<G> {
+    #[inline]
+    fn sample(&self, point: [f64; 2]) -> f64 {
+        self.generator.sample(LatLon::new(point[0], point[1]).to_unit_sphere())
+    }
+}
+
+impl<G: GradientGenerator<3>> GradientGenerator<2> for Sphere<G> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        let (sin_lon, cos_lon) = point[0].sin_cos();
+        let (sin_lat, cos_lat) = point[1].sin_cos();
+        let (value, gradient) = self
+            .generator
+            .sample_with_gradient(LatLon::new(point[0], point[1]).to_unit_sphere());
+        // chain rule with the partial derivatives of the point on the sphere
+        let d_lon = cos_lat * (cos_lon * gradient[1] - sin_lon * gradient[0]);
+        let d_lat =
+            cos_lat * gradient[2] - sin_lat * (cos_lon * gradient[0] + sin_lon * gradient[1]);
+        (value, [d_lon, d_lat])
+    }
+}
@@ -0,0 +1,89 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
+
+/// The strength with which the accumulated gradient of previous octaves displaces the input of
+/// subsequent octaves.
+const WARP: f64 = 0.15;
+
+/// A generator applying swiss turbulence on the underlying generator.
+///
+/// For details, see the documentation of [`swissturbulence()`]. Typically, this struct is not
+/// meant to be used directly. Instead, [`swissturbulence()`] implemented by [`Generator`], should
+/// be used to create [`SwissTurbulence`].
+///
+/// [`swissturbulence()`]: Generator::swissturbulence
+#[derive(Clone, Copy, Debug)]
+pub struct SwissTurbulence<const D: usize, G> {
+    generator: G,
+    octaves: u32,
+    frequency: f64,
+    lacunarity: f64,
+    persistence: f64,
+    normalization_factor: f64,
+}
+
+impl<G: GradientGenerator<1>> Generator1D for SwissTurbulence<1, G> {}
+impl<G: GradientGenerator<2>> Generator2D for SwissTurbulence<2, G> {}
+impl<G: GradientGenerator<3>> Generator3D for SwissTurbulence<3, G> {}
+impl<G: GradientGenerator<4>> Generator4D for SwissTurbulence<4, G> {}
+
+impl<const D: usize, G> SwissTurbulence<D, G>
+where
+    G: GradientGenerator<D>,
+{
+    #[inline]
+    pub fn new(
+        generator: G,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    ) -> Self {
+        let normalization_factor = compute_normalization_factor(octaves, persistence);
+        Self {
+            generator,
+            octaves,
+            frequency,
+            lacunarity,
+            persistence,
+            normalization_factor,
+        }
+    }
+}
+
+macro_rules! impl_generator {
+    ($dim:literal) => {
+        impl<G: GradientGenerator<$dim>> Generator<$dim> for SwissTurbulence<$dim, G> {
+            fn sample(&self, point: [f64; $dim]) -> f64 {
+                let mut noise = 0.0;
+                let mut warp = [0.0; $dim];
+                let mut amp = 1.0;
+                let mut freq = self.frequency;
+                for _ in 0..self.octaves {
+                    let warped: [f64; $dim] =
+                        std::array::from_fn(|i| (point[i] + WARP * warp[i]) * freq);
+                    let (value, gradient) = self.generator.sample_with_gradient(warped);
+                    noise += amp * (1.0 - value.abs());
+                    for (w, d) in warp.iter_mut().zip(gradient) {
+                        *w -= amp * value * d;
+                    }
+                    freq *= self.lacunarity;
+                    // dampen the following octaves in valleys, keeping lowlands smooth
+                    amp *= self.persistence * noise.clamp(0.0, 1.0);
+                }
+                noise * self.normalization_factor * 2.0 - 1.0
+            }
+        }
+    };
+}
+
+impl_generator!(1);
+impl_generator!(2);
+impl_generator!(3);
+impl_generator!(4);
+
+#[inline]
+fn compute_normalization_factor(octaves: u32, persistence: f64) -> f64 {
+    1.0 / (0..octaves).fold(0.0, |acc, octave| acc + persistence.powi(octave as i32))
+}
@@ -0,0 +1,109 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
+
+/// A generator mapping results of the underlying generator onto terrace-like steps.
+///
+/// For details, see the documentation of [`terrace()`]. Typically, this struct is not meant
+/// to be used directly. Instead, [`terrace()`] implemented by [`Generator`], should be used
+/// to create [`Terrace`].
+///
+/// [`terrace()`]: Generator::terrace
+#[derive(Clone, Debug)]
+pub struct Terrace<const D: usize, G> {
+    generator: G,
+    control_points: Vec<f64>,
+    invert: bool,
+}
+
+impl<G: Generator<1>> Generator1D for Terrace<1, G> {}
+impl<G: Generator<2>> Generator2D for Terrace<2, G> {}
+impl<G: Generator<3>> Generator3D for Terrace<3, G> {}
+impl<G: Generator<4>> Generator4D for Terrace<4, G> {}
+
+impl<const D: usize, G> Terrace<D, G>
+where
+    G: Generator<D>,
+{
+    #[inline]
+    pub fn new(generator: G, control_points: &[f64], invert: bool) -> Self {
+        let mut control_points = control_points.to_vec();
+        control_points.sort_by(f64::total_cmp);
+        control_points.dedup();
+        assert!(
+            control_points.len() >= 2,
+            "a terrace requires at least two distinct control points"
+        );
+        Self {
+            generator,
+            control_points,
+            invert,
+        }
+    }
+
+    /// Maps `x` onto the terraces, returning the result and its derivative.
+    fn evaluate(&self, x: f64) -> (f64, f64) {
+        let points = &self.control_points;
+        let (first, last) = (points[0], points[points.len() - 1]);
+        // NaN fails every comparison below, so it would not fall into any segment
+        if x.is_nan() {
+            return (x, x);
+        }
+        if x <= first {
+            return (first, 0.0);
+        }
+        if x >= last {
+            return (last, 0.0);
+        }
+        // index of the terrace containing x
+        let k = points.partition_point(|&point| point <= x) - 1;
+        let (mut lower, mut upper) = (points[k], points[k + 1]);
+        let mut alpha = (x - lower) / (upper - lower);
+        let mut d_alpha = 1.0 / (upper - lower);
+        if self.invert {
+            alpha = 1.0 - alpha;
+            d_alpha = -d_alpha;
+            std::mem::swap(&mut lower, &mut upper);
+        }
+        // squaring the position within the terrace flattens its lower end into a plateau
+        let value = lower + alpha * alpha * (upper - lower);
+        let derivative = 2.0 * alpha * d_alpha * (upper - lower);
+        (value, derivative)
+    }
+}
+
+impl<const D: usize, G> Generator<D> for Terrace<D, G>
+where
+    G: Generator<D>,
+{
+    #[inline]
+    fn sample(&self, point: [f64; D]) -> f64 {
+        self.evaluate(self.generator.sample(point)).0
+    }
+}
+
+impl<const D: usize, G> GradientGenerator<D> for Terrace<D, G>
+where
+    G: GradientGenerator<D>,
+{
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]) {
+        let (value, gradient) = self.generator.sample_with_gradient(point);
+        let (value, derivative) = self.evaluate(value);
+        (value, gradient.map(|d| d * derivative))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::macro_map::jungle_noise::generator::Generator;
+    use crate::macro_map::jungle_noise::source::Source;
+
+    #[test]
+    fn nan_propagates() {
+        for invert in [false, true] {
+            let generator = Source::<1>::constant(f64::NAN).terrace(&[-1.0, 0.0, 1.0], invert);
+            assert!(generator.sample([0.0]).is_nan());
+        }
+    }
+}
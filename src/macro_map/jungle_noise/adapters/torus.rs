@@ -0,0 +1,68 @@
+use crate::macro_map::jungle_noise::generator::{Generator, Generator2D, GradientGenerator};
+use std::f64::consts::TAU;
+
+/// A generator which wraps both axes of 2-dimensional input points around a torus before
+/// passing them to the underlying 4-dimensional generator.
+///
+/// For details, see the documentation of [`torus()`]. Typically, this struct is not meant
+/// to be used directly. Instead, [`torus()`] implemented by [`Generator4D`], should be used
+/// to create [`Torus`].
+///
+/// [`torus()`]: crate::macro_map::jungle_noise::generator::Generator4D::torus
+/// [`Generator4D`]: crate::macro_map::jungle_noise::generator::Generator4D
+#[derive(Clone, Copy, Debug)]
+pub struct Torus<G> {
+    generator: G,
+    period: [f64; 2],
+    radius: [f64; 2],
+}
+
+impl<G: Generator<4>> Generator2D for Torus<G> {}
+
+impl<G: Generator<4>> Torus<G> {
+    #[inline]
+    pub fn new(generator: G, period: [f64; 2]) -> Self {
+        Self {
+            generator,
+            period,
+            radius: period.map(|p| p / TAU),
+        }
+    }
+
+    /// Lifts each coordinate onto a circle whose circumference equals the period of that axis,
+    /// returning the lifted point alongside the sine and cosine of each angle.
+    #[inline]
+    fn lift(&self, point: [f64; 2]) -> ([f64; 4], [(f64, f64); 2]) {
+        let angles: [(f64, f64); 2] =
+            std::array::from_fn(|i| (point[i] * TAU / self.period[i]).sin_cos());
+        let lifted = [
+            self.radius[0] * angles[0].1,
+            self.radius[0] * angles[0].0,
+            self.radius[1] * angles[1].1,
+            self.radius[1] * angles[1].0,
+        ];
+        (lifted, angles)
+    }
+}
+
+impl<G: Generator<4>> Generator<2> for Torus<G> {
+    #[inline]
+    fn sample(&self, point: [f64; 2]) -> f64 {
+        self.generator.sample(self.lift(point).0)
+    }
+}
+
+impl<G: GradientGenerator<4>> GradientGenerator<2> for Torus<G> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        let (lifted, [(sin_x, cos_x), (sin_y, cos_y)]) = self.lift(point);
+        let (value, gradient) = self.generator.sample_with_gradient(lifted);
+        (
+            value,
+            [
+                cos_x * gradient[1] - sin_x * gradient[0],
+                cos_y * gradient[3] - sin_y * gradient[2],
+            ],
+        )
+    }
+}
@@ -0,0 +1,106 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GradientGenerator,
+};
+use crate::macro_map::jungle_noise::sources::Simplex;
+
+/// A generator randomly displacing the input of the underlying generator along every axis.
+///
+/// For details, see the documentation of [`turbulence()`]. Typically, this struct is not meant
+/// to be used directly. Instead, [`turbulence()`] implemented by [`Generator`], should be used
+/// to create [`Turbulence`].
+///
+/// [`turbulence()`]: Generator::turbulence
+#[derive(Clone, Debug)]
+pub struct Turbulence<const D: usize, G> {
+    generator: G,
+    distortion: [Simplex<D>; D],
+    frequency: f64,
+    power: f64,
+    roughness: u32,
+    normalization_factor: f64,
+}
+
+impl<G: Generator<1>> Generator1D for Turbulence<1, G> {}
+impl<G: Generator<2>> Generator2D for Turbulence<2, G> {}
+impl<G: Generator<3>> Generator3D for Turbulence<3, G> {}
+impl<G: Generator<4>> Generator4D for Turbulence<4, G> {}
+
+impl<const D: usize, G> Turbulence<D, G>
+where
+    G: Generator<D>,
+{
+    #[inline]
+    pub fn new(generator: G, seed: u64, frequency: f64, power: f64, roughness: u32) -> Self {
+        // every axis is displaced by its own, differently seeded noise
+        let distortion = std::array::from_fn(|axis| Simplex::new(seed.wrapping_add(axis as u64)));
+        let normalization_factor =
+            1.0 / (0..roughness).fold(0.0, |acc, octave| acc + 0.5_f64.powi(octave as i32));
+        Self {
+            generator,
+            distortion,
+            frequency,
+            power,
+            roughness,
+            normalization_factor,
+        }
+    }
+}
+
+macro_rules! impl_generator {
+    ($dim:literal) => {
+        impl<G: Generator<$dim>> Generator<$dim> for Turbulence<$dim, G> {
+            fn sample(&self, point: [f64; $dim]) -> f64 {
+                let displaced: [f64; $dim] = std::array::from_fn(|axis| {
+                    let mut distortion = 0.0;
+                    let mut amp = 1.0;
+                    let mut freq = self.frequency;
+                    for _ in 0..self.roughness {
+                        distortion += amp * self.distortion[axis].sample(point.map(|x| x * freq));
+                        freq *= 2.0;
+                        amp *= 0.5;
+                    }
+                    point[axis] + self.power * distortion * self.normalization_factor
+                });
+                self.generator.sample(displaced)
+            }
+        }
+
+        impl<G: GradientGenerator<$dim>> GradientGenerator<$dim> for Turbulence<$dim, G> {
+            fn sample_with_gradient(&self, point: [f64; $dim]) -> (f64, [f64; $dim]) {
+                let mut displaced = point;
+                // jacobian of the displaced point with respect to the input point
+                let mut jacobian = [[0.0; $dim]; $dim];
+                for axis in 0..$dim {
+                    jacobian[axis][axis] = 1.0;
+                    let mut amp = 1.0;
+                    let mut freq = self.frequency;
+                    for _ in 0..self.roughness {
+                        let (value, gradient) =
+                            self.distortion[axis].sample_with_gradient(point.map(|x| x * freq));
+                        let scale = self.power * self.normalization_factor * amp;
+                        displaced[axis] += scale * value;
+                        for (d, layer_d) in jacobian[axis].iter_mut().zip(gradient) {
+                            *d += scale * freq * layer_d;
+                        }
+                        freq *= 2.0;
+                        amp *= 0.5;
+                    }
+                }
+                let (value, gradient) = self.generator.sample_with_gradient(displaced);
+                (
+                    value,
+                    std::array::from_fn(|i| {
+                        (0..$dim)
+                            .map(|axis| gradient[axis] * jacobian[axis][i])
+                            .sum()
+                    }),
+                )
+            }
+        }
+    };
+}
+
+impl_generator!(1);
+impl_generator!(2);
+impl_generator!(3);
+impl_generator!(4);
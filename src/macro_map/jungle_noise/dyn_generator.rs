@@ -0,0 +1,146 @@
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D,
+};
+use std::fmt;
+use std::sync::Arc;
+
+/// An object-safe counterpart to [`Generator<D>`].
+///
+/// [`Generator<D>`] requires `Sized` so that its combinators can take `self` by value, which
+/// rules out `dyn Generator<D>`. This trait only exposes sampling and is implemented for every
+/// generator that can be shared across threads, allowing it to be used as a trait object. It is
+/// not meant to be used directly, see [`DynGenerator`] instead.
+pub trait DynSample<const D: usize>: Send + Sync {
+    /// Samples the underlying generator at a given `point`.
+    fn sample_dyn(&self, point: [f64; D]) -> f64;
+
+    /// Samples the underlying generator at each of the given `points`.
+    fn sample_many_dyn(&self, points: &[[f64; D]], values: &mut [f64]);
+}
+
+impl<const D: usize, G> DynSample<D> for G
+where
+    G: Generator<D> + Send + Sync,
+{
+    #[inline]
+    fn sample_dyn(&self, point: [f64; D]) -> f64 {
+        self.sample(point)
+    }
+
+    #[inline]
+    fn sample_many_dyn(&self, points: &[[f64; D]], values: &mut [f64]) {
+        self.sample_many(points, values)
+    }
+}
+
+/// A type-erased generator which can hold any generator pipeline of a given dimensionality.
+///
+/// Every source and adapter has its own concrete type, so two pipelines built from different
+/// adapters cannot be stored in the same field. [`DynGenerator`] erases the concrete type behind
+/// a reference-counted trait object, so that pipelines can be chosen at runtime and stored side
+/// by side. As it implements [`Generator<D>`] itself, all combinators remain available on the
+/// erased type, and the result can be erased again.
+///
+/// Cloning a [`DynGenerator`] is cheap, as the underlying pipeline is shared.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use fungal_jungle::macro_map::jungle_noise::{dyn_generator::DynGenerator, generator::Generator, source::Source};
+/// // two pipelines of different concrete types stored side by side
+/// let layers: Vec<DynGenerator<2>> = vec![
+///     Source::simplex(42).fbm(3, 0.01, 2.0, 0.5).into_dyn(),
+///     Source::worley(43).scale([0.05; 2]).into_dyn(),
+/// ];
+///
+/// // combinators can still be applied to the erased generator
+/// let generator = layers[0].clone().abs().mul(2.0).into_dyn();
+/// let value = generator.sample([0.2, 0.5]);
+/// ```
+///
+/// [`Generator<D>`]: Generator
+#[derive(Clone)]
+pub struct DynGenerator<const D: usize> {
+    generator: Arc<dyn DynSample<D>>,
+}
+
+impl Generator1D for DynGenerator<1> {}
+impl Generator2D for DynGenerator<2> {}
+impl Generator3D for DynGenerator<3> {}
+impl Generator4D for DynGenerator<4> {}
+
+impl<const D: usize> DynGenerator<D> {
+    /// Create a new type-erased generator from the given `generator`.
+    #[inline]
+    pub fn new<G>(generator: G) -> Self
+    where
+        G: Generator<D> + Send + Sync + 'static,
+    {
+        Self {
+            generator: Arc::new(generator),
+        }
+    }
+}
+
+impl<const D: usize> Generator<D> for DynGenerator<D> {
+    #[inline]
+    fn sample(&self, point: [f64; D]) -> f64 {
+        self.generator.sample_dyn(point)
+    }
+
+    #[inline]
+    fn sample_many(&self, points: &[[f64; D]], values: &mut [f64]) {
+        self.generator.sample_many_dyn(points, values)
+    }
+
+    #[inline]
+    fn into_dyn(self) -> DynGenerator<D> {
+        self
+    }
+}
+
+impl<const D: usize> fmt::Debug for DynGenerator<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynGenerator")
+            .field("dimensions", &D)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::macro_map::jungle_noise::generator::Generator;
+    use crate::macro_map::jungle_noise::source::Source;
+
+    #[test]
+    fn erased_generators_sample_like_the_wrapped_generator() {
+        let generator = Source::<3>::simplex(42).fbm(4, 0.01, 2.0, 0.5).abs();
+        let erased = generator.clone().into_dyn();
+        for i in 0..256 {
+            let point = [i as f64 * 1.3 - 100.0, i as f64 * 0.7, i as f64 * -2.1];
+            assert_eq!(erased.sample(point), generator.sample(point));
+        }
+
+        let points: Vec<[f64; 3]> = (0..64).map(|i| [i as f64, 0.5, -(i as f64)]).collect();
+        let mut expected = vec![0.0; points.len()];
+        let mut values = vec![0.0; points.len()];
+        generator.sample_many(&points, &mut expected);
+        erased.sample_many(&points, &mut values);
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn clones_share_their_output() {
+        let erased = Source::<2>::worley(7).scale([0.05; 2]).into_dyn();
+        let clone = erased.clone();
+        // adapting a clone leaves the pipeline it shares untouched
+        let adapted = clone.clone().mul(2.0).into_dyn();
+        for i in 0..256 {
+            let point = [i as f64 * 0.9, i as f64 * -1.7];
+            assert_eq!(clone.sample(point), erased.sample(point));
+            assert_eq!(adapted.sample(point), 2.0 * erased.sample(point));
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use crate::macro_map::jungle_noise::adapters;
+use crate::macro_map::jungle_noise::dyn_generator::DynGenerator;
+use crate::macro_map::jungle_noise::utils::range::RangeAnalysis;
 use std::marker::Sized;
 
 /// A trait for building a coherent noise generation pipeline.
@@ -20,10 +22,10 @@ use std::marker::Sized;
 /// generator for simplex noise is created. Note how the constant generic parameter `D` represents the
 /// dimensionality of the input is inferred:
 ///
-/// [`Source`]: crate::Source
+/// [`Source`]: crate::macro_map::jungle_noise::source::Source
 ///
 /// ```
-/// # use libnoise::{Source, Generator};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
 /// let generator = Source::simplex(42);        // create a generator
 /// let value = generator.sample([0.2, 0.5]);   // sample the generator at [0.2, 0.5]
 /// ```
@@ -32,7 +34,7 @@ use std::marker::Sized;
 /// ways:
 ///
 /// ```
-/// # use libnoise::{Source, Generator, Generator4D};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator4D}, source::Source};
 /// // create a complex generator by chaining adapters
 /// let generator = Source::simplex(42)     // create a simplex noise generator
 ///     .fbm(3, 0.013, 2.0, 0.5)            // apply fractal brownian motion
@@ -65,7 +67,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// // Create a generator, here simplex noise.
     /// let generator = Source::simplex(42);
     ///
@@ -77,6 +79,42 @@ pub trait Generator<const D: usize>: Sized {
     /// ```
     fn sample(&self, point: [f64; D]) -> f64;
 
+    /// Samples the generator at each of the given `points` and writes the resulting values into
+    /// `values`.
+    ///
+    /// This is equivalent to calling [`sample()`] for every point, and the results are
+    /// identical. Generators which can process many points more efficiently than one at a time
+    /// may override this method. [`NoiseBuffer`] fills its buffer using this method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` and `values` differ in length.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
+    /// let generator = Source::simplex(42);
+    ///
+    /// let points = [[0.2, 0.5], [1.3, 0.7], [4.1, 2.2]];
+    /// let mut values = [0.0; 3];
+    /// generator.sample_many(&points, &mut values);
+    ///
+    /// assert_eq!(values[1], generator.sample([1.3, 0.7]));
+    /// ```
+    ///
+    /// [`sample()`]: Generator::sample
+    /// [`NoiseBuffer`]: crate::macro_map::jungle_noise::utils::noisebuf::NoiseBuffer
+    #[inline]
+    fn sample_many(&self, points: &[[f64; D]], values: &mut [f64]) {
+        assert_eq!(points.len(), values.len());
+        for (value, &point) in values.iter_mut().zip(points) {
+            *value = self.sample(point);
+        }
+    }
+
     /// Create a generator which scales input points before passing them to the underlying generator.
     ///
     /// Takes a scale factor for each dimension of the input space and crates a generator which scales
@@ -87,7 +125,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -113,7 +151,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -129,6 +167,46 @@ pub trait Generator<const D: usize>: Sized {
         adapters::Translate::new(self, translation)
     }
 
+    /// Create a generator randomly displacing the input of the underlying generator along every
+    /// axis.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except each
+    /// coordinate of the input point is first displaced by fractal simplex noise. Every axis is
+    /// displaced by its own noise, seeded with `seed` incremented by the index of the axis. The
+    /// displacement noise is sampled at the given `frequency` with `roughness` octaves, a lacunarity
+    /// of 2 and a persistence of 0.5, and is scaled by `power`, which is the largest distance by
+    /// which a point can be displaced.
+    ///
+    /// In contrast to [`displace_x()`] and similar adapters, which displace a single axis by an
+    /// arbitrary generator, this adapter distorts the entire input space, which makes the output of
+    /// the underlying generator appear more turbulent.
+    ///
+    /// [`displace_x()`]: Generator2D::displace_x
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
+    /// let point = [0.2, 0.5];
+    ///
+    /// let generator = Source::simplex(42)             // build a generator
+    ///     .turbulence(43, 2.0, 0.25, 3);              // apply the adapter
+    ///
+    /// let value = generator.sample(point);            // sample the generator
+    /// ```
+    #[inline]
+    fn turbulence(
+        self,
+        seed: u64,
+        frequency: f64,
+        power: f64,
+        roughness: u32,
+    ) -> adapters::Turbulence<D, Self> {
+        adapters::Turbulence::new(self, seed, frequency, power, roughness)
+    }
+
     /// Create a generator which negates the results of the underlying generator.
     ///
     /// Creates a generator which is exactly the same as the underlying generator, except it changes
@@ -139,7 +217,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -164,7 +242,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -190,7 +268,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -215,7 +293,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -240,7 +318,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -267,7 +345,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -294,7 +372,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -321,7 +399,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -336,6 +414,118 @@ pub trait Generator<const D: usize>: Sized {
         adapters::Clamp::new(self, min, max)
     }
 
+    /// Create a generator remapping results of the underlying generator along a smooth curve.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the result
+    /// is remapped along a curve passing through each of the given `control_points`, which are
+    /// pairs of an input and the corresponding output value. Between control points, the curve
+    /// is a monotone cubic spline, so it never overshoots: where the control points rise or fall,
+    /// so does the curve. Results below the lowest or above the highest input are mapped to the
+    /// output of that control point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than two control points are supplied, or if two control points share the
+    /// same input.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
+    /// let point = [0.2, 0.5];
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .curve(&[                           // apply the adapter
+    ///         (-1.0, -1.0),                   // deep ocean
+    ///         (-0.2, -0.1),                   // shallow continental shelf
+    ///         (0.1, 0.05),                    // flat coastal lowlands
+    ///         (1.0, 1.0),                     // mountains
+    ///     ]);
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    /// ```
+    #[inline]
+    fn curve(self, control_points: &[(f64, f64)]) -> adapters::Curve<D, Self> {
+        adapters::Curve::new(self, control_points)
+    }
+
+    /// Create a generator mapping results of the underlying generator onto terrace-like steps.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the result
+    /// is mapped onto terraces between each pair of adjacent `control_points`. Within a terrace,
+    /// the result stays close to the lower control point before rising steeply towards the upper
+    /// one, forming plateaus with cliffs. If `invert` is true, the terraces are flipped, such
+    /// that the result rises steeply from the lower control point before flattening out below
+    /// the upper one. Results outside the control points are clamped to the nearest one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than two distinct control points are supplied.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
+    /// let point = [0.2, 0.5];
+    ///
+    /// let generator = Source::simplex(42)                 // build a generator
+    ///     .terrace(&[-1.0, -0.2, 0.3, 0.6, 1.0], false);  // apply the adapter
+    ///
+    /// let value = generator.sample(point);                // sample the generator
+    /// ```
+    #[inline]
+    fn terrace(self, control_points: &[f64], invert: bool) -> adapters::Terrace<D, Self> {
+        adapters::Terrace::new(self, control_points, invert)
+    }
+
+    /// Create a generator linearly remapping results of the underlying generator from one interval
+    /// onto another.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the result
+    /// is linearly mapped such that `from[0]` becomes `to[0]` and `from[1]` becomes `to[1]`. The
+    /// remapped result is clamped to the target interval, so values outside of `from` do not escape
+    /// the target range. This makes it possible to bring generators with different ranges into a
+    /// common range, or into a physical range such as a temperature in degrees.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
+    /// let point = [0.2, 0.5];
+    ///
+    /// let generator = Source::simplex(42)                 // build a generator
+    ///     .remap([-1.0, 1.0], [-30.0, 50.0]);             // apply the adapter
+    ///
+    /// let value = generator.sample(point);                // sample the generator
+    ///
+    /// let expected = (Source::simplex(42).sample(point) + 1.0) * 0.5 * 80.0 - 30.0;
+    /// assert!((value - expected).abs() < 1e-12);
+    /// ```
+    #[inline]
+    fn remap(self, from: [f64; 2], to: [f64; 2]) -> adapters::Remap<D, Self> {
+        adapters::Remap::new(self, from, to)
+    }
+
+    /// Create a generator mapping results of the underlying generator onto the [0, 1] range, based
+    /// on the given empirical `range` of the underlying generator.
+    ///
+    /// This is equivalent to [`remap()`] from the observed minimum and maximum of `range` onto
+    /// [0, 1]. Values outside of the observed range are clamped. For details on how to obtain the
+    /// range of a generator, see [`RangeAnalysis`].
+    ///
+    /// [`remap()`]: Generator::remap
+    /// [`RangeAnalysis`]: crate::macro_map::jungle_noise::utils::range::RangeAnalysis
+    #[inline]
+    fn normalize(self, range: &RangeAnalysis) -> adapters::Remap<D, Self> {
+        adapters::Remap::new(self, [range.min, range.max], [0.0, 1.0])
+    }
+
     /// Create a generator applying the supplied closure to results of the underlying generator.
     ///
     /// Creates a generator which is exactly the same as the underlying generator, except the result
@@ -346,7 +536,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     /// let closure = |x| x * x;
     ///
@@ -376,7 +566,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -405,7 +595,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -434,7 +624,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -463,7 +653,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -493,7 +683,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -542,7 +732,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let octaves = 6;
@@ -614,7 +804,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let octaves = 6;
@@ -690,7 +880,7 @@ pub trait Generator<const D: usize>: Sized {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let octaves = 6;
@@ -733,165 +923,561 @@ pub trait Generator<const D: usize>: Sized {
         adapters::RidgedMulti::new(self, octaves, frequency, lacunarity, attenuation)
     }
 
-    /// Create a generator blending the underlying generator with a given other generator based on the
-    /// value supplied by a control-generator.
+    /// Create a generator applying a hybrid multifractal on the underlying generator.
     ///
-    /// This adapter takes two generators, `other` and `control`, as parameters. The generator `control`
-    /// is expected to produce values in the [-1, 1] range. Based on that value, the results of the
-    /// underlying generator and `other` are blended. If the value is -1, the result is equal to that of
-    /// the underlying generator. If the value is 1, the result is equal to that of `other`. For other
-    /// `control` values, the result is the linear interpolation between the results of the underlying
-    /// generator and `other`.
+    /// This adapter is very similar to the [`fbm()`] adapter. A core difference is that every octave
+    /// after the first is weighted by the results of the previous octaves. The weight starts as the
+    /// result of the first octave, rescaled to the [0, 1] range, and is multiplied by the result of
+    /// each subsequent octave, rescaled to the [0, 2] range, clamping it to at most 1. As a result,
+    /// low-lying regions stay smooth, while high regions accumulate detail. The result is the sum of
+    /// results for each octave, normalized as done by the [`fbm()`] adapter. For details, see the
+    /// [`fbm()`] adapter.
     ///
     /// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
     /// <strong>Warning:</strong>
-    /// This adapter assumes that the control generator produces values in the [-1, 1] range. The
-    /// generator created by this adapter will not produce correct results, if this contract is violated.
+    /// This adapter assumes that the underlying generator produces values in the [-1, 1] range. This is
+    /// because the adapter has no knowledge of the theoretical bounds of the underlying generator and
+    /// must therefore assume a range for the computation of weights. The generator created by this
+    /// adapter will not produce correct results, if this contract is violated.
+    /// </p>
+    ///
+    /// <p style="background:rgba(122,186,255,0.16);padding:0.75em;">
+    /// <strong>Note:</strong>
+    /// Typically, a desireable value for `lacunarity` is 2 while a desireable value for `persistence` lies
+    /// somewhere between 0 and 1.
     /// </p>
     ///
+    /// [`fbm()`]: Generator::fbm
+    ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
+    /// let octaves = 6;
+    /// let frequency = 1.0;
+    /// let lacunarity = 2.0;
+    /// let persistence = 0.5;
+    ///
     /// // build a generator using the adapter
     /// let generator = Source::simplex(42)
-    ///     .blend(Source::simplex(43), Source::simplex(44));
+    ///     .hybridmulti(octaves, frequency, lacunarity, persistence);
     ///
     /// // sample the generator
     /// let value = generator.sample(point);
     ///
     /// // compute manually for the given point to illustrate
-    /// let a = Source::simplex(42).sample(point);
-    /// let b = Source::simplex(43).sample(point);
-    /// let t = Source::simplex(44).sample(point) * 0.5 + 0.5;
-    /// let expected = a + t * (b - a);
+    /// let underlying = Source::simplex(42);
+    /// let mut freq = frequency;
+    /// let mut expected = underlying.sample(point.map(|x| x * freq));
+    /// let mut weight = (expected + 1.0) * 0.5;
+    /// let mut amp = 1.0;
+    /// for _ in 1..octaves {
+    ///     freq *= lacunarity;
+    ///     amp *= persistence;
+    ///     let tmp = underlying.sample(point.map(|x| x * freq));
+    ///     expected += amp * weight * tmp;
+    ///     weight = (weight * (tmp + 1.0)).min(1.0);
+    /// }
+    /// expected /= (0..octaves).fold(0.0, |acc, octave| acc + persistence.powi(octave as i32));
     ///
     /// assert!(value - expected < f64::EPSILON);
     /// ```
     #[inline]
-    fn blend<G, GC>(self, other: G, control: GC) -> adapters::Blend<D, Self, G, GC>
-    where
-        G: Generator<D>,
-        GC: Generator<D>,
-    {
-        adapters::Blend::new(self, other, control)
+    fn hybridmulti(
+        self,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    ) -> adapters::HybridMulti<D, Self> {
+        adapters::HybridMulti::new(self, octaves, frequency, lacunarity, persistence)
     }
 
-    /// Create a generator selecting the result of either the underlying generator or that of a given
-    /// other generator based on whether the value supplied by a control-generator lies within the
-    /// provided interval.
+    /// Create a generator applying heterogeneous terrain noise on the underlying generator.
     ///
-    /// This adapter takes two generators, `other` and `control`, as well as an interval defined by
-    /// `selection_min` and `selection_max`, as parameters. If the value produced by generator `control`
-    /// lies within the provided interval, produce the result of the underlying generator. Otherwise,
-    /// produce the result of `other`.
+    /// This adapter is very similar to the [`fbm()`] adapter. A core difference is that the result of
+    /// every octave after the first is scaled by the normalized sum of all previous octaves, rescaled
+    /// to the [0, 1] range. As a result, the roughness of the result varies with its altitude: valleys
+    /// stay smooth, while peaks become rough. The result is the sum of results for each octave,
+    /// normalized by the sum of amplitudes. For details, see the [`fbm()`] adapter.
+    ///
+    /// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+    /// <strong>Warning:</strong>
+    /// This adapter assumes that the underlying generator produces values in the [-1, 1] range. This is
+    /// because the adapter has no knowledge of the theoretical bounds of the underlying generator and
+    /// must therefore assume a range for the rescaling of the altitude. The generator created by this
+    /// adapter will not produce correct results, if this contract is violated.
+    /// </p>
+    ///
+    /// <p style="background:rgba(122,186,255,0.16);padding:0.75em;">
+    /// <strong>Note:</strong>
+    /// Typically, a desireable value for `lacunarity` is 2 while a desireable value for `persistence` lies
+    /// somewhere between 0 and 1.
+    /// </p>
+    ///
+    /// [`fbm()`]: Generator::fbm
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let point = [0.2, 0.5];
     ///
+    /// let octaves = 6;
+    /// let frequency = 1.0;
+    /// let lacunarity = 2.0;
+    /// let persistence = 0.5;
+    ///
     /// // build a generator using the adapter
     /// let generator = Source::simplex(42)
-    ///     .select(Source::simplex(43), Source::simplex(44), -0.3, 0.1);
+    ///     .heteroterrain(octaves, frequency, lacunarity, persistence);
     ///
     /// // sample the generator
     /// let value = generator.sample(point);
     ///
     /// // compute manually for the given point to illustrate
-    /// let expected = match Source::simplex(44).sample(point) {
-    ///     x if -0.3 <= x && x <= 0.1 => Source::simplex(42).sample(point),
-    ///     _ => Source::simplex(43).sample(point),
-    /// };
+    /// let underlying = Source::simplex(42);
+    /// let mut freq = frequency;
+    /// let mut expected = underlying.sample(point.map(|x| x * freq));
+    /// let mut total_amp = 1.0;
+    /// let mut amp = 1.0;
+    /// for _ in 1..octaves {
+    ///     freq *= lacunarity;
+    ///     amp *= persistence;
+    ///     let altitude = (expected / total_amp + 1.0) * 0.5;
+    ///     expected += amp * altitude * underlying.sample(point.map(|x| x * freq));
+    ///     total_amp += amp;
+    /// }
+    /// expected /= total_amp;
     ///
     /// assert!(value - expected < f64::EPSILON);
     /// ```
     #[inline]
-    fn select<G, GC>(
+    fn heteroterrain(
         self,
-        other: G,
-        control: GC,
-        selection_min: f64,
-        selection_max: f64,
-    ) -> adapters::Select<D, Self, G, GC>
-    where
-        G: Generator<D>,
-        GC: Generator<D>,
-    {
-        adapters::Select::new(self, other, control, selection_min, selection_max)
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    ) -> adapters::HeteroTerrain<D, Self> {
+        adapters::HeteroTerrain::new(self, octaves, frequency, lacunarity, persistence)
     }
-}
 
-/// A trait representing the specialization of [`Generator<D>`] for 1-dimensional input spaces.
-///
-/// Anything implementing this trait must also implement [`Generator<1>`]. This trait exists
-/// for two reasons: The first is to provide functions that either only make sense for specific
-/// dimensionalities, or are either too difficult or inefficient to implement in a
-/// dimension-agnostic manner. The second is to bypass certain limitations of constant generics.
-pub trait Generator1D: Generator<1> {
-    /// Create a generator providing the results of the underlying generator after displacing the
-    /// x-coordinate by the result of the provided generator.
+    /// Create a generator applying swiss turbulence on the underlying generator.
     ///
-    /// Creates a generator which is exactly the same as the underlying generator, except the
-    /// x-coordinate of the input point is first displaced by the result of `displacement_generator`
-    /// for that point.
+    /// This adapter is similar to the [`ridgedmulti()`] adapter, in that the result for a given
+    /// octave is 1 subtracted by the absolute value of the underlying generator, and the amplitude
+    /// of following octaves is dampened by the clamped sum of results so far, multiplied by
+    /// `persistence`. Additionally, the input of each octave is displaced along the gradients of
+    /// previous octaves, which erodes ridges into sharp, curved crests. The total result is the sum
+    /// of results for each octave, normalized to the [-1, 1] range. For details, see the [`fbm()`]
+    /// adapter.
+    ///
+    /// As the gradients of previous octaves are required, the underlying generator must implement
+    /// [`GradientGenerator`].
+    ///
+    /// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+    /// <strong>Warning:</strong>
+    /// This adapter assumes that the underlying generator produces values in the [-1, 1] range. The
+    /// generator created by this adapter will not produce correct results, if this contract is violated.
+    /// </p>
+    ///
+    /// <p style="background:rgba(122,186,255,0.16);padding:0.75em;">
+    /// <strong>Note:</strong>
+    /// Typically, a desireable value for `lacunarity` is 2 while a desireable value for `persistence` lies
+    /// somewhere between 0 and 1.
+    /// </p>
+    ///
+    /// [`fbm()`]: Generator::fbm
+    /// [`ridgedmulti()`]: Generator::ridgedmulti
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator1D};
-    /// let mut point = [0.2];
-    ///
-    /// let generator = Source::simplex(42)     // build a generator
-    ///     .displace_x(Source::simplex(43));   // apply the adapter
-    ///
-    /// let value = generator.sample(point);    // sample the generator
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
+    /// let generator = Source::simplex(42).swissturbulence(6, 0.01, 2.0, 0.5);
+    /// let value = generator.sample([20.0, 50.0]);
     ///
-    /// point[0] += Source::simplex(43).sample(point);
-    /// assert_eq!(value, Source::simplex(42).sample(point))
+    /// assert!((-1.0..=1.0).contains(&value));
     /// ```
     #[inline]
-    fn displace_x<GA>(self, displacement_generator: GA) -> adapters::Displace<1, 0, Self, GA>
+    fn swissturbulence(
+        self,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    ) -> adapters::SwissTurbulence<D, Self>
     where
-        GA: Generator<1>,
+        Self: GradientGenerator<D>,
     {
-        adapters::Displace::new(self, displacement_generator)
+        adapters::SwissTurbulence::new(self, octaves, frequency, lacunarity, persistence)
     }
-}
 
-/// A trait representing the specialization of [`Generator<D>`] for 2-dimensional input spaces.
-///
-/// Anything implementing this trait must also implement [`Generator<2>`]. This trait exists
-/// for two reasons: The first is to provide functions that either only make sense for specific
-/// dimensionalities, or are either too difficult or inefficient to implement in a
-/// dimension-agnostic manner. The second is to bypass certain limitations of constant generics.
-pub trait Generator2D: Generator<2> {
-    /// Create a generator which rotates input points before passing them to the underlying generator.
-    ///
-    /// Takes an angle in radians for each unique pair of axes in the input space and crates a
-    /// generator which rotates each input point for the provided angle on the plane spanned by each
-    /// axis pair, before passing it to the underlying generator. The specific plane of rotation
-    /// for each angle is as follows:
+    /// Create a generator applying jordan turbulence on the underlying generator.
     ///
-    /// | plane of rotation | corresponding angle |
-    /// |-------------------|---------------------|
-    /// | `xy`-plane        | `rotation[0]`       |
+    /// The result for a given octave is the square of the underlying generator. The first octave is
+    /// weighted fully, and following octaves are weighted by an amplitude which starts out at 0.8 and
+    /// is multiplied by `persistence` each octave. The input of each octave is displaced along the
+    /// gradients of previous octaves, and its amplitude is dampened where previous octaves are flat.
+    /// As a result, lowlands stay smooth, while slopes and highlands accumulate detail. The total
+    /// result is the sum of results for each octave, normalized to the [-1, 1] range. For details,
+    /// see the [`fbm()`] adapter.
     ///
-    /// # Examples
+    /// As the gradients of previous octaves are required, the underlying generator must implement
+    /// [`GradientGenerator`].
     ///
-    /// Basic usage:
+    /// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+    /// <strong>Warning:</strong>
+    /// This adapter assumes that the underlying generator produces values in the [-1, 1] range. The
+    /// generator created by this adapter will not produce correct results, if this contract is violated.
+    /// </p>
+    ///
+    /// <p style="background:rgba(122,186,255,0.16);padding:0.75em;">
+    /// <strong>Note:</strong>
+    /// Typically, a desireable value for `lacunarity` is 2 while a desireable value for `persistence` lies
+    /// somewhere between 0 and 1.
+    /// </p>
+    ///
+    /// [`fbm()`]: Generator::fbm
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
+    /// let generator = Source::simplex(42).jordanturbulence(6, 0.01, 2.0, 0.5);
+    /// let value = generator.sample([20.0, 50.0]);
+    ///
+    /// assert!((-1.0..=1.0).contains(&value));
+    /// ```
+    #[inline]
+    fn jordanturbulence(
+        self,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    ) -> adapters::JordanTurbulence<D, Self>
+    where
+        Self: GradientGenerator<D>,
+    {
+        adapters::JordanTurbulence::new(self, octaves, frequency, lacunarity, persistence)
+    }
+
+    /// Create a generator blending the underlying generator with a given other generator based on the
+    /// value supplied by a control-generator.
+    ///
+    /// This adapter takes two generators, `other` and `control`, as parameters. The generator `control`
+    /// is expected to produce values in the [-1, 1] range. Based on that value, the results of the
+    /// underlying generator and `other` are blended. If the value is -1, the result is equal to that of
+    /// the underlying generator. If the value is 1, the result is equal to that of `other`. For other
+    /// `control` values, the result is the linear interpolation between the results of the underlying
+    /// generator and `other`.
+    ///
+    /// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+    /// <strong>Warning:</strong>
+    /// This adapter assumes that the control generator produces values in the [-1, 1] range. The
+    /// generator created by this adapter will not produce correct results, if this contract is violated.
+    /// </p>
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
+    /// let point = [0.2, 0.5];
+    ///
+    /// // build a generator using the adapter
+    /// let generator = Source::simplex(42)
+    ///     .blend(Source::simplex(43), Source::simplex(44));
+    ///
+    /// // sample the generator
+    /// let value = generator.sample(point);
+    ///
+    /// // compute manually for the given point to illustrate
+    /// let a = Source::simplex(42).sample(point);
+    /// let b = Source::simplex(43).sample(point);
+    /// let t = Source::simplex(44).sample(point) * 0.5 + 0.5;
+    /// let expected = a + t * (b - a);
+    ///
+    /// assert!(value - expected < f64::EPSILON);
+    /// ```
+    #[inline]
+    fn blend<G, GC>(self, other: G, control: GC) -> adapters::Blend<D, Self, G, GC>
+    where
+        G: Generator<D>,
+        GC: Generator<D>,
+    {
+        adapters::Blend::new(self, other, control)
+    }
+
+    /// Create a generator selecting the result of either the underlying generator or that of a given
+    /// other generator based on whether the value supplied by a control-generator lies within the
+    /// provided interval.
+    ///
+    /// This adapter takes two generators, `other` and `control`, as well as an interval defined by
+    /// `selection_min` and `selection_max`, as parameters. If the value produced by generator `control`
+    /// lies within the provided interval, produce the result of the underlying generator. Otherwise,
+    /// produce the result of `other`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator2D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
+    /// let point = [0.2, 0.5];
+    ///
+    /// // build a generator using the adapter
+    /// let generator = Source::simplex(42)
+    ///     .select(Source::simplex(43), Source::simplex(44), -0.3, 0.1);
+    ///
+    /// // sample the generator
+    /// let value = generator.sample(point);
+    ///
+    /// // compute manually for the given point to illustrate
+    /// let expected = match Source::simplex(44).sample(point) {
+    ///     x if -0.3 <= x && x <= 0.1 => Source::simplex(42).sample(point),
+    ///     _ => Source::simplex(43).sample(point),
+    /// };
+    ///
+    /// assert!(value - expected < f64::EPSILON);
+    /// ```
+    #[inline]
+    fn select<G, GC>(
+        self,
+        other: G,
+        control: GC,
+        selection_min: f64,
+        selection_max: f64,
+    ) -> adapters::Select<D, Self, G, GC>
+    where
+        G: Generator<D>,
+        GC: Generator<D>,
+    {
+        adapters::Select::new(self, other, control, selection_min, selection_max)
+    }
+
+    /// Create a type-erased generator from the underlying generator.
+    ///
+    /// Wraps the underlying generator in a [`DynGenerator<D>`], which hides its concrete type.
+    /// This allows generators built from different sources and adapters to be stored in the same
+    /// field or collection, and pipelines to be chosen at runtime. The erased generator still
+    /// implements [`Generator<D>`], so further adapters can be applied to it.
+    ///
+    /// [`DynGenerator<D>`]: DynGenerator
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{dyn_generator::DynGenerator, generator::Generator, source::Source};
+    /// let point = [0.2, 0.5];
+    ///
+    /// // build a generator and erase its type
+    /// let generator: DynGenerator<2> = Source::simplex(42).fbm(3, 1.0, 2.0, 0.5).into_dyn();
+    ///
+    /// // sample the generator
+    /// let value = generator.sample(point);
+    ///
+    /// assert_eq!(value, Source::simplex(42).fbm(3, 1.0, 2.0, 0.5).sample(point));
+    /// ```
+    #[inline]
+    fn into_dyn(self) -> DynGenerator<D>
+    where
+        Self: Send + Sync + 'static,
+    {
+        DynGenerator::new(self)
+    }
+}
+
+/// A trait for generators which can compute the analytic gradient of their output.
+///
+/// Next to the value of the generator at a given point, this trait provides the partial
+/// derivatives of that value with respect to each input coordinate. The derivatives are computed
+/// analytically alongside the value, so no additional samples are taken as would be the case
+/// with finite differences. This is useful for deriving slopes, shading terrain, or building
+/// fractals which dampen octaves based on the slope of previous ones.
+///
+/// This trait is implemented for the gradient and value noise sources, as well as for adapters
+/// whose underlying generators implement it. Adapters which transform the output through a
+/// closure, such as [`lambda()`], cannot provide a gradient.
+///
+/// [`lambda()`]: Generator::lambda
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, GradientGenerator}, source::Source};
+/// let generator = Source::simplex(42).fbm(4, 0.01, 2.0, 0.5);
+///
+/// // sample the value together with its partial derivatives
+/// let (value, [dx, dy]) = generator.sample_with_gradient([20.0, 50.0]);
+///
+/// assert_eq!(value, generator.sample([20.0, 50.0]));
+/// ```
+pub trait GradientGenerator<const D: usize>: Generator<D> {
+    /// Samples the generator at a given `point` and returns the resulting value together with
+    /// the partial derivatives of the value along each axis of the input space.
+    fn sample_with_gradient(&self, point: [f64; D]) -> (f64, [f64; D]);
+}
+
+/// A trait for generators which can be sampled in single precision.
+///
+/// All generators compute in [`f64`]. When large amounts of noise are generated for output
+/// which is quantized anyway, such as 8-bit textures, the precision of [`f64`] is wasted and
+/// [`sample_f32()`] may be used instead. It evaluates the same noise functions in [`f32`],
+/// sharing the permutation tables and gradient lookup tables with the [`f64`] path, so that the
+/// result is visually equivalent to [`sample()`] for the same generator.
+///
+/// Results of [`sample_f32()`] are not bit-identical to [`sample()`], but they are covered by the
+/// reproducibility guarantee of the crate in their own right: for a fixed seed and point, they
+/// are the same on every platform. As [`f32`] loses precision far from the origin, they should
+/// still not be used for anything that feeds back into world generation.
+///
+/// This trait is implemented for the lattice based sources, that is simplex, perlin, improved
+/// perlin and value noise, as well as for the constant source. Of the adapters, it is
+/// implemented for [`scale()`], [`translate()`], [`add()`], [`mul()`], [`abs()`], [`neg()`] and
+/// [`fbm()`] when their underlying generator implements it.
+///
+/// [`sample_f32()`]: GeneratorF32::sample_f32
+/// [`sample()`]: Generator::sample
+/// [`scale()`]: Generator::scale
+/// [`translate()`]: Generator::translate
+/// [`add()`]: Generator::add
+/// [`mul()`]: Generator::mul
+/// [`abs()`]: Generator::abs
+/// [`neg()`]: Generator::neg
+/// [`fbm()`]: Generator::fbm
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, GeneratorF32}, source::Source};
+/// let generator = Source::simplex(42).fbm(4, 0.01, 2.0, 0.5);
+///
+/// // sample in single precision
+/// let value = generator.sample_f32([20.0, 50.0]);
+///
+/// assert!((value as f64 - generator.sample([20.0, 50.0])).abs() < 1e-4);
+/// ```
+pub trait GeneratorF32<const D: usize>: Generator<D> {
+    /// Samples the generator at a given `point` in single precision and returns the resulting
+    /// value.
+    fn sample_f32(&self, point: [f32; D]) -> f32;
+}
+
+/// A trait representing the specialization of [`Generator<D>`] for 1-dimensional input spaces.
+///
+/// Anything implementing this trait must also implement [`Generator<1>`]. This trait exists
+/// for two reasons: The first is to provide functions that either only make sense for specific
+/// dimensionalities, or are either too difficult or inefficient to implement in a
+/// dimension-agnostic manner. The second is to bypass certain limitations of constant generics.
+pub trait Generator1D: Generator<1> {
+    /// Create a generator providing the results of the underlying generator after displacing the
+    /// x-coordinate by the result of the provided generator.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the
+    /// x-coordinate of the input point is first displaced by the result of `displacement_generator`
+    /// for that point.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator1D}, source::Source};
+    /// let mut point = [0.2];
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .displace_x(Source::simplex(43));   // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    ///
+    /// point[0] += Source::simplex(43).sample(point);
+    /// assert_eq!(value, Source::simplex(42).sample(point))
+    /// ```
+    #[inline]
+    fn displace_x<GA>(self, displacement_generator: GA) -> adapters::Displace<1, 0, Self, GA>
+    where
+        GA: Generator<1>,
+    {
+        adapters::Displace::new(self, displacement_generator)
+    }
+
+    /// Create a generator multiplying results of the underlying generator by a function of the
+    /// x-coordinate of the input point.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the
+    /// result is multiplied by `modulation` evaluated at the x-coordinate of the input point.
+    /// This allows effects which vary along an axis, such as latitude bands, to be composed like
+    /// any other adapter.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator1D}, source::Source};
+    /// let point = [0.2];
+    /// let modulation = |x: f64| 1.0 - x.abs();
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .modulate_x(modulation);            // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    ///
+    /// assert_eq!(value, Source::simplex(42).sample(point) * modulation(point[0]))
+    /// ```
+    #[inline]
+    fn modulate_x<M>(self, modulation: M) -> adapters::Modulate<1, 0, Self, M>
+    where
+        M: Fn(f64) -> f64,
+    {
+        adapters::Modulate::new(self, modulation)
+    }
+}
+
+/// A trait representing the specialization of [`Generator<D>`] for 2-dimensional input spaces.
+///
+/// Anything implementing this trait must also implement [`Generator<2>`]. This trait exists
+/// for two reasons: The first is to provide functions that either only make sense for specific
+/// dimensionalities, or are either too difficult or inefficient to implement in a
+/// dimension-agnostic manner. The second is to bypass certain limitations of constant generics.
+pub trait Generator2D: Generator<2> {
+    /// Create a generator which rotates input points before passing them to the underlying generator.
+    ///
+    /// Takes an angle in radians for each unique pair of axes in the input space and crates a
+    /// generator which rotates each input point for the provided angle on the plane spanned by each
+    /// axis pair, before passing it to the underlying generator. The specific plane of rotation
+    /// for each angle is as follows:
+    ///
+    /// | plane of rotation | corresponding angle |
+    /// |-------------------|---------------------|
+    /// | `xy`-plane        | `rotation[0]`       |
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator2D}, source::Source};
     /// let point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -916,7 +1502,7 @@ pub trait Generator2D: Generator<2> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator2D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator2D}, source::Source};
     /// let mut point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -935,6 +1521,38 @@ pub trait Generator2D: Generator<2> {
         adapters::Displace::new(self, displacement_generator)
     }
 
+    /// Create a generator multiplying results of the underlying generator by a function of the
+    /// x-coordinate of the input point.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the
+    /// result is multiplied by `modulation` evaluated at the x-coordinate of the input point.
+    /// This allows effects which vary along an axis, such as latitude bands, to be composed like
+    /// any other adapter.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator2D}, source::Source};
+    /// let point = [0.2, 0.5];
+    /// let modulation = |x: f64| 1.0 - x.abs();
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .modulate_x(modulation);            // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    ///
+    /// assert_eq!(value, Source::simplex(42).sample(point) * modulation(point[0]))
+    /// ```
+    #[inline]
+    fn modulate_x<M>(self, modulation: M) -> adapters::Modulate<2, 0, Self, M>
+    where
+        M: Fn(f64) -> f64,
+    {
+        adapters::Modulate::new(self, modulation)
+    }
+
     /// Create a generator providing the results of the underlying generator after displacing the
     /// y-coordinate by the result of the provided generator.
     ///
@@ -947,7 +1565,7 @@ pub trait Generator2D: Generator<2> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator2D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator2D}, source::Source};
     /// let mut point = [0.2, 0.5];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -965,6 +1583,79 @@ pub trait Generator2D: Generator<2> {
     {
         adapters::Displace::new(self, displacement_generator)
     }
+
+    /// Create a generator multiplying results of the underlying generator by a function of the
+    /// y-coordinate of the input point.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the
+    /// result is multiplied by `modulation` evaluated at the y-coordinate of the input point.
+    /// This allows effects which vary along an axis, such as latitude bands, to be composed like
+    /// any other adapter.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator2D}, source::Source};
+    /// let point = [0.2, 0.5];
+    /// let modulation = |y: f64| 1.0 - y.abs();
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .modulate_y(modulation);            // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    ///
+    /// assert_eq!(value, Source::simplex(42).sample(point) * modulation(point[1]))
+    /// ```
+    #[inline]
+    fn modulate_y<M>(self, modulation: M) -> adapters::Modulate<2, 1, Self, M>
+    where
+        M: Fn(f64) -> f64,
+    {
+        adapters::Modulate::new(self, modulation)
+    }
+
+    /// Create a generator warping the input of the underlying generator by a vector of generators.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the input
+    /// point is first offset by the results of the generators in `warp`, one for each axis,
+    /// multiplied by `strength`. With more than one iteration, the warp generators are sampled at
+    /// the point offset by the previous iteration, so that the warp itself is warped. A single
+    /// iteration is equivalent to [`displace_x()`] and its siblings applied at once, while two or
+    /// three iterations produce the swirling, folded patterns described by Inigo Quilez. With zero
+    /// iterations, the input point is left as is.
+    ///
+    /// [`displace_x()`]: Generator2D::displace_x
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator2D}, source::Source};
+    /// let point = [0.2, 0.5];
+    ///
+    /// // one warp generator for each axis
+    /// let warp = [Source::simplex(43), Source::simplex(44)];
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .domain_warp(warp, 4.0, 2);         // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    /// ```
+    #[inline]
+    fn domain_warp<GW>(
+        self,
+        warp: [GW; 2],
+        strength: f64,
+        iterations: u32,
+    ) -> adapters::DomainWarp<2, Self, GW>
+    where
+        GW: Generator<2>,
+    {
+        adapters::DomainWarp::new(self, warp, strength, iterations)
+    }
 }
 
 /// A trait representing the specialization of [`Generator<D>`] for 3-dimensional input spaces.
@@ -992,7 +1683,7 @@ pub trait Generator3D: Generator<3> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator3D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator3D}, source::Source};
     /// let point = [0.2, 0.5, 0.3];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -1017,7 +1708,7 @@ pub trait Generator3D: Generator<3> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator3D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator3D}, source::Source};
     /// let mut point = [0.2, 0.5, 0.3];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -1036,6 +1727,38 @@ pub trait Generator3D: Generator<3> {
         adapters::Displace::new(self, displacement_generator)
     }
 
+    /// Create a generator multiplying results of the underlying generator by a function of the
+    /// x-coordinate of the input point.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the
+    /// result is multiplied by `modulation` evaluated at the x-coordinate of the input point.
+    /// This allows effects which vary along an axis, such as latitude bands, to be composed like
+    /// any other adapter.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator3D}, source::Source};
+    /// let point = [0.2, 0.5, 0.3];
+    /// let modulation = |x: f64| 1.0 - x.abs();
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .modulate_x(modulation);            // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    ///
+    /// assert_eq!(value, Source::simplex(42).sample(point) * modulation(point[0]))
+    /// ```
+    #[inline]
+    fn modulate_x<M>(self, modulation: M) -> adapters::Modulate<3, 0, Self, M>
+    where
+        M: Fn(f64) -> f64,
+    {
+        adapters::Modulate::new(self, modulation)
+    }
+
     /// Create a generator providing the results of the underlying generator after displacing the
     /// y-coordinate by the result of the provided generator.
     ///
@@ -1048,7 +1771,7 @@ pub trait Generator3D: Generator<3> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator3D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator3D}, source::Source};
     /// let mut point = [0.2, 0.5, 0.3];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -1067,6 +1790,38 @@ pub trait Generator3D: Generator<3> {
         adapters::Displace::new(self, displacement_generator)
     }
 
+    /// Create a generator multiplying results of the underlying generator by a function of the
+    /// y-coordinate of the input point.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the
+    /// result is multiplied by `modulation` evaluated at the y-coordinate of the input point.
+    /// This allows effects which vary along an axis, such as latitude bands, to be composed like
+    /// any other adapter.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator3D}, source::Source};
+    /// let point = [0.2, 0.5, 0.3];
+    /// let modulation = |y: f64| 1.0 - y.abs();
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .modulate_y(modulation);            // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    ///
+    /// assert_eq!(value, Source::simplex(42).sample(point) * modulation(point[1]))
+    /// ```
+    #[inline]
+    fn modulate_y<M>(self, modulation: M) -> adapters::Modulate<3, 1, Self, M>
+    where
+        M: Fn(f64) -> f64,
+    {
+        adapters::Modulate::new(self, modulation)
+    }
+
     /// Create a generator providing the results of the underlying generator after displacing the
     /// z-coordinate by the result of the provided generator.
     ///
@@ -1079,7 +1834,7 @@ pub trait Generator3D: Generator<3> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator3D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator3D}, source::Source};
     /// let mut point = [0.2, 0.5, 0.3];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -1097,6 +1852,135 @@ pub trait Generator3D: Generator<3> {
     {
         adapters::Displace::new(self, displacement_generator)
     }
+
+    /// Create a generator multiplying results of the underlying generator by a function of the
+    /// z-coordinate of the input point.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the
+    /// result is multiplied by `modulation` evaluated at the z-coordinate of the input point.
+    /// This allows effects which vary along an axis, such as latitude bands, to be composed like
+    /// any other adapter.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator3D}, source::Source};
+    /// let point = [0.2, 0.5, 0.3];
+    /// let modulation = |z: f64| 1.0 - z.abs();
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .modulate_z(modulation);            // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    ///
+    /// assert_eq!(value, Source::simplex(42).sample(point) * modulation(point[2]))
+    /// ```
+    #[inline]
+    fn modulate_z<M>(self, modulation: M) -> adapters::Modulate<3, 2, Self, M>
+    where
+        M: Fn(f64) -> f64,
+    {
+        adapters::Modulate::new(self, modulation)
+    }
+
+    /// Create a generator warping the input of the underlying generator by a vector of generators.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the input
+    /// point is first offset by the results of the generators in `warp`, one for each axis,
+    /// multiplied by `strength`. With more than one iteration, the warp generators are sampled at
+    /// the point offset by the previous iteration, so that the warp itself is warped. A single
+    /// iteration is equivalent to [`displace_x()`] and its siblings applied at once, while two or
+    /// three iterations produce the swirling, folded patterns described by Inigo Quilez. With zero
+    /// iterations, the input point is left as is.
+    ///
+    /// [`displace_x()`]: Generator3D::displace_x
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator3D}, source::Source};
+    /// let point = [0.2, 0.5, 0.3];
+    ///
+    /// // one warp generator for each axis
+    /// let warp = [Source::simplex(43), Source::simplex(44), Source::simplex(45)];
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .domain_warp(warp, 4.0, 2);         // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    /// ```
+    #[inline]
+    fn domain_warp<GW>(
+        self,
+        warp: [GW; 3],
+        strength: f64,
+        iterations: u32,
+    ) -> adapters::DomainWarp<3, Self, GW>
+    where
+        GW: Generator<3>,
+    {
+        adapters::DomainWarp::new(self, warp, strength, iterations)
+    }
+
+    /// Create a 2-dimensional generator which wraps the x-axis around a cylinder before passing
+    /// input points to this generator.
+    ///
+    /// The x-coordinate of each 2-dimensional input point is mapped onto a circle with a
+    /// circumference of `period`, and the resulting point on the cylinder is passed to this
+    /// generator. The result is seamlessly periodic along the x-axis, such that sampling at `x`
+    /// and `x + period` yields the same value. As the circumference equals the period, distances
+    /// along the x-axis are approximately preserved, so features keep their size.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator3D}, source::Source};
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .scale([0.01; 3])                   // scale the cylinder
+    ///     .cylinder(1024.0);                  // apply the adapter
+    ///
+    /// let value = generator.sample([0.0, 0.5]);   // sample the generator
+    /// assert!((value - generator.sample([1024.0, 0.5])).abs() < 1e-9)
+    /// ```
+    #[inline]
+    fn cylinder(self, period: f64) -> adapters::Cylinder<Self> {
+        adapters::Cylinder::new(self, period)
+    }
+
+    /// Create a 2-dimensional generator which maps longitude and latitude onto the unit sphere
+    /// before passing input points to this generator.
+    ///
+    /// Input points are interpreted as `[longitude, latitude]` in radians, with the poles at a
+    /// latitude of `±π/2`. The resulting generator is continuous across the entire surface of
+    /// the sphere, including the antimeridian and the poles, and features are not stretched
+    /// towards the poles. As the sphere has a radius of 1, the generator should typically be
+    /// scaled beforehand to control the size of features.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator3D}, source::Source};
+    /// use std::f64::consts::PI;
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .scale([4.0; 3])                    // scale the sphere
+    ///     .sphere();                          // apply the adapter
+    ///
+    /// let value = generator.sample([0.3, 0.5]);   // sample the generator
+    /// assert!((value - generator.sample([0.3 + 2.0 * PI, 0.5])).abs() < 1e-9)
+    /// ```
+    #[inline]
+    fn sphere(self) -> adapters::Sphere<Self> {
+        adapters::Sphere::new(self)
+    }
 }
 
 /// A trait representing the specialization of [`Generator<D>`] for 4-dimensional input spaces.
@@ -1106,6 +1990,32 @@ pub trait Generator3D: Generator<3> {
 /// dimensionalities, or are either too difficult or inefficient to implement in a
 /// dimension-agnostic manner. The second is to bypass certain limitations of constant generics.
 pub trait Generator4D: Generator<4> {
+    /// Create a 2-dimensional generator which wraps both axes around a torus before passing
+    /// input points to this generator.
+    ///
+    /// Each coordinate of a 2-dimensional input point is mapped onto its own circle with a
+    /// circumference of the corresponding `period`, and the resulting point on the torus is
+    /// passed to this generator. The result is seamlessly periodic along both axes, which is
+    /// useful for tileable textures.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator4D}, source::Source};
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .scale([0.05; 4])                   // scale the torus
+    ///     .torus([256.0, 128.0]);             // apply the adapter
+    ///
+    /// let value = generator.sample([10.0, 20.0]);     // sample the generator
+    /// assert!((value - generator.sample([266.0, 148.0])).abs() < 1e-9)
+    /// ```
+    #[inline]
+    fn torus(self, period: [f64; 2]) -> adapters::Torus<Self> {
+        adapters::Torus::new(self, period)
+    }
+
     /// Create a generator which rotates input points before passing them to the underlying generator.
     ///
     /// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
@@ -1132,7 +2042,7 @@ pub trait Generator4D: Generator<4> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator4D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator4D}, source::Source};
     /// let point = [0.2, 0.5, 0.3, 0.7];
     ///
     /// let generator = Source::simplex(42)             // build a generator
@@ -1157,7 +2067,7 @@ pub trait Generator4D: Generator<4> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator4D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator4D}, source::Source};
     /// let mut point = [0.2, 0.5, 0.3, 0.7];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -1176,6 +2086,38 @@ pub trait Generator4D: Generator<4> {
         adapters::Displace::new(self, displacement_generator)
     }
 
+    /// Create a generator multiplying results of the underlying generator by a function of the
+    /// x-coordinate of the input point.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the
+    /// result is multiplied by `modulation` evaluated at the x-coordinate of the input point.
+    /// This allows effects which vary along an axis, such as latitude bands, to be composed like
+    /// any other adapter.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator4D}, source::Source};
+    /// let point = [0.2, 0.5, 0.3, 0.7];
+    /// let modulation = |x: f64| 1.0 - x.abs();
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .modulate_x(modulation);            // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    ///
+    /// assert_eq!(value, Source::simplex(42).sample(point) * modulation(point[0]))
+    /// ```
+    #[inline]
+    fn modulate_x<M>(self, modulation: M) -> adapters::Modulate<4, 0, Self, M>
+    where
+        M: Fn(f64) -> f64,
+    {
+        adapters::Modulate::new(self, modulation)
+    }
+
     /// Create a generator providing the results of the underlying generator after displacing the
     /// y-coordinate by the result of the provided generator.
     ///
@@ -1188,7 +2130,7 @@ pub trait Generator4D: Generator<4> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator4D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator4D}, source::Source};
     /// let mut point = [0.2, 0.5, 0.3, 0.7];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -1207,6 +2149,38 @@ pub trait Generator4D: Generator<4> {
         adapters::Displace::new(self, displacement_generator)
     }
 
+    /// Create a generator multiplying results of the underlying generator by a function of the
+    /// y-coordinate of the input point.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the
+    /// result is multiplied by `modulation` evaluated at the y-coordinate of the input point.
+    /// This allows effects which vary along an axis, such as latitude bands, to be composed like
+    /// any other adapter.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator4D}, source::Source};
+    /// let point = [0.2, 0.5, 0.3, 0.7];
+    /// let modulation = |y: f64| 1.0 - y.abs();
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .modulate_y(modulation);            // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    ///
+    /// assert_eq!(value, Source::simplex(42).sample(point) * modulation(point[1]))
+    /// ```
+    #[inline]
+    fn modulate_y<M>(self, modulation: M) -> adapters::Modulate<4, 1, Self, M>
+    where
+        M: Fn(f64) -> f64,
+    {
+        adapters::Modulate::new(self, modulation)
+    }
+
     /// Create a generator providing the results of the underlying generator after displacing the
     /// z-coordinate by the result of the provided generator.
     ///
@@ -1219,7 +2193,7 @@ pub trait Generator4D: Generator<4> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator4D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator4D}, source::Source};
     /// let mut point = [0.2, 0.5, 0.3, 0.7];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -1238,6 +2212,38 @@ pub trait Generator4D: Generator<4> {
         adapters::Displace::new(self, displacement_generator)
     }
 
+    /// Create a generator multiplying results of the underlying generator by a function of the
+    /// z-coordinate of the input point.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the
+    /// result is multiplied by `modulation` evaluated at the z-coordinate of the input point.
+    /// This allows effects which vary along an axis, such as latitude bands, to be composed like
+    /// any other adapter.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator4D}, source::Source};
+    /// let point = [0.2, 0.5, 0.3, 0.7];
+    /// let modulation = |z: f64| 1.0 - z.abs();
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .modulate_z(modulation);            // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    ///
+    /// assert_eq!(value, Source::simplex(42).sample(point) * modulation(point[2]))
+    /// ```
+    #[inline]
+    fn modulate_z<M>(self, modulation: M) -> adapters::Modulate<4, 2, Self, M>
+    where
+        M: Fn(f64) -> f64,
+    {
+        adapters::Modulate::new(self, modulation)
+    }
+
     /// Create a generator providing the results of the underlying generator after displacing the
     /// w-coordinate by the result of the provided generator.
     ///
@@ -1250,7 +2256,7 @@ pub trait Generator4D: Generator<4> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator, Generator4D};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator4D}, source::Source};
     /// let mut point = [0.2, 0.5, 0.3, 0.7];
     ///
     /// let generator = Source::simplex(42)     // build a generator
@@ -1268,4 +2274,36 @@ pub trait Generator4D: Generator<4> {
     {
         adapters::Displace::new(self, displacement_generator)
     }
+
+    /// Create a generator multiplying results of the underlying generator by a function of the
+    /// w-coordinate of the input point.
+    ///
+    /// Creates a generator which is exactly the same as the underlying generator, except the
+    /// result is multiplied by `modulation` evaluated at the w-coordinate of the input point.
+    /// This allows effects which vary along an axis, such as latitude bands, to be composed like
+    /// any other adapter.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::{Generator, Generator4D}, source::Source};
+    /// let point = [0.2, 0.5, 0.3, 0.7];
+    /// let modulation = |w: f64| 1.0 - w.abs();
+    ///
+    /// let generator = Source::simplex(42)     // build a generator
+    ///     .modulate_w(modulation);            // apply the adapter
+    ///
+    /// let value = generator.sample(point);    // sample the generator
+    ///
+    /// assert_eq!(value, Source::simplex(42).sample(point) * modulation(point[3]))
+    /// ```
+    #[inline]
+    fn modulate_w<M>(self, modulation: M) -> adapters::Modulate<4, 3, Self, M>
+    where
+        M: Fn(f64) -> f64,
+    {
+        adapters::Modulate::new(self, modulation)
+    }
 }
@@ -0,0 +1,800 @@
+use crate::macro_map::jungle_noise::dyn_generator::DynGenerator;
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D,
+};
+use crate::macro_map::jungle_noise::source::Source;
+use crate::macro_map::jungle_noise::sources::{CellularReturn, DistanceMetric};
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, path::Path};
+
+/// A serializable description of a noise generation pipeline.
+///
+/// A [`NoiseGraph`] is a tree of [`NoiseNode`]s together with the dimensionality of the input
+/// space it is meant to be sampled in. It can be loaded from and written to RON, which allows
+/// pipelines to be tweaked without recompiling. Once parsed, the graph is validated and turned
+/// into a runnable [`DynGenerator<D>`] using [`build()`].
+///
+/// A graph equivalent to `Source::improved_perlin(42).scale([0.01; 3]).fbm(4, 1.0, 2.0, 0.5)`
+/// is written as follows:
+///
+/// ```text
+/// (
+///     dimensions: 3,
+///     root: Fbm(
+///         source: Scale(
+///             source: ImprovedPerlin(seed: 42),
+///             scale: [0.01, 0.01, 0.01],
+///         ),
+///         octaves: 4,
+///         frequency: 1.0,
+///         lacunarity: 2.0,
+///         persistence: 0.5,
+///     ),
+/// )
+/// ```
+///
+/// Closure based generators such as [`custom()`] and [`lambda()`] cannot be described by a graph.
+///
+/// [`DynGenerator<D>`]: DynGenerator
+/// [`build()`]: NoiseGraph::build
+/// [`custom()`]: Source::custom
+/// [`lambda()`]: Generator::lambda
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NoiseGraph {
+    /// The dimensionality of the input space the graph is sampled in.
+    pub dimensions: usize,
+    /// The node producing the final output of the graph.
+    pub root: NoiseNode,
+}
+
+/// A single source or adapter within a [`NoiseGraph`].
+///
+/// Each variant mirrors the function of the same name on [`Source`] or [`Generator`], and takes
+/// the same parameters. Adapters hold the generators they modify as child nodes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum NoiseNode {
+    Constant {
+        value: f64,
+    },
+    Simplex {
+        seed: u64,
+    },
+    Value {
+        seed: u64,
+    },
+    Perlin {
+        seed: u64,
+    },
+    ImprovedPerlin {
+        seed: u64,
+    },
+    Worley {
+        seed: u64,
+    },
+    Cellular {
+        seed: u64,
+        metric: DistanceMetric,
+        return_type: CellularReturn,
+        jitter: f64,
+    },
+    Checkerboard,
+    Scale {
+        source: Box<NoiseNode>,
+        scale: Vec<f64>,
+    },
+    Translate {
+        source: Box<NoiseNode>,
+        translation: Vec<f64>,
+    },
+    Rotate {
+        source: Box<NoiseNode>,
+        rotation: Vec<f64>,
+    },
+    Neg {
+        source: Box<NoiseNode>,
+    },
+    Abs {
+        source: Box<NoiseNode>,
+    },
+    Exp {
+        source: Box<NoiseNode>,
+    },
+    Add {
+        source: Box<NoiseNode>,
+        offset: f64,
+    },
+    Mul {
+        source: Box<NoiseNode>,
+        scale: f64,
+    },
+    Powi {
+        source: Box<NoiseNode>,
+        exponent: i32,
+    },
+    Powf {
+        source: Box<NoiseNode>,
+        exponent: f64,
+    },
+    Clamp {
+        source: Box<NoiseNode>,
+        min: f64,
+        max: f64,
+    },
+    Remap {
+        source: Box<NoiseNode>,
+        from: [f64; 2],
+        to: [f64; 2],
+    },
+    Curve {
+        source: Box<NoiseNode>,
+        control_points: Vec<(f64, f64)>,
+    },
+    Terrace {
+        source: Box<NoiseNode>,
+        control_points: Vec<f64>,
+        invert: bool,
+    },
+    Sum {
+        source: Box<NoiseNode>,
+        other: Box<NoiseNode>,
+    },
+    Product {
+        source: Box<NoiseNode>,
+        other: Box<NoiseNode>,
+    },
+    Min {
+        source: Box<NoiseNode>,
+        other: Box<NoiseNode>,
+    },
+    Max {
+        source: Box<NoiseNode>,
+        other: Box<NoiseNode>,
+    },
+    Power {
+        source: Box<NoiseNode>,
+        other: Box<NoiseNode>,
+    },
+    Fbm {
+        source: Box<NoiseNode>,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    },
+    Billow {
+        source: Box<NoiseNode>,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    },
+    RidgedMulti {
+        source: Box<NoiseNode>,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        attenuation: f64,
+    },
+    HybridMulti {
+        source: Box<NoiseNode>,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    },
+    HeteroTerrain {
+        source: Box<NoiseNode>,
+        octaves: u32,
+        frequency: f64,
+        lacunarity: f64,
+        persistence: f64,
+    },
+    Blend {
+        source: Box<NoiseNode>,
+        other: Box<NoiseNode>,
+        control: Box<NoiseNode>,
+    },
+    Select {
+        source: Box<NoiseNode>,
+        other: Box<NoiseNode>,
+        control: Box<NoiseNode>,
+        selection_min: f64,
+        selection_max: f64,
+    },
+    Displace {
+        source: Box<NoiseNode>,
+        displacement: Box<NoiseNode>,
+        axis: usize,
+    },
+    Turbulence {
+        source: Box<NoiseNode>,
+        seed: u64,
+        frequency: f64,
+        power: f64,
+        roughness: u32,
+    },
+    DomainWarp {
+        source: Box<NoiseNode>,
+        warp: Vec<NoiseNode>,
+        strength: f64,
+        iterations: u32,
+    },
+}
+
+/// An error produced while loading, saving or building a [`NoiseGraph`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum GraphError {
+    /// The graph file could not be read or written.
+    Io(String),
+    /// The description is not valid RON, or contains an unknown node or parameter.
+    Parse(String),
+    /// The graph could not be serialized.
+    Serialize(String),
+    /// The graph was built for a different dimensionality than it declares.
+    DimensionMismatch { expected: usize, found: usize },
+    /// A node was given the wrong number of per-axis parameters.
+    ParameterCount {
+        node: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// A node does not exist for the dimensionality of the graph.
+    UnsupportedDimension { node: &'static str, dimensions: usize },
+    /// A node refers to an axis the input space does not have.
+    InvalidAxis { axis: usize, dimensions: usize },
+    /// A node was given too few control points, or control points sharing the same input.
+    InvalidControlPoints { node: &'static str },
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::Io(message) => write!(f, "failed to access noise graph: {message}"),
+            GraphError::Parse(message) => write!(f, "failed to parse noise graph: {message}"),
+            GraphError::Serialize(message) => {
+                write!(f, "failed to serialize noise graph: {message}")
+            }
+            GraphError::DimensionMismatch { expected, found } => write!(
+                f,
+                "noise graph declares {found} dimensions but was built for {expected}"
+            ),
+            GraphError::ParameterCount {
+                node,
+                expected,
+                found,
+            } => write!(f, "{node} expects {expected} parameters but got {found}"),
+            GraphError::UnsupportedDimension { node, dimensions } => {
+                write!(f, "{node} is not available for {dimensions}D noise")
+            }
+            GraphError::InvalidAxis { axis, dimensions } => {
+                write!(f, "axis {axis} does not exist in {dimensions}D noise")
+            }
+            GraphError::InvalidControlPoints { node } => {
+                write!(f, "{node} requires at least two control points with distinct inputs")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+impl NoiseGraph {
+    /// Create a new graph for a `dimensions`-dimensional input space.
+    pub fn new(dimensions: usize, root: NoiseNode) -> Self {
+        Self { dimensions, root }
+    }
+
+    /// Parse a graph from a RON description.
+    pub fn from_ron(description: &str) -> Result<Self, GraphError> {
+        ron::from_str(description).map_err(|err| GraphError::Parse(err.to_string()))
+    }
+
+    /// Serialize the graph into a human-readable RON description.
+    pub fn to_ron(&self) -> Result<String, GraphError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| GraphError::Serialize(err.to_string()))
+    }
+
+    /// Read and parse a graph from the RON file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GraphError> {
+        let description =
+            fs::read_to_string(path).map_err(|err| GraphError::Io(err.to_string()))?;
+        Self::from_ron(&description)
+    }
+
+    /// Write the graph as RON to the file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GraphError> {
+        fs::write(path, self.to_ron()?).map_err(|err| GraphError::Io(err.to_string()))
+    }
+
+    /// Validate the graph and build a runnable generator from it.
+    ///
+    /// Fails if the graph does not declare `D` dimensions, or if any node is given parameters
+    /// which do not fit a `D`-dimensional input space.
+    pub fn build<const D: usize>(&self) -> Result<DynGenerator<D>, GraphError>
+    where
+        NoiseNode: BuildGenerator<D>,
+    {
+        if self.dimensions != D {
+            return Err(GraphError::DimensionMismatch {
+                expected: D,
+                found: self.dimensions,
+            });
+        }
+        self.root.build()
+    }
+}
+
+/// A trait for turning a [`NoiseNode`] into a generator for a specific dimensionality.
+///
+/// This is implemented for [`NoiseNode`] in 1D to 4D. Typically, [`NoiseGraph::build()`] should
+/// be used instead, as it also checks the declared dimensionality of the graph.
+pub trait BuildGenerator<const D: usize> {
+    fn build(&self) -> Result<DynGenerator<D>, GraphError>;
+}
+
+macro_rules! impl_build {
+    ($dim:literal, $cellular:ident, $rotate:ident, $displace:ident, $domain_warp:ident) => {
+        impl BuildGenerator<$dim> for NoiseNode {
+            fn build(&self) -> Result<DynGenerator<$dim>, GraphError> {
+                let build = |node: &NoiseNode| -> Result<DynGenerator<$dim>, GraphError> {
+                    node.build()
+                };
+                let generator = match self {
+                    NoiseNode::Constant { value } => Source::<$dim>::constant(*value).into_dyn(),
+                    NoiseNode::Simplex { seed } => Source::<$dim>::simplex(*seed).into_dyn(),
+                    NoiseNode::Value { seed } => Source::<$dim>::value(*seed).into_dyn(),
+                    NoiseNode::Perlin { seed } => Source::<$dim>::perlin(*seed).into_dyn(),
+                    NoiseNode::ImprovedPerlin { seed } => {
+                        Source::<$dim>::improved_perlin(*seed).into_dyn()
+                    }
+                    NoiseNode::Worley { seed } => Source::<$dim>::worley(*seed).into_dyn(),
+                    NoiseNode::Cellular {
+                        seed,
+                        metric,
+                        return_type,
+                        jitter,
+                    } => $cellular(*seed, *metric, *return_type, *jitter)?,
+                    NoiseNode::Checkerboard => Source::<$dim>::checkerboard().into_dyn(),
+                    NoiseNode::Scale { source, scale } => build(source)?
+                        .scale(per_axis("Scale", scale)?)
+                        .into_dyn(),
+                    NoiseNode::Translate {
+                        source,
+                        translation,
+                    } => build(source)?
+                        .translate(per_axis("Translate", translation)?)
+                        .into_dyn(),
+                    NoiseNode::Rotate { source, rotation } => $rotate(build(source)?, rotation)?,
+                    NoiseNode::Neg { source } => build(source)?.neg().into_dyn(),
+                    NoiseNode::Abs { source } => build(source)?.abs().into_dyn(),
+                    NoiseNode::Exp { source } => build(source)?.exp().into_dyn(),
+                    NoiseNode::Add { source, offset } => build(source)?.add(*offset).into_dyn(),
+                    NoiseNode::Mul { source, scale } => build(source)?.mul(*scale).into_dyn(),
+                    NoiseNode::Powi { source, exponent } => {
+                        build(source)?.powi(*exponent).into_dyn()
+                    }
+                    NoiseNode::Powf { source, exponent } => {
+                        build(source)?.powf(*exponent).into_dyn()
+                    }
+                    NoiseNode::Clamp { source, min, max } => {
+                        build(source)?.clamp(*min, *max).into_dyn()
+                    }
+                    NoiseNode::Remap { source, from, to } => {
+                        build(source)?.remap(*from, *to).into_dyn()
+                    }
+                    NoiseNode::Curve {
+                        source,
+                        control_points,
+                    } => {
+                        let inputs: Vec<f64> = control_points.iter().map(|point| point.0).collect();
+                        validate_control_points("Curve", &inputs, false)?;
+                        build(source)?.curve(control_points).into_dyn()
+                    }
+                    NoiseNode::Terrace {
+                        source,
+                        control_points,
+                        invert,
+                    } => {
+                        validate_control_points("Terrace", control_points, true)?;
+                        build(source)?.terrace(control_points, *invert).into_dyn()
+                    }
+                    NoiseNode::Sum { source, other } => {
+                        build(source)?.sum(build(other)?).into_dyn()
+                    }
+                    NoiseNode::Product { source, other } => build(source)?
+                        .product(build(other)?)
+                        .into_dyn(),
+                    NoiseNode::Min { source, other } => {
+                        build(source)?.min(build(other)?).into_dyn()
+                    }
+                    NoiseNode::Max { source, other } => {
+                        build(source)?.max(build(other)?).into_dyn()
+                    }
+                    NoiseNode::Power { source, other } => build(source)?
+                        .power(build(other)?)
+                        .into_dyn(),
+                    NoiseNode::Fbm {
+                        source,
+                        octaves,
+                        frequency,
+                        lacunarity,
+                        persistence,
+                    } => build(source)?
+                        .fbm(*octaves, *frequency, *lacunarity, *persistence)
+                        .into_dyn(),
+                    NoiseNode::Billow {
+                        source,
+                        octaves,
+                        frequency,
+                        lacunarity,
+                        persistence,
+                    } => build(source)?
+                        .billow(*octaves, *frequency, *lacunarity, *persistence)
+                        .into_dyn(),
+                    NoiseNode::RidgedMulti {
+                        source,
+                        octaves,
+                        frequency,
+                        lacunarity,
+                        attenuation,
+                    } => build(source)?
+                        .ridgedmulti(*octaves, *frequency, *lacunarity, *attenuation)
+                        .into_dyn(),
+                    NoiseNode::HybridMulti {
+                        source,
+                        octaves,
+                        frequency,
+                        lacunarity,
+                        persistence,
+                    } => build(source)?
+                        .hybridmulti(*octaves, *frequency, *lacunarity, *persistence)
+                        .into_dyn(),
+                    NoiseNode::HeteroTerrain {
+                        source,
+                        octaves,
+                        frequency,
+                        lacunarity,
+                        persistence,
+                    } => build(source)?
+                        .heteroterrain(*octaves, *frequency, *lacunarity, *persistence)
+                        .into_dyn(),
+                    NoiseNode::Blend {
+                        source,
+                        other,
+                        control,
+                    } => build(source)?
+                        .blend(
+                            build(other)?,
+                            build(control)?,
+                        )
+                        .into_dyn(),
+                    NoiseNode::Select {
+                        source,
+                        other,
+                        control,
+                        selection_min,
+                        selection_max,
+                    } => build(source)?
+                        .select(
+                            build(other)?,
+                            build(control)?,
+                            *selection_min,
+                            *selection_max,
+                        )
+                        .into_dyn(),
+                    NoiseNode::Displace {
+                        source,
+                        displacement,
+                        axis,
+                    } => $displace(build(source)?, build(displacement)?, *axis)?,
+                    NoiseNode::Turbulence {
+                        source,
+                        seed,
+                        frequency,
+                        power,
+                        roughness,
+                    } => build(source)?
+                        .turbulence(*seed, *frequency, *power, *roughness)
+                        .into_dyn(),
+                    NoiseNode::DomainWarp {
+                        source,
+                        warp,
+                        strength,
+                        iterations,
+                    } => $domain_warp(
+                        build(source)?,
+                        warp.iter().map(build).collect::<Result<_, _>>()?,
+                        *strength,
+                        *iterations,
+                    )?,
+                };
+                Ok(generator)
+            }
+        }
+    };
+}
+
+impl_build!(1, cellular_1d, rotate_1d, displace_1d, domain_warp_1d);
+impl_build!(2, cellular_2d, rotate_2d, displace_2d, domain_warp_2d);
+impl_build!(3, cellular_3d, rotate_3d, displace_3d, domain_warp_3d);
+impl_build!(4, cellular_4d, rotate_4d, displace_4d, domain_warp_4d);
+
+fn per_axis<const N: usize>(node: &'static str, values: &[f64]) -> Result<[f64; N], GraphError> {
+    values
+        .try_into()
+        .map_err(|_| GraphError::ParameterCount {
+            node,
+            expected: N,
+            found: values.len(),
+        })
+}
+
+fn per_axis_generators<const N: usize>(
+    generators: Vec<DynGenerator<N>>,
+) -> Result<[DynGenerator<N>; N], GraphError> {
+    generators
+        .try_into()
+        .map_err(|generators: Vec<_>| GraphError::ParameterCount {
+            node: "DomainWarp",
+            expected: N,
+            found: generators.len(),
+        })
+}
+
+/// Checks that at least two control points with distinct inputs are given, and unless
+/// `allow_duplicates` is set, that no two control points share an input. The adapters taking
+/// control points would otherwise panic.
+fn validate_control_points(
+    node: &'static str,
+    inputs: &[f64],
+    allow_duplicates: bool,
+) -> Result<(), GraphError> {
+    let mut distinct = inputs.to_vec();
+    distinct.sort_by(f64::total_cmp);
+    distinct.dedup();
+    if distinct.len() < 2 || (!allow_duplicates && distinct.len() != inputs.len()) {
+        return Err(GraphError::InvalidControlPoints { node });
+    }
+    Ok(())
+}
+
+fn cellular_1d(
+    _: u64,
+    _: DistanceMetric,
+    _: CellularReturn,
+    _: f64,
+) -> Result<DynGenerator<1>, GraphError> {
+    Err(GraphError::UnsupportedDimension {
+        node: "Cellular",
+        dimensions: 1,
+    })
+}
+
+fn cellular_2d(
+    seed: u64,
+    metric: DistanceMetric,
+    return_type: CellularReturn,
+    jitter: f64,
+) -> Result<DynGenerator<2>, GraphError> {
+    Ok(Source::cellular(seed, metric, return_type, jitter).into_dyn())
+}
+
+fn cellular_3d(
+    seed: u64,
+    metric: DistanceMetric,
+    return_type: CellularReturn,
+    jitter: f64,
+) -> Result<DynGenerator<3>, GraphError> {
+    Ok(Source::cellular(seed, metric, return_type, jitter).into_dyn())
+}
+
+fn cellular_4d(
+    _: u64,
+    _: DistanceMetric,
+    _: CellularReturn,
+    _: f64,
+) -> Result<DynGenerator<4>, GraphError> {
+    Err(GraphError::UnsupportedDimension {
+        node: "Cellular",
+        dimensions: 4,
+    })
+}
+
+fn rotate_1d(_: DynGenerator<1>, _: &[f64]) -> Result<DynGenerator<1>, GraphError> {
+    Err(GraphError::UnsupportedDimension {
+        node: "Rotate",
+        dimensions: 1,
+    })
+}
+
+fn rotate_2d(generator: DynGenerator<2>, rotation: &[f64]) -> Result<DynGenerator<2>, GraphError> {
+    Ok(generator.rotate(per_axis("Rotate", rotation)?).into_dyn())
+}
+
+fn rotate_3d(generator: DynGenerator<3>, rotation: &[f64]) -> Result<DynGenerator<3>, GraphError> {
+    Ok(generator.rotate(per_axis("Rotate", rotation)?).into_dyn())
+}
+
+fn rotate_4d(generator: DynGenerator<4>, rotation: &[f64]) -> Result<DynGenerator<4>, GraphError> {
+    Ok(generator.rotate(per_axis("Rotate", rotation)?).into_dyn())
+}
+
+fn displace_1d(
+    generator: DynGenerator<1>,
+    displacement: DynGenerator<1>,
+    axis: usize,
+) -> Result<DynGenerator<1>, GraphError> {
+    match axis {
+        0 => Ok(generator.displace_x(displacement).into_dyn()),
+        _ => Err(GraphError::InvalidAxis { axis, dimensions: 1 }),
+    }
+}
+
+fn displace_2d(
+    generator: DynGenerator<2>,
+    displacement: DynGenerator<2>,
+    axis: usize,
+) -> Result<DynGenerator<2>, GraphError> {
+    match axis {
+        0 => Ok(generator.displace_x(displacement).into_dyn()),
+        1 => Ok(generator.displace_y(displacement).into_dyn()),
+        _ => Err(GraphError::InvalidAxis { axis, dimensions: 2 }),
+    }
+}
+
+fn displace_3d(
+    generator: DynGenerator<3>,
+    displacement: DynGenerator<3>,
+    axis: usize,
+) -> Result<DynGenerator<3>, GraphError> {
+    match axis {
+        0 => Ok(generator.displace_x(displacement).into_dyn()),
+        1 => Ok(generator.displace_y(displacement).into_dyn()),
+        2 => Ok(generator.displace_z(displacement).into_dyn()),
+        _ => Err(GraphError::InvalidAxis { axis, dimensions: 3 }),
+    }
+}
+
+fn displace_4d(
+    generator: DynGenerator<4>,
+    displacement: DynGenerator<4>,
+    axis: usize,
+) -> Result<DynGenerator<4>, GraphError> {
+    match axis {
+        0 => Ok(generator.displace_x(displacement).into_dyn()),
+        1 => Ok(generator.displace_y(displacement).into_dyn()),
+        2 => Ok(generator.displace_z(displacement).into_dyn()),
+        3 => Ok(generator.displace_w(displacement).into_dyn()),
+        _ => Err(GraphError::InvalidAxis { axis, dimensions: 4 }),
+    }
+}
+
+fn domain_warp_1d(
+    _: DynGenerator<1>,
+    _: Vec<DynGenerator<1>>,
+    _: f64,
+    _: u32,
+) -> Result<DynGenerator<1>, GraphError> {
+    Err(GraphError::UnsupportedDimension {
+        node: "DomainWarp",
+        dimensions: 1,
+    })
+}
+
+fn domain_warp_2d(
+    generator: DynGenerator<2>,
+    warp: Vec<DynGenerator<2>>,
+    strength: f64,
+    iterations: u32,
+) -> Result<DynGenerator<2>, GraphError> {
+    Ok(generator
+        .domain_warp(per_axis_generators(warp)?, strength, iterations)
+        .into_dyn())
+}
+
+fn domain_warp_3d(
+    generator: DynGenerator<3>,
+    warp: Vec<DynGenerator<3>>,
+    strength: f64,
+    iterations: u32,
+) -> Result<DynGenerator<3>, GraphError> {
+    Ok(generator
+        .domain_warp(per_axis_generators(warp)?, strength, iterations)
+        .into_dyn())
+}
+
+fn domain_warp_4d(
+    _: DynGenerator<4>,
+    _: Vec<DynGenerator<4>>,
+    _: f64,
+    _: u32,
+) -> Result<DynGenerator<4>, GraphError> {
+    Err(GraphError::UnsupportedDimension {
+        node: "DomainWarp",
+        dimensions: 4,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTINENTALNESS: &str = include_str!("../../../assets/noise/continentalness.ron");
+
+    #[test]
+    fn graph_matches_equivalent_pipeline() {
+        let generator = NoiseGraph::from_ron(CONTINENTALNESS)
+            .unwrap()
+            .build::<3>()
+            .unwrap();
+        let expected = Source::improved_perlin(42)
+            .scale([0.01; 3])
+            .fbm(4, 1.0, 2.0, 0.5);
+        let point = [13.0, 27.5, 0.0];
+        assert_eq!(generator.sample(point), expected.sample(point));
+    }
+
+    #[test]
+    fn graph_round_trips_through_ron() {
+        let graph = NoiseGraph::from_ron(CONTINENTALNESS).unwrap();
+        let reparsed = NoiseGraph::from_ron(&graph.to_ron().unwrap()).unwrap();
+        assert_eq!(graph, reparsed);
+    }
+
+    #[test]
+    fn unknown_nodes_are_rejected() {
+        let result = NoiseGraph::from_ron("(dimensions: 2, root: Gabor(seed: 1))");
+        assert!(matches!(result, Err(GraphError::Parse(_))));
+    }
+
+    #[test]
+    fn domain_warp_requires_one_generator_per_axis() {
+        let warp = |count: usize| NoiseNode::DomainWarp {
+            source: Box::new(NoiseNode::Simplex { seed: 1 }),
+            warp: (0..count).map(|seed| NoiseNode::Simplex { seed: seed as u64 }).collect(),
+            strength: 2.0,
+            iterations: 2,
+        };
+        let generator = NoiseGraph::new(2, warp(2)).build::<2>().unwrap();
+        let expected =
+            Source::<2>::simplex(1).domain_warp([Source::simplex(0), Source::simplex(1)], 2.0, 2);
+        assert_eq!(generator.sample([0.3, 1.7]), expected.sample([0.3, 1.7]));
+        assert_eq!(
+            NoiseGraph::new(3, warp(2)).build::<3>().unwrap_err(),
+            GraphError::ParameterCount {
+                node: "DomainWarp",
+                expected: 3,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    fn wrong_dimensionality_is_rejected() {
+        let graph = NoiseGraph::from_ron(CONTINENTALNESS).unwrap();
+        assert_eq!(
+            graph.build::<2>().unwrap_err(),
+            GraphError::DimensionMismatch {
+                expected: 2,
+                found: 3
+            }
+        );
+
+        let scale = NoiseGraph::new(
+            2,
+            NoiseNode::Scale {
+                source: Box::new(NoiseNode::Simplex { seed: 1 }),
+                scale: vec![0.1; 3],
+            },
+        );
+        assert_eq!(
+            scale.build::<2>().unwrap_err(),
+            GraphError::ParameterCount {
+                node: "Scale",
+                expected: 2,
+                found: 3
+            }
+        );
+    }
+}
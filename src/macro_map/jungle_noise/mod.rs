@@ -1,6 +1,25 @@
-mod scale_on_axis;
-mod utils;
-pub(crate) mod generator;
-pub(crate) mod adapters;
-pub(crate) mod sources;
-pub(crate) mod source;
+//! Procedural noise generation, ported from libnoise.
+//!
+//! # Reproducibility
+//!
+//! For a fixed seed and fixed input coordinates, every source and adapter produces the same
+//! output on every platform and across releases. The only tolerated deviation is in the last
+//! bits of transcendental functions such as `sin` or `powf`, which may differ between platforms;
+//! the golden tests in `reproducibility` accept relative deviations of up to `1e-9`. The same
+//! holds for the single precision path of `GeneratorF32`, which the golden tests cover as well.
+//!
+//! Changes which alter the output for any seed, such as fixing a bug in a source or changing the
+//! default parameters of an adapter, are allowed only if they are intentional. They must
+//! re-record the golden fingerprints and state the change in their commit message, as they
+//! change every world generated from an existing seed. Refactors and performance improvements
+//! must not alter output. New sources and adapters must add golden cases.
+
+pub mod utils;
+pub mod generator;
+pub mod dyn_generator;
+pub mod graph;
+pub mod adapters;
+pub mod sources;
+pub mod source;
+#[cfg(test)]
+mod reproducibility;
@@ -0,0 +1,809 @@
+//! Golden tests guarding the reproducibility of every source and adapter.
+//!
+//! Each case samples a generator on a fixed grid at a fixed seed and condenses the samples into
+//! a fingerprint, which is compared against the value recorded in [`GOLDEN`]. For the guarantee
+//! these tests enforce, and which changes may alter output, see the [module documentation]
+//! of `jungle_noise`.
+//!
+//! To record new fingerprints after an intentional change, run the tests with the environment
+//! variable `JUNGLE_NOISE_BLESS` set, and replace [`GOLDEN`] with the printed table.
+//!
+//! [module documentation]: super
+
+use crate::macro_map::jungle_noise::adapters::Cylinder;
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GeneratorF32,
+    GradientGenerator,
+};
+use crate::macro_map::jungle_noise::source::Source;
+use crate::macro_map::jungle_noise::sources::{CellularReturn, DistanceMetric};
+use crate::macro_map::jungle_noise::utils::noisebuf::NoiseBuffer;
+
+const SEED: u64 = 42;
+
+/// The largest relative deviation from a recorded fingerprint which is tolerated. This absorbs
+/// differences in the last bits of transcendental functions between platforms.
+const TOLERANCE: f64 = 1e-9;
+
+/// Samples `generator` on a grid spanning [-3.3, 4.7) along each axis, so that negative
+/// coordinates and several lattice cells are covered, and condenses the samples into a single,
+/// order-sensitive value.
+fn fingerprint<const D: usize, G: Generator<D>>(generator: &G) -> f64 {
+    let resolution = [0, 256, 16, 6, 4][D];
+    let step = 8.0 / resolution as f64;
+    let buf = NoiseBuffer::new_with_transform([resolution; D], [-3.3; D], [step; D], generator);
+    condense(buf.buffer)
+}
+
+/// Like [`fingerprint()`], but condenses the gradient along with each sample.
+fn gradient_fingerprint<const D: usize, G: GradientGenerator<D>>(generator: &G) -> f64 {
+    let resolution = [0, 256, 16, 6, 4][D];
+    let step = 8.0 / resolution as f64;
+    let buf = NoiseBuffer::new_with_transform([resolution; D], [-3.3; D], [step; D], generator);
+    let values = buf
+        .tensor_indices()
+        .flat_map(|index| {
+            let point = std::array::from_fn(|i| -3.3 + index[i] as f64 * step);
+            let (value, gradient) = generator.sample_with_gradient(point);
+            std::iter::once(value).chain(gradient)
+        })
+        .collect();
+    condense(values)
+}
+
+/// Like [`fingerprint()`], but samples through the single precision path.
+fn f32_fingerprint<const D: usize, G: GeneratorF32<D>>(generator: &G) -> f64 {
+    let resolution = [0, 256, 16, 6, 4][D];
+    let step = 8.0 / resolution as f64;
+    let buf = NoiseBuffer::new_with_transform([resolution; D], [-3.3; D], [step; D], generator);
+    let values = buf
+        .tensor_indices()
+        .map(|index| {
+            let point = std::array::from_fn(|i| (-3.3 + index[i] as f64 * step) as f32);
+            generator.sample_f32(point) as f64
+        })
+        .collect();
+    condense(values)
+}
+
+fn condense(values: Vec<f64>) -> f64 {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| (1 + i % 7) as f64 * value)
+        .sum()
+}
+
+/// Records the fingerprint of a case for each listed dimensionality. The closure-like argument
+/// binds a simplex source of the respective dimensionality to serve as the underlying generator.
+/// Cases prefixed with `gradient` also record the gradients of the generator, and cases prefixed
+/// with `f32` sample through the single precision path instead.
+macro_rules! golden {
+    ($cases:ident, $name:literal, [$($dim:literal),*], |$source:ident| $generator:expr) => {
+        golden!(@fingerprint $cases, fingerprint, $name, [$($dim),*], |$source| $generator)
+    };
+    (gradient $cases:ident, $name:literal, [$($dim:literal),*], |$source:ident| $generator:expr) => {
+        golden!(@fingerprint $cases, gradient_fingerprint, $name, [$($dim),*], |$source| $generator)
+    };
+    (f32 $cases:ident, $name:literal, [$($dim:literal),*], |$source:ident| $generator:expr) => {
+        golden!(@fingerprint $cases, f32_fingerprint, $name, [$($dim),*], |$source| $generator)
+    };
+    (@fingerprint $cases:ident, $fingerprint:ident, $name:literal, [$($dim:literal),*], |$source:ident| $generator:expr) => {
+        $({
+            let $source = Source::<$dim>::simplex(SEED);
+            let _ = &$source;
+            $cases.push(($name, $dim, $fingerprint::<$dim, _>(&$generator)));
+        })*
+    };
+}
+
+fn cases() -> Vec<(&'static str, usize, f64)> {
+    let mut cases = Vec::new();
+
+    // sources
+    golden!(cases, "constant", [1, 2, 3, 4], |s| Source::constant(0.3));
+    golden!(cases, "simplex", [1, 2, 3, 4], |s| s);
+    golden!(cases, "value", [1, 2, 3, 4], |s| Source::value(SEED));
+    golden!(cases, "perlin", [1, 2, 3, 4], |s| Source::perlin(SEED));
+    golden!(cases, "improved_perlin", [1, 2, 3, 4], |s| {
+        Source::improved_perlin(SEED)
+    });
+    golden!(cases, "worley", [1, 2, 3, 4], |s| Source::worley(SEED));
+    golden!(cases, "checkerboard", [1, 2, 3, 4], |s| {
+        Source::checkerboard()
+    });
+    golden!(cases, "custom", [1, 2, 3, 4], |s| Source::custom(|point| {
+        point
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (x * (1.0 + 0.5 * i as f64)).sin())
+            .sum::<f64>()
+    }));
+    golden!(cases, "cellular_f2_minus_f1", [2, 3], |s| Source::cellular(
+        SEED,
+        DistanceMetric::Manhattan,
+        CellularReturn::F2MinusF1,
+        0.8
+    ));
+    golden!(cases, "cellular_value", [2, 3], |s| Source::cellular(
+        SEED,
+        DistanceMetric::Chebyshev,
+        CellularReturn::CellValue,
+        0.5
+    ));
+
+    // adapters transforming the input
+    golden!(cases, "scale", [1, 2, 3, 4], |s| s
+        .scale(std::array::from_fn(|i| 0.5 + 0.25 * i as f64)));
+    golden!(cases, "translate", [1, 2, 3, 4], |s| s
+        .translate(std::array::from_fn(|i| 1.5 - i as f64)));
+    golden!(cases, "turbulence", [1, 2, 3, 4], |s| s.turbulence(
+        SEED + 1,
+        0.5,
+        0.8,
+        3
+    ));
+    golden!(cases, "displace_x", [1, 2, 3, 4], |s| s
+        .displace_x(Source::simplex(SEED + 1)));
+    golden!(cases, "displace_y", [2, 3, 4], |s| s
+        .displace_y(Source::simplex(SEED + 1)));
+    golden!(cases, "displace_z", [3, 4], |s| s
+        .displace_z(Source::simplex(SEED + 1)));
+    golden!(cases, "displace_w", [4], |s| s
+        .displace_w(Source::simplex(SEED + 1)));
+    golden!(cases, "modulate_x", [1, 2, 3, 4], |s| s.modulate_x(|x| 1.0 - 0.1 * x));
+    golden!(cases, "modulate_y", [2, 3, 4], |s| s.modulate_y(|y| (0.5 * y).cos()));
+    golden!(cases, "modulate_z", [3, 4], |s| s.modulate_z(|z| 0.5 + 0.1 * z));
+    golden!(cases, "modulate_w", [4], |s| s.modulate_w(|w| w * w));
+    golden!(cases, "rotate", [2, 3, 4], |s| s
+        .rotate(std::array::from_fn(|i| 0.4 + 0.3 * i as f64)));
+    golden!(cases, "domain_warp", [2, 3], |s| s.domain_warp(
+        std::array::from_fn(|i| Source::simplex(SEED + 1 + i as u64)),
+        1.5,
+        2
+    ));
+
+    // adapters lifting the input onto higher-dimensional manifolds
+    cases.push((
+        "cylinder",
+        2,
+        fingerprint(&Source::<3>::simplex(SEED).cylinder(6.0)),
+    ));
+    cases.push((
+        "cylinder",
+        3,
+        fingerprint(&Cylinder::new(Source::<4>::simplex(SEED), 6.0)),
+    ));
+    cases.push((
+        "sphere",
+        2,
+        fingerprint(&Source::<3>::simplex(SEED).sphere()),
+    ));
+    cases.push((
+        "torus",
+        2,
+        fingerprint(&Source::<4>::simplex(SEED).torus([6.0, 4.0])),
+    ));
+
+    // adapters transforming the output
+    golden!(cases, "neg", [1, 2, 3, 4], |s| s.neg());
+    golden!(cases, "abs", [1, 2, 3, 4], |s| s.abs());
+    golden!(cases, "exp", [1, 2, 3, 4], |s| s.exp());
+    golden!(cases, "add", [1, 2, 3, 4], |s| s.add(0.25));
+    golden!(cases, "mul", [1, 2, 3, 4], |s| s.mul(1.5));
+    golden!(cases, "powi", [1, 2, 3, 4], |s| s.powi(3));
+    golden!(cases, "powf", [1, 2, 3, 4], |s| s.abs().powf(1.5));
+    golden!(cases, "clamp", [1, 2, 3, 4], |s| s.clamp(-0.3, 0.4));
+    golden!(cases, "curve", [1, 2, 3, 4], |s| s.curve(&[
+        (-1.0, -1.0),
+        (0.0, 0.4),
+        (0.5, 0.5),
+        (1.0, 1.0)
+    ]));
+    golden!(cases, "terrace", [1, 2, 3, 4], |s| s
+        .terrace(&[-1.0, -0.2, 0.3, 1.0], false));
+    golden!(cases, "terrace_inverted", [1, 2, 3, 4], |s| s
+        .terrace(&[-1.0, -0.2, 0.3, 1.0], true));
+    golden!(cases, "remap", [1, 2, 3, 4], |s| s
+        .remap([-0.5, 0.5], [10.0, 20.0]));
+    golden!(cases, "lambda", [1, 2, 3, 4], |s| s.lambda(|x| x * x - 0.5 * x));
+
+    // adapters combining generators
+    golden!(cases, "sum", [1, 2, 3, 4], |s| s
+        .sum(Source::simplex(SEED + 1)));
+    golden!(cases, "product", [1, 2, 3, 4], |s| s
+        .product(Source::simplex(SEED + 1)));
+    golden!(cases, "min", [1, 2, 3, 4], |s| s
+        .min(Source::simplex(SEED + 1)));
+    golden!(cases, "max", [1, 2, 3, 4], |s| s
+        .max(Source::simplex(SEED + 1)));
+    golden!(cases, "power", [1, 2, 3, 4], |s| s
+        .abs()
+        .add(0.1)
+        .power(Source::simplex(SEED + 1)));
+    golden!(cases, "blend", [1, 2, 3, 4], |s| s
+        .blend(Source::value(SEED), Source::simplex(SEED + 1)));
+    golden!(cases, "select", [1, 2, 3, 4], |s| s.select(
+        Source::value(SEED),
+        Source::simplex(SEED + 1),
+        -0.2,
+        0.3
+    ));
+
+    // fractal adapters
+    golden!(cases, "fbm", [1, 2, 3, 4], |s| s.fbm(5, 0.7, 2.0, 0.5));
+    golden!(cases, "billow", [1, 2, 3, 4], |s| s
+        .billow(5, 0.7, 2.0, 0.5));
+    golden!(cases, "ridgedmulti", [1, 2, 3, 4], |s| s
+        .ridgedmulti(5, 0.7, 2.0, 2.0));
+    golden!(cases, "hybridmulti", [1, 2, 3, 4], |s| s
+        .hybridmulti(5, 0.7, 2.0, 0.5));
+    golden!(cases, "heteroterrain", [1, 2, 3, 4], |s| s
+        .heteroterrain(5, 0.7, 2.0, 0.5));
+    golden!(cases, "swissturbulence", [1, 2, 3, 4], |s| s
+        .swissturbulence(5, 0.7, 2.0, 0.5));
+    golden!(cases, "jordanturbulence", [1, 2, 3, 4], |s| s
+        .jordanturbulence(5, 0.7, 2.0, 0.5));
+
+    // type erasure must not change results
+    golden!(cases, "dyn", [1, 2, 3, 4], |s| s
+        .fbm(5, 0.7, 2.0, 0.5)
+        .into_dyn());
+
+    cases
+}
+
+fn gradient_cases() -> Vec<(&'static str, usize, f64)> {
+    let mut cases = Vec::new();
+    golden!(gradient cases, "gradient_simplex", [1, 2, 3, 4], |s| s);
+    golden!(gradient cases, "gradient_value", [1, 2, 3, 4], |s| Source::value(SEED));
+    golden!(gradient cases, "gradient_perlin", [1, 2, 3, 4], |s| Source::perlin(SEED));
+    golden!(gradient cases, "gradient_improved_perlin", [1, 2, 3, 4], |s| Source::improved_perlin(SEED));
+    golden!(gradient cases, "gradient_constant", [1, 2, 3, 4], |s| Source::constant(0.3));
+
+    // adapters transforming the input
+    golden!(gradient cases, "gradient_scale", [1, 2, 3, 4], |s| s
+        .scale(std::array::from_fn(|i| 0.5 + 0.25 * i as f64)));
+    golden!(gradient cases, "gradient_translate", [1, 2, 3, 4], |s| s
+        .translate(std::array::from_fn(|i| 1.5 - i as f64)));
+    golden!(gradient cases, "gradient_turbulence", [1, 2, 3, 4], |s| s.turbulence(
+        SEED + 1,
+        0.5,
+        0.8,
+        3
+    ));
+    golden!(gradient cases, "gradient_displace_x", [1, 2, 3, 4], |s| s
+        .displace_x(Source::simplex(SEED + 1)));
+    golden!(gradient cases, "gradient_displace_y", [2, 3, 4], |s| s
+        .displace_y(Source::simplex(SEED + 1)));
+    golden!(gradient cases, "gradient_displace_z", [3, 4], |s| s
+        .displace_z(Source::simplex(SEED + 1)));
+    golden!(gradient cases, "gradient_displace_w", [4], |s| s
+        .displace_w(Source::simplex(SEED + 1)));
+    golden!(gradient cases, "gradient_domain_warp", [2, 3], |s| s.domain_warp(
+        std::array::from_fn(|i| Source::simplex(SEED + 1 + i as u64)),
+        1.5,
+        2
+    ));
+
+    // adapters transforming the output
+    golden!(gradient cases, "gradient_neg", [1, 2, 3, 4], |s| s.neg());
+    golden!(gradient cases, "gradient_abs", [1, 2, 3, 4], |s| s.abs());
+    golden!(gradient cases, "gradient_exp", [1, 2, 3, 4], |s| s.exp());
+    golden!(gradient cases, "gradient_add", [1, 2, 3, 4], |s| s.add(0.25));
+    golden!(gradient cases, "gradient_mul", [1, 2, 3, 4], |s| s.mul(1.5));
+    golden!(gradient cases, "gradient_powi", [1, 2, 3, 4], |s| s.powi(3));
+    golden!(gradient cases, "gradient_powf", [1, 2, 3, 4], |s| s.abs().powf(1.5));
+    golden!(gradient cases, "gradient_clamp", [1, 2, 3, 4], |s| s.clamp(-0.3, 0.4));
+    golden!(gradient cases, "gradient_curve", [1, 2, 3, 4], |s| s.curve(&[
+        (-1.0, -1.0),
+        (0.0, 0.4),
+        (0.5, 0.5),
+        (1.0, 1.0)
+    ]));
+    golden!(gradient cases, "gradient_terrace", [1, 2, 3, 4], |s| s
+        .terrace(&[-1.0, -0.2, 0.3, 1.0], false));
+    golden!(gradient cases, "gradient_remap", [1, 2, 3, 4], |s| s
+        .remap([-0.5, 0.5], [10.0, 20.0]));
+
+    // adapters combining generators
+    golden!(gradient cases, "gradient_sum", [1, 2, 3, 4], |s| s
+        .sum(Source::simplex(SEED + 1)));
+    golden!(gradient cases, "gradient_product", [1, 2, 3, 4], |s| s
+        .product(Source::simplex(SEED + 1)));
+    golden!(gradient cases, "gradient_min", [1, 2, 3, 4], |s| s
+        .min(Source::simplex(SEED + 1)));
+    golden!(gradient cases, "gradient_max", [1, 2, 3, 4], |s| s
+        .max(Source::simplex(SEED + 1)));
+    golden!(gradient cases, "gradient_power", [1, 2, 3, 4], |s| s
+        .abs()
+        .add(0.1)
+        .power(Source::simplex(SEED + 1)));
+    golden!(gradient cases, "gradient_blend", [1, 2, 3, 4], |s| s
+        .blend(Source::value(SEED), Source::simplex(SEED + 1)));
+    golden!(gradient cases, "gradient_select", [1, 2, 3, 4], |s| s.select(
+        Source::value(SEED),
+        Source::simplex(SEED + 1),
+        -0.2,
+        0.3
+    ));
+
+    // fractal adapters
+    golden!(gradient cases, "gradient_fbm", [1, 2, 3, 4], |s| s.fbm(5, 0.7, 2.0, 0.5));
+    golden!(gradient cases, "gradient_billow", [1, 2, 3, 4], |s| s
+        .billow(5, 0.7, 2.0, 0.5));
+    golden!(gradient cases, "gradient_ridgedmulti", [1, 2, 3, 4], |s| s
+        .ridgedmulti(5, 0.7, 2.0, 2.0));
+    golden!(gradient cases, "gradient_hybridmulti", [1, 2, 3, 4], |s| s
+        .hybridmulti(5, 0.7, 2.0, 0.5));
+    golden!(gradient cases, "gradient_heteroterrain", [1, 2, 3, 4], |s| s
+        .heteroterrain(5, 0.7, 2.0, 0.5));
+
+    // adapters lifting the input onto higher-dimensional manifolds
+    cases.push((
+        "gradient_cylinder",
+        2,
+        gradient_fingerprint(&Source::<3>::simplex(SEED).cylinder(6.0)),
+    ));
+    cases.push((
+        "gradient_cylinder",
+        3,
+        gradient_fingerprint(&Cylinder::new(Source::<4>::simplex(SEED), 6.0)),
+    ));
+    cases.push((
+        "gradient_sphere",
+        2,
+        gradient_fingerprint(&Source::<3>::simplex(SEED).sphere()),
+    ));
+    cases.push((
+        "gradient_torus",
+        2,
+        gradient_fingerprint(&Source::<4>::simplex(SEED).torus([6.0, 4.0])),
+    ));
+    cases
+}
+
+fn f32_cases() -> Vec<(&'static str, usize, f64)> {
+    let mut cases = Vec::new();
+    golden!(f32 cases, "f32_constant", [1, 2, 3, 4], |s| Source::constant(0.3));
+    golden!(f32 cases, "f32_simplex", [1, 2, 3, 4], |s| s);
+    golden!(f32 cases, "f32_value", [1, 2, 3, 4], |s| Source::value(SEED));
+    golden!(f32 cases, "f32_perlin", [1, 2, 3, 4], |s| Source::perlin(SEED));
+    golden!(f32 cases, "f32_improved_perlin", [1, 2, 3, 4], |s| Source::improved_perlin(SEED));
+    golden!(f32 cases, "f32_scale", [1, 2, 3, 4], |s| s
+        .scale(std::array::from_fn(|i| 0.5 + 0.25 * i as f64)));
+    golden!(f32 cases, "f32_translate", [1, 2, 3, 4], |s| s
+        .translate(std::array::from_fn(|i| 1.5 - i as f64)));
+    golden!(f32 cases, "f32_neg", [1, 2, 3, 4], |s| s.neg());
+    golden!(f32 cases, "f32_abs", [1, 2, 3, 4], |s| s.abs());
+    golden!(f32 cases, "f32_add", [1, 2, 3, 4], |s| s.add(0.25));
+    golden!(f32 cases, "f32_mul", [1, 2, 3, 4], |s| s.mul(1.5));
+    golden!(f32 cases, "f32_fbm", [1, 2, 3, 4], |s| s.fbm(5, 0.7, 2.0, 0.5));
+    cases
+}
+
+#[test]
+fn outputs_match_golden_fingerprints() {
+    let cases: Vec<_> = cases()
+        .into_iter()
+        .chain(gradient_cases())
+        .chain(f32_cases())
+        .collect();
+    if std::env::var_os("JUNGLE_NOISE_BLESS").is_some() {
+        println!("const GOLDEN: &[(&str, usize, f64)] = &[");
+        for (name, dim, fingerprint) in &cases {
+            println!("    ({name:?}, {dim}, {fingerprint:?}),");
+        }
+        println!("];");
+        return;
+    }
+    let mut mismatches = Vec::new();
+    for (name, dim, fingerprint) in &cases {
+        match GOLDEN
+            .iter()
+            .find(|golden| golden.0 == *name && golden.1 == *dim)
+        {
+            Some(&(_, _, expected)) => {
+                if (fingerprint - expected).abs() > TOLERANCE * expected.abs().max(1.0) {
+                    mismatches.push(format!(
+                        "{name} ({dim}D): expected {expected:?}, got {fingerprint:?}"
+                    ));
+                }
+            }
+            None => mismatches.push(format!("{name} ({dim}D): no golden fingerprint recorded")),
+        }
+    }
+    for (name, dim, _) in GOLDEN {
+        if !cases.iter().any(|case| case.0 == *name && case.1 == *dim) {
+            mismatches.push(format!(
+                "{name} ({dim}D): golden fingerprint is no longer checked"
+            ));
+        }
+    }
+    assert!(
+        mismatches.is_empty(),
+        "output changed:\n{}",
+        mismatches.join("\n")
+    );
+}
+
+#[rustfmt::skip]
+const GOLDEN: &[(&str, usize, f64)] = &[
+    ("constant", 1, 305.4000000000002),
+    ("constant", 2, 305.4000000000002),
+    ("constant", 3, 258.3),
+    ("constant", 4, 305.4000000000002),
+    ("simplex", 1, 22.621296985650076),
+    ("simplex", 2, -3.194498315141709),
+    ("simplex", 3, -0.5034594473885088),
+    ("simplex", 4, 25.02764092611849),
+    ("value", 1, -188.3134583358764),
+    ("value", 2, 40.59990199999997),
+    ("value", 3, -28.08468015626636),
+    ("value", 4, -18.800192135424012),
+    ("perlin", 1, 12.834096899414032),
+    ("perlin", 2, 7.810937600000015),
+    ("perlin", 3, -19.437012414750576),
+    ("perlin", 4, 13.3693933693535),
+    ("improved_perlin", 1, 9.053952716328599),
+    ("improved_perlin", 2, -8.147777426949784),
+    ("improved_perlin", 3, -17.41778421958977),
+    ("improved_perlin", 4, -2.550699503048042),
+    ("worley", 1, -405.59687499999995),
+    ("worley", 2, -120.12398294363595),
+    ("worley", 3, 43.654364804045095),
+    ("worley", 4, 165.74880142963195),
+    ("checkerboard", 1, -200.0),
+    ("checkerboard", 2, 10.0),
+    ("checkerboard", 3, -21.0),
+    ("checkerboard", 4, -14.0),
+    ("custom", 1, -121.17835303806235),
+    ("custom", 2, -124.97153944097533),
+    ("custom", 3, 7.984818396555205),
+    ("custom", 4, 107.27354658107969),
+    ("cellular_f2_minus_f1", 2, -282.83124999999995),
+    ("cellular_f2_minus_f1", 3, -361.9687500000001),
+    ("cellular_value", 2, -97.45098039215686),
+    ("cellular_value", 3, 15.90196078431373),
+    ("scale", 1, 16.57098647943187),
+    ("scale", 2, -5.032319174053145),
+    ("scale", 3, 16.94562479095255),
+    ("scale", 4, -29.140240353138253),
+    ("translate", 1, -14.84293764685685),
+    ("translate", 2, -14.7098393278162),
+    ("translate", 3, -16.518657229648955),
+    ("translate", 4, -32.28835646870049),
+    ("turbulence", 1, 58.79578436486202),
+    ("turbulence", 2, -13.77685611880499),
+    ("turbulence", 3, 31.720171721514088),
+    ("turbulence", 4, -34.4503867161645),
+    ("displace_x", 1, -57.44719188082872),
+    ("displace_x", 2, 23.499060110669618),
+    ("displace_x", 3, 1.1478613244714584),
+    ("displace_x", 4, 11.293569794955463),
+    ("displace_y", 2, -36.74191753947403),
+    ("displace_y", 3, -19.945745388703894),
+    ("displace_y", 4, 11.316909034238874),
+    ("displace_z", 3, 7.561700274178648),
+    ("displace_z", 4, 25.30223832779768),
+    ("displace_w", 4, 16.85294695373573),
+    ("modulate_x", 1, 22.83655237150941),
+    ("modulate_x", 2, -0.9379679422859069),
+    ("modulate_x", 3, -5.479236933355068),
+    ("modulate_x", 4, 19.347612771189752),
+    ("modulate_y", 2, -14.130841876428697),
+    ("modulate_y", 3, -2.5530412684027017),
+    ("modulate_y", 4, 21.1995663356154),
+    ("modulate_z", 3, 0.5304692511684419),
+    ("modulate_z", 4, 14.674427623144767),
+    ("modulate_w", 4, 87.73568685537737),
+    ("rotate", 2, 9.05686973395837),
+    ("rotate", 3, -36.81626962620082),
+    ("rotate", 4, 11.761091690971357),
+    ("domain_warp", 2, -28.94060273698832),
+    ("domain_warp", 3, 23.884555048257482),
+    ("cylinder", 2, 2.3068321203211894),
+    ("cylinder", 3, -23.230505000223094),
+    ("sphere", 2, -30.83518236304783),
+    ("torus", 2, -0.8866152644801002),
+    ("neg", 1, -22.621296985650076),
+    ("neg", 2, 3.194498315141709),
+    ("neg", 3, 0.5034594473885088),
+    ("neg", 4, -25.02764092611849),
+    ("abs", 1, 447.5213164128813),
+    ("abs", 2, 476.5913632228623),
+    ("abs", 3, 211.17586634907548),
+    ("abs", 4, 311.90265401791385),
+    ("exp", 1, 1181.5269326479959),
+    ("exp", 2, 1157.1856531048784),
+    ("exp", 3, 912.7081979865609),
+    ("exp", 4, 1116.4184012835806),
+    ("add", 1, 277.1212969856499),
+    ("add", 2, 251.3055016848584),
+    ("add", 3, 214.7465405526114),
+    ("add", 4, 279.5276409261185),
+    ("mul", 1, 33.93194547847505),
+    ("mul", 2, -4.791747472712549),
+    ("mul", 3, -0.7551891710827361),
+    ("mul", 4, 37.541461389177734),
+    ("powi", 1, 7.218919602572183),
+    ("powi", 2, -3.7400853825817117),
+    ("powi", 3, -3.6875360318648487),
+    ("powi", 4, 10.584860271694064),
+    ("powf", 1, 336.3165496280391),
+    ("powf", 2, 356.5165243236182),
+    ("powf", 3, 139.94286143337237),
+    ("powf", 4, 199.46079836305148),
+    ("clamp", 1, 45.291103666852294),
+    ("clamp", 2, 37.91974555252559),
+    ("clamp", 3, 12.782312622069753),
+    ("clamp", 4, 26.918946356554937),
+    ("curve", 1, 233.0827062571682),
+    ("curve", 2, 187.7318081859384),
+    ("curve", 3, 260.61562812852736),
+    ("curve", 4, 308.15495178564595),
+    ("terrace", 1, -83.63534212435245),
+    ("terrace", 2, -135.65602766327032),
+    ("terrace", 3, -91.33429132472129),
+    ("terrace", 4, -55.15436218844145),
+    ("terrace_inverted", 1, 128.8779360956526),
+    ("terrace_inverted", 2, 129.26703103298698),
+    ("terrace_inverted", 3, 90.32737242994426),
+    ("terrace_inverted", 4, 105.20964404067851),
+    ("remap", 1, 15459.768194864282),
+    ("remap", 2, 15253.72286988214),
+    ("remap", 3, 12926.384638350348),
+    ("remap", 4, 15464.610422514084),
+    ("lambda", 1, 255.5131432204598),
+    ("lambda", 2, 277.2734349529577),
+    ("lambda", 3, 102.21478727366714),
+    ("lambda", 4, 125.90323321392411),
+    ("sum", 1, 28.677379128429703),
+    ("sum", 2, -21.648785912483266),
+    ("sum", 3, -22.576864699620003),
+    ("sum", 4, 22.266407809233637),
+    ("product", 1, 5.374784373111884),
+    ("product", 2, -14.417347804540777),
+    ("product", 3, -8.448719138700252),
+    ("product", 4, 8.598996872901399),
+    ("min", 1, -218.37798410417423),
+    ("min", 2, -294.0060489349023),
+    ("min", 3, -173.80814592052567),
+    ("min", 4, -175.23735419260902),
+    ("max", 1, 247.05536323260387),
+    ("max", 2, 272.3572630224193),
+    ("max", 3, 151.23128122090543),
+    ("max", 4, 197.5037620018426),
+    ("power", 1, 1062.4166601721943),
+    ("power", 2, 1122.0454785379181),
+    ("power", 3, 1022.1386902870473),
+    ("power", 4, 1084.2916573067891),
+    ("blend", 1, -89.50942116831472),
+    ("blend", 2, 31.040547064940384),
+    ("blend", 3, -16.500931343520815),
+    ("blend", 4, -5.283293924563184),
+    ("select", 1, -76.21597802481375),
+    ("select", 2, 32.02523816451081),
+    ("select", 3, -22.466039771700885),
+    ("select", 4, 10.939050423450286),
+    ("fbm", 1, -19.42407829759894),
+    ("fbm", 2, -20.126626156152476),
+    ("fbm", 3, 17.172620506189844),
+    ("fbm", 4, 10.66483004306562),
+    ("billow", 1, -206.66185652876314),
+    ("billow", 2, -46.90593384540338),
+    ("billow", 3, -207.7748354496273),
+    ("billow", 4, -629.3401178325576),
+    ("ridgedmulti", 1, -457.7966718550139),
+    ("ridgedmulti", 2, -578.5806862766685),
+    ("ridgedmulti", 3, -399.3622039285403),
+    ("ridgedmulti", 4, 93.75429254897988),
+    ("hybridmulti", 1, -12.772935860765429),
+    ("hybridmulti", 2, -16.71591626343285),
+    ("hybridmulti", 3, 12.86956697170929),
+    ("hybridmulti", 4, 13.919899641562507),
+    ("heteroterrain", 1, -16.33889511437893),
+    ("heteroterrain", 2, -15.70425528431403),
+    ("heteroterrain", 3, 12.742053337304435),
+    ("heteroterrain", 4, 13.875946433793205),
+    ("swissturbulence", 1, -59.30780155315336),
+    ("swissturbulence", 2, -253.72015912010917),
+    ("swissturbulence", 3, -74.91968687629392),
+    ("swissturbulence", 4, 477.45971002671143),
+    ("jordanturbulence", 1, -647.3565346757975),
+    ("jordanturbulence", 2, -445.53371281440934),
+    ("jordanturbulence", 3, -553.8824666043681),
+    ("jordanturbulence", 4, -907.089528266851),
+    ("dyn", 1, -19.42407829759894),
+    ("dyn", 2, -20.126626156152476),
+    ("dyn", 3, 17.172620506189844),
+    ("dyn", 4, 10.66483004306562),
+    ("gradient_simplex", 1, 182.04027134448597),
+    ("gradient_simplex", 2, -248.56778861810227),
+    ("gradient_simplex", 3, -63.82890966235334),
+    ("gradient_simplex", 4, -33.1716381890868),
+    ("gradient_value", 1, -236.2165211219787),
+    ("gradient_value", 2, 75.87155750000005),
+    ("gradient_value", 3, -12.251065503202534),
+    ("gradient_value", 4, -62.57477670684816),
+    ("gradient_perlin", 1, -75.93316131591801),
+    ("gradient_perlin", 2, -30.855257600000048),
+    ("gradient_perlin", 3, 46.3430063638166),
+    ("gradient_perlin", 4, 45.86007929513446),
+    ("gradient_improved_perlin", 1, -60.65936470536934),
+    ("gradient_improved_perlin", 2, -15.429502721639649),
+    ("gradient_improved_perlin", 3, 38.631413165260376),
+    ("gradient_improved_perlin", 4, -38.24953207294644),
+    ("gradient_constant", 1, 307.2000000000002),
+    ("gradient_constant", 2, 306.9000000000002),
+    ("gradient_constant", 3, 259.2),
+    ("gradient_constant", 4, 306.3000000000002),
+    ("gradient_scale", 1, -53.57029206564269),
+    ("gradient_scale", 2, -12.510353033893018),
+    ("gradient_scale", 3, 85.35437022646384),
+    ("gradient_scale", 4, -50.43839141415097),
+    ("gradient_translate", 1, 47.442549455157554),
+    ("gradient_translate", 2, -200.64184644534234),
+    ("gradient_translate", 3, -158.69572985119882),
+    ("gradient_translate", 4, 32.78581619188811),
+    ("gradient_turbulence", 1, 118.33090750740264),
+    ("gradient_turbulence", 2, -618.3568873809045),
+    ("gradient_turbulence", 3, -179.64210366586758),
+    ("gradient_turbulence", 4, -116.92974776229434),
+    ("gradient_displace_x", 1, 62.549074638500706),
+    ("gradient_displace_x", 2, -909.1947166113473),
+    ("gradient_displace_x", 3, -26.672255112148214),
+    ("gradient_displace_x", 4, -429.5259535135711),
+    ("gradient_displace_y", 2, -78.73414043495814),
+    ("gradient_displace_y", 3, 2.306494798676429),
+    ("gradient_displace_y", 4, -494.8269369342077),
+    ("gradient_displace_z", 3, -137.46537160653364),
+    ("gradient_displace_z", 4, -524.9441113578544),
+    ("gradient_displace_w", 4, 44.10462173720261),
+    ("gradient_domain_warp", 2, 7890.463827476799),
+    ("gradient_domain_warp", 3, 1511.2609582934592),
+    ("gradient_neg", 1, -182.04027134448597),
+    ("gradient_neg", 2, 248.56778861810227),
+    ("gradient_neg", 3, 63.82890966235334),
+    ("gradient_neg", 4, 33.1716381890868),
+    ("gradient_abs", 1, 364.4790438963804),
+    ("gradient_abs", 2, 529.0939829160722),
+    ("gradient_abs", 3, 709.0195219699032),
+    ("gradient_abs", 4, 163.6721558138866),
+    ("gradient_exp", 1, 1312.2750349625198),
+    ("gradient_exp", 2, 1013.571929436972),
+    ("gradient_exp", 3, 850.6652760087017),
+    ("gradient_exp", 4, 1156.7859458388305),
+    ("gradient_add", 1, 438.0402713444862),
+    ("gradient_add", 2, 7.182211381897931),
+    ("gradient_add", 3, 152.1710903376465),
+    ("gradient_add", 4, 222.07836181091344),
+    ("gradient_mul", 1, 273.0604070167292),
+    ("gradient_mul", 2, -372.85168292715264),
+    ("gradient_mul", 3, -95.74336449352995),
+    ("gradient_mul", 4, -49.75745728362972),
+    ("gradient_powi", 1, 114.64632879917423),
+    ("gradient_powi", 2, 13.650771580292016),
+    ("gradient_powi", 3, 10.923543626469403),
+    ("gradient_powi", 4, 184.4692222007153),
+    ("gradient_powf", 1, 239.64123423152887),
+    ("gradient_powf", 2, 503.045845018804),
+    ("gradient_powf", 3, 360.45699336978146),
+    ("gradient_powf", 4, 211.75572173366953),
+    ("gradient_clamp", 1, 100.3187331602476),
+    ("gradient_clamp", 2, -170.93033441498184),
+    ("gradient_clamp", 3, -53.05152105853753),
+    ("gradient_clamp", 4, -156.5269007766196),
+    ("gradient_curve", 1, 418.156723109411),
+    ("gradient_curve", 2, -18.725105687731375),
+    ("gradient_curve", 3, -4.308572191921591),
+    ("gradient_curve", 4, 391.595578968329),
+    ("gradient_terrace", 1, 33.119968261066575),
+    ("gradient_terrace", 2, -737.2759354049733),
+    ("gradient_terrace", 3, 61.05960793318845),
+    ("gradient_terrace", 4, -354.3751544362491),
+    ("gradient_remap", 1, 16778.130608027932),
+    ("gradient_remap", 2, 13537.457827088116),
+    ("gradient_remap", 3, 12215.935334947122),
+    ("gradient_remap", 4, 13579.302829966204),
+    ("gradient_sum", 1, 231.39671053988866),
+    ("gradient_sum", 2, -469.42717070036815),
+    ("gradient_sum", 3, -101.66068123816504),
+    ("gradient_sum", 4, 25.57806507051369),
+    ("gradient_product", 1, -16.443850354218306),
+    ("gradient_product", 2, 127.42323611192671),
+    ("gradient_product", 3, -115.72055179255086),
+    ("gradient_product", 4, 73.0551321081713),
+    ("gradient_min", 1, -94.93094025586589),
+    ("gradient_min", 2, -391.3098950241663),
+    ("gradient_min", 3, -525.7528496209186),
+    ("gradient_min", 4, 96.86894199554176),
+    ("gradient_max", 1, 326.3276507957545),
+    ("gradient_max", 2, -123.76486106389885),
+    ("gradient_max", 3, 452.1623945143657),
+    ("gradient_max", 4, 95.38219344137858),
+    ("gradient_power", 1, 1053.0463377853714),
+    ("gradient_power", 2, 259.44586378757634),
+    ("gradient_power", 3, 10.251851171288502),
+    ("gradient_power", 4, 970.5690275577812),
+    ("gradient_blend", 1, -33.17136926891832),
+    ("gradient_blend", 2, -219.02639375446492),
+    ("gradient_blend", 3, -8.276491286321),
+    ("gradient_blend", 4, -60.50915849786014),
+    ("gradient_select", 1, 39.693189659501996),
+    ("gradient_select", 2, -22.963169046758335),
+    ("gradient_select", 3, -116.26330109316446),
+    ("gradient_select", 4, -266.59844276567037),
+    ("gradient_fbm", 1, -29.882628219896127),
+    ("gradient_fbm", 2, -24.61484988927912),
+    ("gradient_fbm", 3, -54.582703663522494),
+    ("gradient_fbm", 4, -102.25818246817659),
+    ("gradient_billow", 1, -319.50653161384895),
+    ("gradient_billow", 2, -286.7065754458252),
+    ("gradient_billow", 3, -855.4640553100942),
+    ("gradient_billow", 4, -856.0843871391554),
+    ("gradient_ridgedmulti", 1, -383.56096689599957),
+    ("gradient_ridgedmulti", 2, -88.93970669251354),
+    ("gradient_ridgedmulti", 3, 106.2233093504569),
+    ("gradient_ridgedmulti", 4, 155.86778871625896),
+    ("gradient_hybridmulti", 1, -27.958678267493895),
+    ("gradient_hybridmulti", 2, 57.68345603929868),
+    ("gradient_hybridmulti", 3, 32.84110302380377),
+    ("gradient_hybridmulti", 4, -74.34330961810846),
+    ("gradient_heteroterrain", 1, -18.643802007184764),
+    ("gradient_heteroterrain", 2, 81.88098633759651),
+    ("gradient_heteroterrain", 3, 18.01587450402036),
+    ("gradient_heteroterrain", 4, -65.22086291246171),
+    ("gradient_cylinder", 2, -107.528441713706),
+    ("gradient_cylinder", 3, 128.9424869028106),
+    ("gradient_sphere", 2, -70.18757744806227),
+    ("gradient_torus", 2, -12.073078147736535),
+    ("f32_constant", 1, 305.4000121355057),
+    ("f32_constant", 2, 305.4000121355057),
+    ("f32_constant", 3, 258.30001026391983),
+    ("f32_constant", 4, 305.4000121355057),
+    ("f32_simplex", 1, 22.621295111253858),
+    ("f32_simplex", 2, -3.194499496370554),
+    ("f32_simplex", 3, -0.5034717535649946),
+    ("f32_simplex", 4, 25.027642039582133),
+    ("f32_value", 1, -188.31345063447952),
+    ("f32_value", 2, 40.59991377592087),
+    ("f32_value", 3, -28.084679901599884),
+    ("f32_value", 4, -18.800199568271637),
+    ("f32_perlin", 1, 12.834125822409987),
+    ("f32_perlin", 2, 7.810948610305786),
+    ("f32_perlin", 3, -19.437009097706323),
+    ("f32_perlin", 4, 13.369392204796895),
+    ("f32_improved_perlin", 1, 9.053935423493385),
+    ("f32_improved_perlin", 2, -8.147776758298278),
+    ("f32_improved_perlin", 3, -17.417785537429154),
+    ("f32_improved_perlin", 4, -2.550702038803138),
+    ("f32_scale", 1, 16.57100481679663),
+    ("f32_scale", 2, -5.032318104058504),
+    ("f32_scale", 3, 16.945621069753543),
+    ("f32_scale", 4, -29.140238800544466),
+    ("f32_translate", 1, -14.842946317978203),
+    ("f32_translate", 2, -14.709839101880789),
+    ("f32_translate", 3, -16.518669034545283),
+    ("f32_translate", 4, -32.288285145536065),
+    ("f32_neg", 1, -22.621295111253858),
+    ("f32_neg", 2, 3.194499496370554),
+    ("f32_neg", 3, 0.5034717535649946),
+    ("f32_neg", 4, -25.027642039582133),
+    ("f32_abs", 1, 447.52130893431604),
+    ("f32_abs", 2, 476.59132862463593),
+    ("f32_abs", 3, 211.1758708897347),
+    ("f32_abs", 4, 311.90255333669484),
+    ("f32_add", 1, 277.1212938427925),
+    ("f32_add", 2, 251.3055015951395),
+    ("f32_add", 3, 214.746528044343),
+    ("f32_add", 4, 279.52764070034027),
+    ("f32_mul", 1, 33.93194271624088),
+    ("f32_mul", 2, -4.791752107441425),
+    ("f32_mul", 3, -0.7552074491001406),
+    ("f32_mul", 4, 37.54146349802613),
+    ("f32_fbm", 1, -19.424129371065646),
+    ("f32_fbm", 2, -20.126656749052927),
+    ("f32_fbm", 3, 17.172621191013604),
+    ("f32_fbm", 4, 10.664832316775573),
+];
@@ -1,6 +1,7 @@
 use super::utils::ptable::Seed;
 use crate::macro_map::jungle_noise::sources::{
-    Checkerboard, Constant, Custom, ImprovedPerlin, Perlin, Simplex, Value, Worley,
+    Cellular, CellularReturn, Checkerboard, Constant, Custom, DistanceMetric, ImprovedPerlin,
+    Perlin, Simplex, Value, Worley,
 };
 
 /// A struct serving as entry point for building generators.
@@ -18,7 +19,7 @@ use crate::macro_map::jungle_noise::sources::{
 /// compile time. Here it is inferred due to the call to [`sample()`] with an argument of size 2:
 ///
 /// ```
-/// # use libnoise::{Source, Generator};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
 /// // create a 2-dimensional simplex noise generator
 /// let generator = Source::simplex(42);
 ///
@@ -31,13 +32,13 @@ use crate::macro_map::jungle_noise::sources::{
 ///
 /// ```
 /// // create a 4-dimensional simplex noise generator
-/// # use libnoise::Source;
+/// # use fungal_jungle::macro_map::jungle_noise::source::Source;
 /// let generator = Source::<4>::simplex(42);
 /// ```
 ///
-/// [`Generator<D>`]: crate::Generator
+/// [`Generator<D>`]: crate::macro_map::jungle_noise::generator::Generator
 /// [`simplex()`]: Source::simplex
-/// [`sample()`]: crate::Generator::sample
+/// [`sample()`]: crate::macro_map::jungle_noise::generator::Generator::sample
 #[derive(Clone, Debug, Default)]
 pub struct Source<const D: usize>;
 
@@ -51,7 +52,7 @@ impl<const D: usize> Source<D> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// // create the generator
     /// let generator = Source::constant(6.9);
     ///
@@ -82,7 +83,7 @@ impl<const D: usize> Source<D> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let generator = Source::simplex(42);
     /// let value = generator.sample([0.2, 0.5]);
     /// ```
@@ -108,7 +109,7 @@ impl<const D: usize> Source<D> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let generator = Source::value(42);
     /// let value = generator.sample([0.2, 0.5]);
     /// ```
@@ -135,7 +136,7 @@ impl<const D: usize> Source<D> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let generator = Source::perlin(42);
     /// let value = generator.sample([0.2, 0.5]);
     /// ```
@@ -166,7 +167,7 @@ impl<const D: usize> Source<D> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let generator = Source::improved_perlin(42);
     /// let value = generator.sample([0.2, 0.5]);
     /// ```
@@ -200,7 +201,7 @@ impl<const D: usize> Source<D> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let generator = Source::worley(42);
     /// let value = generator.sample([0.2, 0.5]);
     /// ```
@@ -208,6 +209,54 @@ impl<const D: usize> Source<D> {
         Worley::new(seed)
     }
 
+    /// Create a generator which produces configurable 2- or 3-dimensional cellular noise.
+    ///
+    /// Like [`worley()`], the input space is divided into a grid lattice and each hypercube is
+    /// assigned a pseudorandom feature point derived from `seed`. Unlike [`worley()`], the
+    /// created generator can measure distances using any [`DistanceMetric`], and can return any
+    /// of the quantities listed in [`CellularReturn`]: the distance to the nearest feature point
+    /// (F1), the distance to the second-nearest feature point (F2), their difference, which
+    /// outlines the cells, or a value or identifier which is constant within each cell.
+    ///
+    /// `jitter` ranges from 0 to 1 and controls how far feature points may stray from the centre
+    /// of their hypercube. A jitter of 0 produces a regular grid of cells, whereas a jitter of 1
+    /// places feature points anywhere within their hypercube. With a euclidean metric, F1 as
+    /// return type and a jitter of 1, the result is identical to [`worley()`].
+    ///
+    /// <p style="background:rgba(122,186,255,0.16);padding:0.75em;">
+    /// <strong>Note:</strong>
+    /// Distances are restricted to 1 and mapped to the range [-1, 1]. Cell values lie in the
+    /// range [-1, 1], whereas cell identifiers are non-negative integers which repeat every 256
+    /// units along each axis.
+    /// </p>
+    ///
+    /// <p style="background:rgba(122,186,255,0.16);padding:0.75em;">
+    /// <strong>Note:</strong>
+    /// Only the own as well as directly and diagonally adjacent hypercubes are considered, as for
+    /// [`worley()`]. In rare cases, this causes F2 to be slightly overestimated.
+    /// </p>
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source, sources::{CellularReturn, DistanceMetric}};
+    /// // cell borders, e.g. for cracks in a dried out desert floor
+    /// let generator = Source::cellular(42, DistanceMetric::Euclidean, CellularReturn::F2MinusF1, 1.0);
+    /// let value = generator.sample([0.2, 0.5]);
+    /// ```
+    ///
+    /// [`worley()`]: Source::worley
+    pub fn cellular(
+        seed: impl Seed,
+        metric: DistanceMetric,
+        return_type: CellularReturn,
+        jitter: f64,
+    ) -> Cellular<D> {
+        Cellular::new(seed, metric, return_type, jitter)
+    }
+
     /// Create a generator which produces an n-dimensional checkerboard pattern.
     ///
     /// The created generator returns n-dimensional checkerboard pattern. That is, the input space
@@ -220,7 +269,7 @@ impl<const D: usize> Source<D> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let generator = Source::checkerboard();
     /// let value = generator.sample([0.2, 0.5]);
     /// ```
@@ -239,7 +288,7 @@ impl<const D: usize> Source<D> {
     /// Basic usage:
     ///
     /// ```
-    /// # use libnoise::{Source, Generator};
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
     /// let generator = Source::custom(|[x, y]| x % 2.0 + (1.0 - y * y) % 3.0);
     /// let value = generator.sample([0.2, 0.5]);
     /// ```
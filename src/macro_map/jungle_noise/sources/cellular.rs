@@ -0,0 +1,141 @@
+use super::functional::{
+    self,
+    cellular::{Cell, CellularReturn, DistanceMetric},
+    constants::PERMUTATION_TABLE_SIZE,
+};
+use crate::macro_map::jungle_noise::{
+    generator::{Generator, Generator2D, Generator3D},
+    utils::ptable::{PermutationTable, Seed},
+};
+
+/// A generator which produces configurable 2- or 3-dimensional cellular noise.
+///
+/// For details, see the documentation of [`cellular()`]. Typically, this struct is not meant
+/// to be used directly. Instead, [`cellular()`] implemented by [`Source`], should be used to
+/// create a cellular noise generator.
+///
+/// # Direct usage of this struct
+///
+/// Direct instantiation of this struct:
+///
+/// ```
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, sources::{Cellular, CellularReturn, DistanceMetric}};
+/// let generator = Cellular::new(42, DistanceMetric::Manhattan, CellularReturn::F2MinusF1, 0.8);
+/// let value = generator.sample([0.2, 0.5]);
+/// ```
+///
+/// [`cellular()`]: crate::macro_map::jungle_noise::source::Source::cellular
+/// [`Source`]: crate::macro_map::jungle_noise::source::Source
+#[derive(Clone, Debug)]
+pub struct Cellular<const D: usize> {
+    permutation_table: PermutationTable,
+    metric: DistanceMetric,
+    return_type: CellularReturn,
+    jitter: f64,
+}
+
+impl Generator2D for Cellular<2> {}
+impl Generator3D for Cellular<3> {}
+
+impl<const D: usize> Cellular<D> {
+    /// Create a new cellular noise generator.
+    #[inline]
+    pub fn new(
+        seed: impl Seed,
+        metric: DistanceMetric,
+        return_type: CellularReturn,
+        jitter: f64,
+    ) -> Self {
+        let permutation_table = PermutationTable::new(seed, PERMUTATION_TABLE_SIZE, true);
+        Self {
+            permutation_table,
+            metric,
+            return_type,
+            jitter: jitter.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Find the cell containing `point`, along with its distances, feature point, identifier and
+    /// value, as well as those of the second-nearest cell. This provides all quantities at once,
+    /// regardless of the configured return type.
+    #[inline]
+    pub fn cell(&self, point: [f64; D]) -> Cell<D> {
+        functional::cellular::cell(&self.permutation_table, point, self.metric, self.jitter)
+    }
+}
+
+impl Generator<2> for Cellular<2> {
+    #[inline]
+    fn sample(&self, point: [f64; 2]) -> f64 {
+        functional::cellular::noise(
+            &self.permutation_table,
+            point,
+            self.metric,
+            self.return_type,
+            self.jitter,
+        )
+    }
+}
+
+impl Generator<3> for Cellular<3> {
+    #[inline]
+    fn sample(&self, point: [f64; 3]) -> f64 {
+        functional::cellular::noise(
+            &self.permutation_table,
+            point,
+            self.metric,
+            self.return_type,
+            self.jitter,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::macro_map::jungle_noise::sources::Worley;
+
+    #[test]
+    fn euclidean_f1_with_full_jitter_matches_worley() {
+        let worley = Worley::<3>::new(7);
+        let cellular = Cellular::<3>::new(7, DistanceMetric::Euclidean, CellularReturn::F1, 1.0);
+        for i in 0..500 {
+            let point = [
+                i as f64 * 0.137 - 20.0,
+                i as f64 * 0.071 + 3.0,
+                i as f64 * -0.05,
+            ];
+            assert_eq!(worley.sample(point), cellular.sample(point));
+        }
+    }
+
+    #[test]
+    fn cells_are_consistent_across_return_types() {
+        for metric in [
+            DistanceMetric::Euclidean,
+            DistanceMetric::Manhattan,
+            DistanceMetric::Chebyshev,
+        ] {
+            let ids = Cellular::<2>::new(3, metric, CellularReturn::CellId, 0.7);
+            let values = Cellular::<2>::new(3, metric, CellularReturn::CellValue, 0.7);
+            for i in 0..400 {
+                let point = [i as f64 * 0.37, (i % 20) as f64 * 0.9];
+                let cell = ids.cell(point);
+                assert!(cell.f1 <= cell.f2);
+                assert_eq!(ids.sample(point), cell.id as f64);
+                assert_eq!(values.sample(point), cell.value);
+                let neighbour = ids.cell(cell.neighbour_point);
+                assert_eq!(neighbour.id, cell.neighbour_id);
+                assert_eq!(neighbour.value, cell.neighbour_value);
+            }
+        }
+    }
+
+    #[test]
+    fn zero_jitter_produces_a_regular_grid() {
+        let grid = Cellular::<2>::new(3, DistanceMetric::Euclidean, CellularReturn::F1, 0.0);
+        let cell = grid.cell([2.5, 7.5]);
+        assert_eq!(cell.f1, 0.0);
+        assert_eq!(cell.feature_point, [2.5, 7.5]);
+    }
+}
@@ -12,7 +12,7 @@ use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generato
 /// [`Checkerboard`] is a unit struct and thus can be used directly:
 ///
 /// ```
-/// # use libnoise::{Checkerboard, Generator};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, sources::Checkerboard};
 /// let value = Checkerboard.sample([0.2, 0.5]);
 /// ```
 ///
@@ -20,13 +20,13 @@ use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generato
 ///
 /// ```
 /// // create
-/// # use libnoise::{Checkerboard, Generator};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, sources::Checkerboard};
 /// let generator = Checkerboard::new();
 /// let value = generator.sample([0.2, 0.5]);
 /// ```
 ///
-/// [`checkerboard()`]: crate::Source::checkerboard
-/// [`Source`]: crate::Source
+/// [`checkerboard()`]: crate::macro_map::jungle_noise::source::Source::checkerboard
+/// [`Source`]: crate::macro_map::jungle_noise::source::Source
 /// [`new()`]: Checkerboard::new
 #[derive(Clone, Copy, Debug)]
 pub struct Checkerboard<const D: usize>;
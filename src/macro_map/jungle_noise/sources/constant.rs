@@ -1,4 +1,7 @@
-use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D};
+use crate::macro_map::jungle_noise::generator::{
+    Generator, Generator1D, Generator2D, Generator3D, Generator4D, GeneratorF32,
+    GradientGenerator,
+};
 
 /// A generator which produces the supplied value for every input point.
 ///
@@ -11,13 +14,13 @@ use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generato
 /// Direct instantiation of this struct:
 ///
 /// ```
-/// # use libnoise::{Constant, Generator};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, sources::Constant};
 /// let generator = Constant::new(6.9);
 /// let value = generator.sample([0.2, 0.5]);
 /// ```
 ///
-/// [`constant()`]: crate::Source::constant
-/// [`Source`]: crate::Source
+/// [`constant()`]: crate::macro_map::jungle_noise::source::Source::constant
+/// [`Source`]: crate::macro_map::jungle_noise::source::Source
 #[derive(Clone, Copy, Debug)]
 pub struct Constant<const D: usize> {
     value: f64,
@@ -41,4 +44,24 @@ impl<const D: usize> Generator<D> for Constant<D> {
     fn sample(&self, _point: [f64; D]) -> f64 {
         self.value
     }
+
+    #[inline]
+    fn sample_many(&self, points: &[[f64; D]], values: &mut [f64]) {
+        assert_eq!(points.len(), values.len());
+        values.fill(self.value);
+    }
+}
+
+impl<const D: usize> GradientGenerator<D> for Constant<D> {
+    #[inline]
+    fn sample_with_gradient(&self, _point: [f64; D]) -> (f64, [f64; D]) {
+        (self.value, [0.0; D])
+    }
+}
+
+impl<const D: usize> GeneratorF32<D> for Constant<D> {
+    #[inline]
+    fn sample_f32(&self, _point: [f32; D]) -> f32 {
+        self.value as f32
+    }
 }
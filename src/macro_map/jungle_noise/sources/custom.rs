@@ -11,13 +11,13 @@ use crate::macro_map::jungle_noise::generator::{Generator, Generator1D, Generato
 /// Direct instantiation of this struct:
 ///
 /// ```
-/// # use libnoise::{Custom, Generator};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, sources::Custom};
 /// let generator = Custom::new(|[x, y]| x % 2.0 + (1.0 - y * y) % 3.0);
 /// let value = generator.sample([0.2, 0.5]);
 /// ```
 ///
-/// [`custom()`]: crate::Source::custom
-/// [`Source`]: crate::Source
+/// [`custom()`]: crate::macro_map::jungle_noise::source::Source::custom
+/// [`Source`]: crate::macro_map::jungle_noise::source::Source
 #[derive(Clone, Copy, Debug)]
 pub struct Custom<const D: usize, N> {
     noise: N,
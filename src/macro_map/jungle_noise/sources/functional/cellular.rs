@@ -0,0 +1,168 @@
+use super::{constants::PERMUTATION_TABLE_SIZE, lattice};
+use crate::macro_map::jungle_noise::utils::ptable::PermutationTable;
+use serde::{Deserialize, Serialize};
+
+/// The metric used to measure the distance between an input point and the feature points of
+/// the surrounding cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DistanceMetric {
+    /// The straight-line (L2) distance, producing round cells.
+    #[default]
+    Euclidean,
+    /// The sum of the distances along each axis (L1), producing diamond-shaped cells.
+    Manhattan,
+    /// The largest distance along any axis (L∞), producing square cells.
+    Chebyshev,
+}
+
+impl DistanceMetric {
+    #[inline]
+    fn distance<const D: usize>(&self, delta: [f64; D]) -> f64 {
+        match self {
+            DistanceMetric::Euclidean => delta.iter().map(|d| d * d).sum::<f64>().sqrt(),
+            DistanceMetric::Manhattan => delta.iter().map(|d| d.abs()).sum(),
+            DistanceMetric::Chebyshev => delta.iter().fold(0.0, |acc, d| f64::max(acc, d.abs())),
+        }
+    }
+}
+
+/// The quantity a cellular generator returns for each input point.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CellularReturn {
+    /// The distance to the nearest feature point.
+    #[default]
+    F1,
+    /// The distance to the second-nearest feature point.
+    F2,
+    /// The difference between the distances to the second-nearest and the nearest feature point,
+    /// which is zero along cell borders.
+    F2MinusF1,
+    /// A pseudorandom value in the range [-1, 1] which is constant within each cell.
+    CellValue,
+    /// An integer identifying the cell, which is constant within each cell.
+    CellId,
+}
+
+/// The cell nearest to an input point, as found by [`Cellular::cell()`].
+///
+/// [`Cellular::cell()`]: crate::macro_map::jungle_noise::sources::Cellular::cell
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell<const D: usize> {
+    /// The distance to the feature point of this cell.
+    pub f1: f64,
+    /// The distance to the feature point of the second-nearest cell.
+    pub f2: f64,
+    /// The feature point of this cell, in the coordinates of the input space.
+    pub feature_point: [f64; D],
+    /// An integer identifying this cell. Cells repeat every 256 units along each axis.
+    pub id: usize,
+    /// A pseudorandom value in the range [-1, 1] assigned to this cell.
+    pub value: f64,
+    /// The feature point of the second-nearest cell, which usually lies across the nearest border.
+    pub neighbour_point: [f64; D],
+    /// The identifier of the second-nearest cell.
+    pub neighbour_id: usize,
+    /// The value of the second-nearest cell.
+    pub neighbour_value: f64,
+}
+
+/// Finds the nearest and second-nearest feature points to `point`.
+///
+/// Each hypercube of the lattice holds one feature point, which is placed pseudorandomly within
+/// it. `jitter` controls how far feature points may stray from the centre of their hypercube:
+/// with a jitter of 0 the feature points form a regular grid, with a jitter of 1 they may lie
+/// anywhere within their hypercube. As only the own and directly adjacent hypercubes are
+/// searched, the second-nearest distance may in rare cases be slightly overestimated.
+pub(crate) fn cell<const D: usize>(
+    perm: &PermutationTable,
+    point: [f64; D],
+    metric: DistanceMetric,
+    jitter: f64,
+) -> Cell<D> {
+    // origin of hypercube in which input lies and relative input position
+    let x0 = point.map(f64::floor);
+    let dx: [f64; D] = std::array::from_fn(|i| point[i] - x0[i]);
+    let mut nearest = (f64::INFINITY, [0.0; D], [0; D]);
+    let mut second = nearest;
+    // visit the 3^D hypercubes surrounding the input
+    for neighbor in 0..3usize.pow(D as u32) {
+        let offset: [f64; D] =
+            std::array::from_fn(|i| ((neighbor / 3usize.pow(i as u32)) % 3) as f64 - 1.0);
+        let index: [usize; D] = std::array::from_fn(|i| {
+            (x0[i] + offset[i]).rem_euclid(PERMUTATION_TABLE_SIZE as f64) as usize
+        });
+        let feature = feature_point(perm, &index, jitter);
+        let delta: [f64; D] = std::array::from_fn(|i| feature[i] + offset[i] - dx[i]);
+        let distance = metric.distance(delta);
+        let feature_point = std::array::from_fn(|i| x0[i] + offset[i] + feature[i]);
+        if distance < nearest.0 {
+            second = nearest;
+            nearest = (distance, feature_point, index);
+        } else if distance < second.0 {
+            second = (distance, feature_point, index);
+        }
+    }
+    let (f1, feature_point, index) = nearest;
+    let (f2, neighbour_point, neighbour_index) = second;
+    Cell {
+        f1,
+        f2,
+        feature_point,
+        id: cell_id(&index),
+        value: cell_value(perm, &index),
+        neighbour_point,
+        neighbour_id: cell_id(&neighbour_index),
+        neighbour_value: cell_value(perm, &neighbour_index),
+    }
+}
+
+#[inline]
+fn cell_id<const D: usize>(index: &[usize; D]) -> usize {
+    index
+        .iter()
+        .rev()
+        .fold(0, |acc, &i| acc * PERMUTATION_TABLE_SIZE + i)
+}
+
+#[inline]
+fn cell_value<const D: usize>(perm: &PermutationTable, index: &[usize; D]) -> f64 {
+    let value = unsafe { perm.hash1d(lattice::hash(perm, index) + 1) };
+    value as f64 / (PERMUTATION_TABLE_SIZE - 1) as f64 * 2.0 - 1.0
+}
+
+pub(crate) fn noise<const D: usize>(
+    perm: &PermutationTable,
+    point: [f64; D],
+    metric: DistanceMetric,
+    return_type: CellularReturn,
+    jitter: f64,
+) -> f64 {
+    let cell = cell(perm, point, metric, jitter);
+    // restrict distances to 1 and normalize, as done for worley noise
+    let normalize = |distance: f64| distance.clamp(0.0, 1.0) * 2.0 - 1.0;
+    match return_type {
+        CellularReturn::F1 => normalize(cell.f1),
+        CellularReturn::F2 => normalize(cell.f2),
+        CellularReturn::F2MinusF1 => normalize(cell.f2 - cell.f1),
+        CellularReturn::CellValue => cell.value,
+        CellularReturn::CellId => cell.id as f64,
+    }
+}
+
+/// Computes the position of the feature point within the hypercube at `index`, using the same
+/// hash chain as worley noise so that a jitter of 1 reproduces its feature points.
+#[inline]
+fn feature_point<const D: usize>(
+    perm: &PermutationTable,
+    index: &[usize; D],
+    jitter: f64,
+) -> [f64; D] {
+    let mut hash = unsafe { lattice::hash(perm, index) };
+    std::array::from_fn(|i| {
+        if i > 0 {
+            hash = unsafe { perm.hash1d(hash) };
+        }
+        let offset = hash as f64 / PERMUTATION_TABLE_SIZE as f64;
+        offset * jitter + (1.0 - jitter) * 0.5
+    })
+}
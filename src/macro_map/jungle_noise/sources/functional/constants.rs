@@ -1,3 +1,6 @@
+/// Lattice coordinates are wrapped into the permutation table with a Euclidean remainder before
+/// they are hashed, so negative coordinates continue the lattice of the positive ones, and every
+/// lattice source repeats after this many cells along each axis.
 pub(crate) const PERMUTATION_TABLE_SIZE: usize = 256;
 
 pub(crate) const GRADIENT_LUT_1D_SIZE: usize = 16;
@@ -0,0 +1,58 @@
+use crate::macro_map::jungle_noise::utils::ptable::PermutationTable;
+
+/// Computes noise values and their gradient on a hypercube lattice.
+///
+/// The input point is split into the lattice `origin` of the hypercube it resides in, which must
+/// already be wrapped into the permutation table, and its relative position `dx` within that
+/// hypercube. For each hypercube corner, `corner` is called with the hashed corner index and the
+/// position of the input relative to that corner, and returns the value contributed by that
+/// corner together with the gradient of the contribution. The contributions are then combined by
+/// interpolating along each axis in turn using `interpolant`, which returns the smoothed
+/// interpolation factor and its derivative.
+///
+/// The interpolation is performed in the same order as the explicitly unrolled noise functions,
+/// so the resulting value is identical to theirs.
+pub(crate) fn noise_with_gradient<const D: usize, C, I>(
+    perm: &PermutationTable,
+    origin: [usize; D],
+    dx: [f64; D],
+    interpolant: I,
+    corner: C,
+) -> (f64, [f64; D])
+where
+    C: Fn(usize, [f64; D]) -> (f64, [f64; D]),
+    I: Fn(f64) -> (f64, f64),
+{
+    // corner `c` lies at offset `(c >> axis) & 1` along each axis
+    let mut corners = [(0.0, [0.0; D]); 16];
+    for (c, contribution) in corners.iter_mut().enumerate().take(1 << D) {
+        let offset: [usize; D] = std::array::from_fn(|axis| (c >> axis) & 1);
+        let index: [usize; D] = std::array::from_fn(|axis| origin[axis] + offset[axis]);
+        let hash = unsafe { hash(perm, &index) };
+        let relative = std::array::from_fn(|axis| dx[axis] - offset[axis] as f64);
+        *contribution = corner(hash, relative);
+    }
+    // interpolate along one axis at a time, halving the number of values each time
+    let mut len = 1 << D;
+    for axis in 0..D {
+        let (t, dt) = interpolant(dx[axis]);
+        len /= 2;
+        for i in 0..len {
+            let (a, da) = corners[2 * i];
+            let (b, db) = corners[2 * i + 1];
+            let mut gradient: [f64; D] = std::array::from_fn(|k| da[k] + t * (db[k] - da[k]));
+            gradient[axis] += (b - a) * dt;
+            corners[i] = (a + t * (b - a), gradient);
+        }
+    }
+    corners[0]
+}
+
+/// Hashes the lattice point at `index`, equivalent to the `hashNd` functions of the
+/// permutation table.
+#[inline]
+pub(crate) unsafe fn hash(perm: &PermutationTable, index: &[usize]) -> usize {
+    index[1..]
+        .iter()
+        .fold(perm.get(index[0]), |acc, &i| perm.get(i + acc))
+}
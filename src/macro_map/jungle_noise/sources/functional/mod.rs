@@ -1,6 +1,8 @@
+pub(crate) mod cellular;
 pub(crate) mod checkerboard;
 pub(crate) mod constants;
 pub(crate) mod improved_perlin;
+pub(crate) mod lattice;
 pub(crate) mod perlin;
 pub(crate) mod simplex;
 pub(crate) mod value;
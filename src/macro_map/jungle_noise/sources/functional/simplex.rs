@@ -1,33 +1,73 @@
 use super::constants::*;
 use crate::macro_map::jungle_noise::utils::{
-    math::{Vec2, Vec3, Vec4},
+    math::{NoiseFloat, Vec2, Vec3, Vec4},
     ptable::PermutationTable,
 };
 
-pub(crate) fn noise1d(perm: &PermutationTable, point: [f64; 1]) -> f64 {
+pub(crate) fn noise1d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 1]) -> T {
+    let [(x0, gi0), (x1, gi1)] = vertices1d(perm, point);
+    // compute contributions
+    let n0 = unsafe { contribution1d(x0, gi0) };
+    let n1 = unsafe { contribution1d(x1, gi1) };
+    // combine contributions and scale to [-1, 1]
+    (n0 + n1) * T::cast(SIMPLEX_NORMALIZATION_FACTOR_1D)
+}
+
+pub(crate) fn noise1d_with_gradient(perm: &PermutationTable, point: [f64; 1]) -> (f64, [f64; 1]) {
+    let [(x0, gi0), (x1, gi1)] = vertices1d(perm, point);
+    // compute contributions and their derivatives
+    let (n0, d0) = unsafe { contribution1d_with_gradient(x0, gi0) };
+    let (n1, d1) = unsafe { contribution1d_with_gradient(x1, gi1) };
+    // combine contributions and scale to [-1, 1]
+    (
+        (n0 + n1) * SIMPLEX_NORMALIZATION_FACTOR_1D,
+        [(d0 + d1) * SIMPLEX_NORMALIZATION_FACTOR_1D],
+    )
+}
+
+fn vertices1d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 1]) -> [(T, usize); 2] {
     let x = point[0];
     // no transformation into lattice space required, get cube origin
     let i0 = x.floor();
     // input point relative the two simplex vertices
     let x0 = x - i0;
-    let x1 = x0 - 1.0;
+    let x1 = x0 - T::one();
     // hashed gradient (-1 or 1) directly
-    let i0 = i0.rem_euclid(PERMUTATION_TABLE_SIZE as f64) as usize;
+    let i0 = i0.rem_euclid(&T::cast(PERMUTATION_TABLE_SIZE as f64)).to_index();
     let gi0 = unsafe { perm.hash1d(i0) % GRADIENT_LUT_1D_SIZE };
     let gi1 = unsafe { perm.hash1d(i0 + 1) % GRADIENT_LUT_1D_SIZE };
+    [(x0, gi0), (x1, gi1)]
+}
+
+pub(crate) fn noise2d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 2]) -> T {
+    let [(x0, gi0), (x1, gi1), (x2, gi2)] = vertices2d(perm, point);
     // compute contributions
-    let n0 = unsafe { contribution1d(x0, gi0) };
-    let n1 = unsafe { contribution1d(x1, gi1) };
+    let n0 = unsafe { contribution2d(x0, gi0) };
+    let n1 = unsafe { contribution2d(x1, gi1) };
+    let n2 = unsafe { contribution2d(x2, gi2) };
     // combine contributions and scale to [-1, 1]
-    (n0 + n1) * SIMPLEX_NORMALIZATION_FACTOR_1D
+    (n0 + n1 + n2) * T::cast(SIMPLEX_NORMALIZATION_FACTOR_2D)
 }
 
-pub(crate) fn noise2d(perm: &PermutationTable, point: [f64; 2]) -> f64 {
+pub(crate) fn noise2d_with_gradient(perm: &PermutationTable, point: [f64; 2]) -> (f64, [f64; 2]) {
+    let [(x0, gi0), (x1, gi1), (x2, gi2)] = vertices2d(perm, point);
+    // compute contributions and their gradients
+    let (n0, d0) = unsafe { contribution2d_with_gradient(x0, gi0) };
+    let (n1, d1) = unsafe { contribution2d_with_gradient(x1, gi1) };
+    let (n2, d2) = unsafe { contribution2d_with_gradient(x2, gi2) };
+    // combine contributions and scale to [-1, 1]
+    (
+        (n0 + n1 + n2) * SIMPLEX_NORMALIZATION_FACTOR_2D,
+        std::array::from_fn(|i| (d0[i] + d1[i] + d2[i]) * SIMPLEX_NORMALIZATION_FACTOR_2D),
+    )
+}
+
+fn vertices2d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 2]) -> [(Vec2<T>, usize); 3] {
     let x = Vec2::from(point);
     // transform into lattice space and floor for cube origin
-    let is = (x + x.sum() * SIMPLEX_SKEW_FACTOR_2D).floor();
+    let is = (x + x.sum() * T::cast(SIMPLEX_SKEW_FACTOR_2D)).floor();
     // input point relative to unskewed cube (and simplex) origin in source space
-    let x0 = x - is + is.sum() * SIMPLEX_UNSKEW_FACTOR_2D;
+    let x0 = x - is + is.sum() * T::cast(SIMPLEX_UNSKEW_FACTOR_2D);
     // compute middle simplex traversal vector(s) between 0-vector and 1-vector
     let mut i1 = Vec2::from([1, 0]);
     if x0.x < x0.y {
@@ -35,27 +75,49 @@ pub(crate) fn noise2d(perm: &PermutationTable, point: [f64; 2]) -> f64 {
         i1.y = 1;
     }
     // imput point relative to other unskewed simplex vertices
-    let x1 = x0 - i1.cast() + SIMPLEX_UNSKEW_FACTOR_2D;
-    let x2 = x0 - 1.0 + 2.0 * SIMPLEX_UNSKEW_FACTOR_2D;
+    let x1 = x0 - i1.cast() + T::cast(SIMPLEX_UNSKEW_FACTOR_2D);
+    let x2 = x0 - T::one() + T::cast(2.0 * SIMPLEX_UNSKEW_FACTOR_2D);
     // hashed gradient indices
-    let is = is.rem_euclid(PERMUTATION_TABLE_SIZE as f64).cast();
+    let is = is.rem_euclid(T::cast(PERMUTATION_TABLE_SIZE as f64)).to_index();
     let gi0 = unsafe { perm.hash2d(is.x, is.y) } % MIDPOINT_GRADIENT_LUT_2D_SIZE;
     let gi1 = unsafe { perm.hash2d(is.x + i1.x, is.y + i1.y) } % MIDPOINT_GRADIENT_LUT_2D_SIZE;
     let gi2 = unsafe { perm.hash2d(is.x + 1, is.y + 1) } % MIDPOINT_GRADIENT_LUT_2D_SIZE;
+    [(x0, gi0), (x1, gi1), (x2, gi2)]
+}
+
+pub(crate) fn noise3d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 3]) -> T {
+    let [(x0, gi0), (x1, gi1), (x2, gi2), (x3, gi3)] = vertices3d(perm, point);
     // compute contributions
-    let n0 = unsafe { contribution2d(x0, gi0) };
-    let n1 = unsafe { contribution2d(x1, gi1) };
-    let n2 = unsafe { contribution2d(x2, gi2) };
+    let n0 = unsafe { contribution3d(x0, gi0) };
+    let n1 = unsafe { contribution3d(x1, gi1) };
+    let n2 = unsafe { contribution3d(x2, gi2) };
+    let n3 = unsafe { contribution3d(x3, gi3) };
+    // combine contributions and scale to [-1, 1]
+    (n0 + n1 + n2 + n3) * T::cast(SIMPLEX_NORMALIZATION_FACTOR_3D)
+}
+
+pub(crate) fn noise3d_with_gradient(perm: &PermutationTable, point: [f64; 3]) -> (f64, [f64; 3]) {
+    let [(x0, gi0), (x1, gi1), (x2, gi2), (x3, gi3)] = vertices3d(perm, point);
+    // compute contributions and their gradients
+    let (n0, d0) = unsafe { contribution3d_with_gradient(x0, gi0) };
+    let (n1, d1) = unsafe { contribution3d_with_gradient(x1, gi1) };
+    let (n2, d2) = unsafe { contribution3d_with_gradient(x2, gi2) };
+    let (n3, d3) = unsafe { contribution3d_with_gradient(x3, gi3) };
     // combine contributions and scale to [-1, 1]
-    (n0 + n1 + n2) * SIMPLEX_NORMALIZATION_FACTOR_2D
+    (
+        (n0 + n1 + n2 + n3) * SIMPLEX_NORMALIZATION_FACTOR_3D,
+        std::array::from_fn(|i| {
+            (d0[i] + d1[i] + d2[i] + d3[i]) * SIMPLEX_NORMALIZATION_FACTOR_3D
+        }),
+    )
 }
 
-pub(crate) fn noise3d(perm: &PermutationTable, point: [f64; 3]) -> f64 {
+fn vertices3d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 3]) -> [(Vec3<T>, usize); 4] {
     let x = Vec3::from(point);
     // transform into lattice space and floor for cube origin
-    let is = (x + x.sum() * SIMPLEX_SKEW_FACTOR_3D).floor();
+    let is = (x + x.sum() * T::cast(SIMPLEX_SKEW_FACTOR_3D)).floor();
     // input point relative to unskewed cube (and simplex) origin in source space
-    let x0 = x - is + is.sum() * SIMPLEX_UNSKEW_FACTOR_3D;
+    let x0 = x - is + is.sum() * T::cast(SIMPLEX_UNSKEW_FACTOR_3D);
     // compute middle simplex traversal vector(s) between 0-vector and 1-vector
     let idx = (x0.x > x0.y) as usize * 4 + (x0.y > x0.z) as usize * 2 + (x0.x > x0.z) as usize;
     let i1 = Vec3::from([
@@ -69,30 +131,53 @@ pub(crate) fn noise3d(perm: &PermutationTable, point: [f64; 3]) -> f64 {
         SIMPLEX_TRAVERSAL_LUT_3D[idx][5],
     ]);
     // imput point relative to other unskewed simplex vertices
-    let x1 = x0 - i1.cast() + SIMPLEX_UNSKEW_FACTOR_3D;
-    let x2 = x0 - i2.cast() + 2.0 * SIMPLEX_UNSKEW_FACTOR_3D;
-    let x3 = x0 - 1.0 + 3.0 * SIMPLEX_UNSKEW_FACTOR_3D;
+    let x1 = x0 - i1.cast() + T::cast(SIMPLEX_UNSKEW_FACTOR_3D);
+    let x2 = x0 - i2.cast() + T::cast(2.0 * SIMPLEX_UNSKEW_FACTOR_3D);
+    let x3 = x0 - T::one() + T::cast(3.0 * SIMPLEX_UNSKEW_FACTOR_3D);
     // hashed gradient indices
-    let is = is.cast().rem_euclid(PERMUTATION_TABLE_SIZE);
+    let is = is.rem_euclid(T::cast(PERMUTATION_TABLE_SIZE as f64)).to_index();
     let gi0 = unsafe { perm.hash3d_vec(is) } % MIDPOINT_GRADIENT_LUT_3D_SIZE;
     let gi1 = unsafe { perm.hash3d_vec(is + i1) } % MIDPOINT_GRADIENT_LUT_3D_SIZE;
     let gi2 = unsafe { perm.hash3d_vec(is + i2) } % MIDPOINT_GRADIENT_LUT_3D_SIZE;
     let gi3 = unsafe { perm.hash3d_vec(is + 1) } % MIDPOINT_GRADIENT_LUT_3D_SIZE;
+    [(x0, gi0), (x1, gi1), (x2, gi2), (x3, gi3)]
+}
+
+pub(crate) fn noise4d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 4]) -> T {
+    let [(x0, gi0), (x1, gi1), (x2, gi2), (x3, gi3), (x4, gi4)] = vertices4d(perm, point);
     // compute contributions
-    let n0 = unsafe { contribution3d(x0, gi0) };
-    let n1 = unsafe { contribution3d(x1, gi1) };
-    let n2 = unsafe { contribution3d(x2, gi2) };
-    let n3 = unsafe { contribution3d(x3, gi3) };
+    let n0 = unsafe { contribution4d(x0, gi0) };
+    let n1 = unsafe { contribution4d(x1, gi1) };
+    let n2 = unsafe { contribution4d(x2, gi2) };
+    let n3 = unsafe { contribution4d(x3, gi3) };
+    let n4 = unsafe { contribution4d(x4, gi4) };
+    // combine contributions and scale to [-1, 1]
+    (n0 + n1 + n2 + n3 + n4) * T::cast(SIMPLEX_NORMALIZATION_FACTOR_4D)
+}
+
+pub(crate) fn noise4d_with_gradient(perm: &PermutationTable, point: [f64; 4]) -> (f64, [f64; 4]) {
+    let [(x0, gi0), (x1, gi1), (x2, gi2), (x3, gi3), (x4, gi4)] = vertices4d(perm, point);
+    // compute contributions and their gradients
+    let (n0, d0) = unsafe { contribution4d_with_gradient(x0, gi0) };
+    let (n1, d1) = unsafe { contribution4d_with_gradient(x1, gi1) };
+    let (n2, d2) = unsafe { contribution4d_with_gradient(x2, gi2) };
+    let (n3, d3) = unsafe { contribution4d_with_gradient(x3, gi3) };
+    let (n4, d4) = unsafe { contribution4d_with_gradient(x4, gi4) };
     // combine contributions and scale to [-1, 1]
-    (n0 + n1 + n2 + n3) * SIMPLEX_NORMALIZATION_FACTOR_3D
+    (
+        (n0 + n1 + n2 + n3 + n4) * SIMPLEX_NORMALIZATION_FACTOR_4D,
+        std::array::from_fn(|i| {
+            (d0[i] + d1[i] + d2[i] + d3[i] + d4[i]) * SIMPLEX_NORMALIZATION_FACTOR_4D
+        }),
+    )
 }
 
-pub(crate) fn noise4d(perm: &PermutationTable, point: [f64; 4]) -> f64 {
+fn vertices4d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 4]) -> [(Vec4<T>, usize); 5] {
     let x = Vec4::from(point);
     // transform into lattice space and floor for cube origin
-    let is = (x + x.sum() * SIMPLEX_SKEW_FACTOR_4D).floor();
+    let is = (x + x.sum() * T::cast(SIMPLEX_SKEW_FACTOR_4D)).floor();
     // input point relative to unskewed cube (and simplex) origin in source space
-    let x0 = x - is + is.sum() * SIMPLEX_UNSKEW_FACTOR_4D;
+    let x0 = x - is + is.sum() * T::cast(SIMPLEX_UNSKEW_FACTOR_4D);
     // compute middle simplex traversal vector(s) between 0-vector and 1-vector
     let idx = (x0.x > x0.y) as usize * 32
         + (x0.x > x0.z) as usize * 16
@@ -119,73 +204,135 @@ pub(crate) fn noise4d(perm: &PermutationTable, point: [f64; 4]) -> f64 {
         SIMPLEX_TRAVERSAL_LUT_4D[idx][11],
     ]);
     // imput point relative to other unskewed simplex vertices
-    let x1 = x0 - i1.cast() + SIMPLEX_UNSKEW_FACTOR_4D;
-    let x2 = x0 - i2.cast() + 2.0 * SIMPLEX_UNSKEW_FACTOR_4D;
-    let x3 = x0 - i3.cast() + 3.0 * SIMPLEX_UNSKEW_FACTOR_4D;
-    let x4 = x0 - 1.0 + 4.0 * SIMPLEX_UNSKEW_FACTOR_4D;
+    let x1 = x0 - i1.cast() + T::cast(SIMPLEX_UNSKEW_FACTOR_4D);
+    let x2 = x0 - i2.cast() + T::cast(2.0 * SIMPLEX_UNSKEW_FACTOR_4D);
+    let x3 = x0 - i3.cast() + T::cast(3.0 * SIMPLEX_UNSKEW_FACTOR_4D);
+    let x4 = x0 - T::one() + T::cast(4.0 * SIMPLEX_UNSKEW_FACTOR_4D);
     // hashed gradient indices
-    let is = is.cast().rem_euclid(PERMUTATION_TABLE_SIZE);
+    let is = is.rem_euclid(T::cast(PERMUTATION_TABLE_SIZE as f64)).to_index();
     let gi0 = unsafe { perm.hash4d_vec(is) } % MIDPOINT_GRADIENT_LUT_4D_SIZE;
     let gi1 = unsafe { perm.hash4d_vec(is + i1) } % MIDPOINT_GRADIENT_LUT_4D_SIZE;
     let gi2 = unsafe { perm.hash4d_vec(is + i2) } % MIDPOINT_GRADIENT_LUT_4D_SIZE;
     let gi3 = unsafe { perm.hash4d_vec(is + i3) } % MIDPOINT_GRADIENT_LUT_4D_SIZE;
     let gi4 = unsafe { perm.hash4d_vec(is + 1) } % MIDPOINT_GRADIENT_LUT_4D_SIZE;
-    // compute contributions
-    let n0 = unsafe { contribution4d(x0, gi0) };
-    let n1 = unsafe { contribution4d(x1, gi1) };
-    let n2 = unsafe { contribution4d(x2, gi2) };
-    let n3 = unsafe { contribution4d(x3, gi3) };
-    let n4 = unsafe { contribution4d(x4, gi4) };
-    // combine contributions and scale to [-1, 1]
-    (n0 + n1 + n2 + n3 + n4) * SIMPLEX_NORMALIZATION_FACTOR_4D
+    [(x0, gi0), (x1, gi1), (x2, gi2), (x3, gi3), (x4, gi4)]
 }
 
-unsafe fn contribution1d(x: f64, gi: usize) -> f64 {
-    if x.abs() >= std::f64::consts::FRAC_1_SQRT_2 {
-        0.0
+unsafe fn contribution1d<T: NoiseFloat>(x: T, gi: usize) -> T {
+    if x.abs() >= T::cast(std::f64::consts::FRAC_1_SQRT_2) {
+        T::zero()
     } else {
-        let mut t = SIMPLEX_R_SQUARED - x * x;
+        let mut t = T::cast(SIMPLEX_R_SQUARED) - x * x;
         t *= t;
-        t * t * GRADIENT_LUT_1D.get_unchecked(gi) * x
+        t * t * T::cast(*GRADIENT_LUT_1D.get_unchecked(gi)) * x
     }
 }
 
-unsafe fn contribution2d(x: Vec2<f64>, gi: usize) -> f64 {
-    let mut t = SIMPLEX_R_SQUARED - x.x * x.x - x.y * x.y;
-    if t <= 0.0 {
-        0.0
+unsafe fn contribution2d<T: NoiseFloat>(x: Vec2<T>, gi: usize) -> T {
+    let mut t = T::cast(SIMPLEX_R_SQUARED) - x.x * x.x - x.y * x.y;
+    if t <= T::zero() {
+        T::zero()
     } else {
         let gradient = MIDPOINT_GRADIENT_LUT_2D.get_unchecked(gi);
         t *= t;
-        t * t * (gradient.get_unchecked(0) * x.x + gradient.get_unchecked(1) * x.y)
+        t * t
+            * (T::cast(*gradient.get_unchecked(0)) * x.x
+                + T::cast(*gradient.get_unchecked(1)) * x.y)
     }
 }
 
-unsafe fn contribution3d(x: Vec3<f64>, gi: usize) -> f64 {
-    let mut t = SIMPLEX_R_SQUARED - x.x * x.x - x.y * x.y - x.z * x.z;
-    if t <= 0.0 {
-        0.0
+unsafe fn contribution3d<T: NoiseFloat>(x: Vec3<T>, gi: usize) -> T {
+    let mut t = T::cast(SIMPLEX_R_SQUARED) - x.x * x.x - x.y * x.y - x.z * x.z;
+    if t <= T::zero() {
+        T::zero()
     } else {
         let gradient = MIDPOINT_GRADIENT_LUT_3D.get_unchecked(gi);
         t *= t;
         t * t
-            * (gradient.get_unchecked(0) * x.x
-                + gradient.get_unchecked(1) * x.y
-                + gradient.get_unchecked(2) * x.z)
+            * (T::cast(*gradient.get_unchecked(0)) * x.x
+                + T::cast(*gradient.get_unchecked(1)) * x.y
+                + T::cast(*gradient.get_unchecked(2)) * x.z)
     }
 }
 
-unsafe fn contribution4d(x: Vec4<f64>, gi: usize) -> f64 {
-    let mut t = SIMPLEX_R_SQUARED - x.x * x.x - x.y * x.y - x.z * x.z - x.w * x.w;
-    if t <= 0.0 {
-        0.0
+unsafe fn contribution4d<T: NoiseFloat>(x: Vec4<T>, gi: usize) -> T {
+    let mut t = T::cast(SIMPLEX_R_SQUARED) - x.x * x.x - x.y * x.y - x.z * x.z - x.w * x.w;
+    if t <= T::zero() {
+        T::zero()
     } else {
         let gradient = MIDPOINT_GRADIENT_LUT_4D.get_unchecked(gi);
         t *= t;
         t * t
-            * (gradient.get_unchecked(0) * x.x
-                + gradient.get_unchecked(1) * x.y
-                + gradient.get_unchecked(2) * x.z
-                + gradient.get_unchecked(3) * x.w)
+            * (T::cast(*gradient.get_unchecked(0)) * x.x
+                + T::cast(*gradient.get_unchecked(1)) * x.y
+                + T::cast(*gradient.get_unchecked(2)) * x.z
+                + T::cast(*gradient.get_unchecked(3)) * x.w)
+    }
+}
+
+unsafe fn contribution1d_with_gradient(x: f64, gi: usize) -> (f64, f64) {
+    if x.abs() >= std::f64::consts::FRAC_1_SQRT_2 {
+        (0.0, 0.0)
+    } else {
+        let t = SIMPLEX_R_SQUARED - x * x;
+        let t2 = t * t;
+        let gradient = GRADIENT_LUT_1D.get_unchecked(gi);
+        (
+            t2 * t2 * gradient * x,
+            t2 * t2 * gradient - 8.0 * t2 * t * x * x * gradient,
+        )
+    }
+}
+
+unsafe fn contribution2d_with_gradient(x: Vec2<f64>, gi: usize) -> (f64, [f64; 2]) {
+    let t = SIMPLEX_R_SQUARED - x.x * x.x - x.y * x.y;
+    if t <= 0.0 {
+        (0.0, [0.0; 2])
+    } else {
+        let gradient = MIDPOINT_GRADIENT_LUT_2D.get_unchecked(gi);
+        let t2 = t * t;
+        let n = gradient.get_unchecked(0) * x.x + gradient.get_unchecked(1) * x.y;
+        let x = [x.x, x.y];
+        (
+            t2 * t2 * n,
+            std::array::from_fn(|i| t2 * t2 * gradient[i] - 8.0 * t2 * t * x[i] * n),
+        )
+    }
+}
+
+unsafe fn contribution3d_with_gradient(x: Vec3<f64>, gi: usize) -> (f64, [f64; 3]) {
+    let t = SIMPLEX_R_SQUARED - x.x * x.x - x.y * x.y - x.z * x.z;
+    if t <= 0.0 {
+        (0.0, [0.0; 3])
+    } else {
+        let gradient = MIDPOINT_GRADIENT_LUT_3D.get_unchecked(gi);
+        let t2 = t * t;
+        let n = gradient.get_unchecked(0) * x.x
+            + gradient.get_unchecked(1) * x.y
+            + gradient.get_unchecked(2) * x.z;
+        let x = [x.x, x.y, x.z];
+        (
+            t2 * t2 * n,
+            std::array::from_fn(|i| t2 * t2 * gradient[i] - 8.0 * t2 * t * x[i] * n),
+        )
+    }
+}
+
+unsafe fn contribution4d_with_gradient(x: Vec4<f64>, gi: usize) -> (f64, [f64; 4]) {
+    let t = SIMPLEX_R_SQUARED - x.x * x.x - x.y * x.y - x.z * x.z - x.w * x.w;
+    if t <= 0.0 {
+        (0.0, [0.0; 4])
+    } else {
+        let gradient = MIDPOINT_GRADIENT_LUT_4D.get_unchecked(gi);
+        let t2 = t * t;
+        let n = gradient.get_unchecked(0) * x.x
+            + gradient.get_unchecked(1) * x.y
+            + gradient.get_unchecked(2) * x.z
+            + gradient.get_unchecked(3) * x.w;
+        let x = [x.x, x.y, x.z, x.w];
+        (
+            t2 * t2 * n,
+            std::array::from_fn(|i| t2 * t2 * gradient[i] - 8.0 * t2 * t * x[i] * n),
+        )
     }
 }
@@ -1,36 +1,36 @@
-use super::constants::PERMUTATION_TABLE_SIZE;
+use super::{constants::PERMUTATION_TABLE_SIZE, lattice};
 use crate::macro_map::jungle_noise::utils::{
-    math::{Vec2, Vec3, Vec4},
+    math::{NoiseFloat, Vec2, Vec3, Vec4},
     ptable::PermutationTable,
 };
 
-pub(crate) fn noise1d(perm: &PermutationTable, point: [f64; 1]) -> f64 {
+pub(crate) fn noise1d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 1]) -> T {
     let x = point[0];
     // origin of hypercube in which input lies
     let x0 = x.floor();
     // smoothed distance from hypercube origin
     let dxs = smoothstep_3(x - x0);
     // get values from hypercube corners
-    let x0 = x0.rem_euclid(PERMUTATION_TABLE_SIZE as f64) as usize;
-    let f0 = unsafe { perm.hash1d(x0) } as f64;
-    let f1 = unsafe { perm.hash1d(x0 + 1) } as f64;
+    let x0 = x0.rem_euclid(&T::cast(PERMUTATION_TABLE_SIZE as f64)).to_index();
+    let f0 = T::from_hash(unsafe { perm.hash1d(x0) });
+    let f1 = T::from_hash(unsafe { perm.hash1d(x0 + 1) });
     // interpolate values from hypercube corners
     let xf = lerp(f0, f1, dxs);
     normalize(xf)
 }
 
-pub(crate) fn noise2d(perm: &PermutationTable, point: [f64; 2]) -> f64 {
+pub(crate) fn noise2d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 2]) -> T {
     let x = Vec2::from(point);
     // origin of hypercube in which input lies
     let x0 = x.floor();
     // smoothed distance from hypercube origin
     let dxs = (x - x0).map(smoothstep_3);
     // get values from hypercube corners
-    let x0 = x0.cast().rem_euclid(PERMUTATION_TABLE_SIZE);
-    let f00 = unsafe { perm.hash2d(x0.x, x0.y) } as f64;
-    let f01 = unsafe { perm.hash2d(x0.x, x0.y + 1) } as f64;
-    let f10 = unsafe { perm.hash2d(x0.x + 1, x0.y) } as f64;
-    let f11 = unsafe { perm.hash2d(x0.x + 1, x0.y + 1) } as f64;
+    let x0 = x0.rem_euclid(T::cast(PERMUTATION_TABLE_SIZE as f64)).to_index();
+    let f00 = T::from_hash(unsafe { perm.hash2d(x0.x, x0.y) });
+    let f01 = T::from_hash(unsafe { perm.hash2d(x0.x, x0.y + 1) });
+    let f10 = T::from_hash(unsafe { perm.hash2d(x0.x + 1, x0.y) });
+    let f11 = T::from_hash(unsafe { perm.hash2d(x0.x + 1, x0.y + 1) });
     // interpolate values from hypercube corners
     let xf0 = lerp(f00, f10, dxs.x);
     let xf1 = lerp(f01, f11, dxs.x);
@@ -38,22 +38,22 @@ pub(crate) fn noise2d(perm: &PermutationTable, point: [f64; 2]) -> f64 {
     normalize(yf)
 }
 
-pub(crate) fn noise3d(perm: &PermutationTable, point: [f64; 3]) -> f64 {
+pub(crate) fn noise3d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 3]) -> T {
     let x = Vec3::from(point);
     // origin of hypercube in which input lies
     let x0 = x.floor();
     // smoothed distance from hypercube origin
     let dxs = (x - x0).map(smoothstep_3);
     // get values from hypercube corners
-    let x0 = x0.cast().rem_euclid(PERMUTATION_TABLE_SIZE);
-    let f000 = unsafe { perm.hash3d(x0.x, x0.y, x0.z) } as f64;
-    let f001 = unsafe { perm.hash3d(x0.x, x0.y, x0.z + 1) } as f64;
-    let f010 = unsafe { perm.hash3d(x0.x, x0.y + 1, x0.z) } as f64;
-    let f011 = unsafe { perm.hash3d(x0.x, x0.y + 1, x0.z + 1) } as f64;
-    let f100 = unsafe { perm.hash3d(x0.x + 1, x0.y, x0.z) } as f64;
-    let f101 = unsafe { perm.hash3d(x0.x + 1, x0.y, x0.z + 1) } as f64;
-    let f110 = unsafe { perm.hash3d(x0.x + 1, x0.y + 1, x0.z) } as f64;
-    let f111 = unsafe { perm.hash3d(x0.x + 1, x0.y + 1, x0.z + 1) } as f64;
+    let x0 = x0.rem_euclid(T::cast(PERMUTATION_TABLE_SIZE as f64)).to_index();
+    let f000 = T::from_hash(unsafe { perm.hash3d(x0.x, x0.y, x0.z) });
+    let f001 = T::from_hash(unsafe { perm.hash3d(x0.x, x0.y, x0.z + 1) });
+    let f010 = T::from_hash(unsafe { perm.hash3d(x0.x, x0.y + 1, x0.z) });
+    let f011 = T::from_hash(unsafe { perm.hash3d(x0.x, x0.y + 1, x0.z + 1) });
+    let f100 = T::from_hash(unsafe { perm.hash3d(x0.x + 1, x0.y, x0.z) });
+    let f101 = T::from_hash(unsafe { perm.hash3d(x0.x + 1, x0.y, x0.z + 1) });
+    let f110 = T::from_hash(unsafe { perm.hash3d(x0.x + 1, x0.y + 1, x0.z) });
+    let f111 = T::from_hash(unsafe { perm.hash3d(x0.x + 1, x0.y + 1, x0.z + 1) });
     // interpolate values from hypercube corners
     let xf00 = lerp(f000, f100, dxs.x);
     let xf01 = lerp(f001, f101, dxs.x);
@@ -65,30 +65,30 @@ pub(crate) fn noise3d(perm: &PermutationTable, point: [f64; 3]) -> f64 {
     normalize(zf)
 }
 
-pub(crate) fn noise4d(perm: &PermutationTable, point: [f64; 4]) -> f64 {
+pub(crate) fn noise4d<T: NoiseFloat>(perm: &PermutationTable, point: [T; 4]) -> T {
     let x = Vec4::from(point);
     // origin of hypercube in which input lies
     let x0 = x.floor();
     // smoothed distance from hypercube origin
     let dxs = (x - x0).map(smoothstep_3);
     // get values from hypercube corners
-    let x0 = x0.cast().rem_euclid(PERMUTATION_TABLE_SIZE);
-    let f0000 = unsafe { perm.hash4d(x0.x, x0.y, x0.z, x0.w) } as f64;
-    let f0001 = unsafe { perm.hash4d(x0.x, x0.y, x0.z, x0.w + 1) } as f64;
-    let f0010 = unsafe { perm.hash4d(x0.x, x0.y, x0.z + 1, x0.w) } as f64;
-    let f0011 = unsafe { perm.hash4d(x0.x, x0.y, x0.z + 1, x0.w + 1) } as f64;
-    let f0100 = unsafe { perm.hash4d(x0.x, x0.y + 1, x0.z, x0.w) } as f64;
-    let f0101 = unsafe { perm.hash4d(x0.x, x0.y + 1, x0.z, x0.w + 1) } as f64;
-    let f0110 = unsafe { perm.hash4d(x0.x, x0.y + 1, x0.z + 1, x0.w) } as f64;
-    let f0111 = unsafe { perm.hash4d(x0.x, x0.y + 1, x0.z + 1, x0.w + 1) } as f64;
-    let f1000 = unsafe { perm.hash4d(x0.x + 1, x0.y, x0.z, x0.w) } as f64;
-    let f1001 = unsafe { perm.hash4d(x0.x + 1, x0.y, x0.z, x0.w + 1) } as f64;
-    let f1010 = unsafe { perm.hash4d(x0.x + 1, x0.y, x0.z + 1, x0.w) } as f64;
-    let f1011 = unsafe { perm.hash4d(x0.x + 1, x0.y, x0.z + 1, x0.w + 1) } as f64;
-    let f1100 = unsafe { perm.hash4d(x0.x + 1, x0.y + 1, x0.z, x0.w) } as f64;
-    let f1101 = unsafe { perm.hash4d(x0.x + 1, x0.y + 1, x0.z, x0.w + 1) } as f64;
-    let f1110 = unsafe { perm.hash4d(x0.x + 1, x0.y + 1, x0.z + 1, x0.w) } as f64;
-    let f1111 = unsafe { perm.hash4d(x0.x + 1, x0.y + 1, x0.z + 1, x0.w + 1) } as f64;
+    let x0 = x0.rem_euclid(T::cast(PERMUTATION_TABLE_SIZE as f64)).to_index();
+    let f0000 = T::from_hash(unsafe { perm.hash4d(x0.x, x0.y, x0.z, x0.w) });
+    let f0001 = T::from_hash(unsafe { perm.hash4d(x0.x, x0.y, x0.z, x0.w + 1) });
+    let f0010 = T::from_hash(unsafe { perm.hash4d(x0.x, x0.y, x0.z + 1, x0.w) });
+    let f0011 = T::from_hash(unsafe { perm.hash4d(x0.x, x0.y, x0.z + 1, x0.w + 1) });
+    let f0100 = T::from_hash(unsafe { perm.hash4d(x0.x, x0.y + 1, x0.z, x0.w) });
+    let f0101 = T::from_hash(unsafe { perm.hash4d(x0.x, x0.y + 1, x0.z, x0.w + 1) });
+    let f0110 = T::from_hash(unsafe { perm.hash4d(x0.x, x0.y + 1, x0.z + 1, x0.w) });
+    let f0111 = T::from_hash(unsafe { perm.hash4d(x0.x, x0.y + 1, x0.z + 1, x0.w + 1) });
+    let f1000 = T::from_hash(unsafe { perm.hash4d(x0.x + 1, x0.y, x0.z, x0.w) });
+    let f1001 = T::from_hash(unsafe { perm.hash4d(x0.x + 1, x0.y, x0.z, x0.w + 1) });
+    let f1010 = T::from_hash(unsafe { perm.hash4d(x0.x + 1, x0.y, x0.z + 1, x0.w) });
+    let f1011 = T::from_hash(unsafe { perm.hash4d(x0.x + 1, x0.y, x0.z + 1, x0.w + 1) });
+    let f1100 = T::from_hash(unsafe { perm.hash4d(x0.x + 1, x0.y + 1, x0.z, x0.w) });
+    let f1101 = T::from_hash(unsafe { perm.hash4d(x0.x + 1, x0.y + 1, x0.z, x0.w + 1) });
+    let f1110 = T::from_hash(unsafe { perm.hash4d(x0.x + 1, x0.y + 1, x0.z + 1, x0.w) });
+    let f1111 = T::from_hash(unsafe { perm.hash4d(x0.x + 1, x0.y + 1, x0.z + 1, x0.w + 1) });
     // interpolate values from hypercube corners
     let xf000 = lerp(f0000, f1000, dxs.x);
     let xf001 = lerp(f0001, f1001, dxs.x);
@@ -108,17 +108,69 @@ pub(crate) fn noise4d(perm: &PermutationTable, point: [f64; 4]) -> f64 {
     normalize(wf)
 }
 
+pub(crate) fn noise1d_with_gradient(perm: &PermutationTable, point: [f64; 1]) -> (f64, [f64; 1]) {
+    let x = point[0];
+    // origin of hypercube in which input lies and relative input position
+    let x0 = x.floor();
+    let dx = x - x0;
+    let x0 = x0.rem_euclid(PERMUTATION_TABLE_SIZE as f64) as usize;
+    let (value, gradient) =
+        lattice::noise_with_gradient(perm, [x0], [dx], smoothstep_3_with_derivative, corner);
+    (normalize(value), gradient.map(normalize_derivative))
+}
+
+pub(crate) fn noise2d_with_gradient(perm: &PermutationTable, point: [f64; 2]) -> (f64, [f64; 2]) {
+    noise_with_gradient(perm, point)
+}
+
+pub(crate) fn noise3d_with_gradient(perm: &PermutationTable, point: [f64; 3]) -> (f64, [f64; 3]) {
+    noise_with_gradient(perm, point)
+}
+
+pub(crate) fn noise4d_with_gradient(perm: &PermutationTable, point: [f64; 4]) -> (f64, [f64; 4]) {
+    noise_with_gradient(perm, point)
+}
+
+fn noise_with_gradient<const D: usize>(
+    perm: &PermutationTable,
+    point: [f64; D],
+) -> (f64, [f64; D]) {
+    // origin of hypercube in which input lies and relative input position
+    let x0 = point.map(f64::floor);
+    let dx = std::array::from_fn(|i| point[i] - x0[i]);
+    let x0 = x0.map(|x| x.rem_euclid(PERMUTATION_TABLE_SIZE as f64) as usize);
+    // interpolate values from hypercube corners
+    let (value, gradient) =
+        lattice::noise_with_gradient(perm, x0, dx, smoothstep_3_with_derivative, corner);
+    (normalize(value), gradient.map(normalize_derivative))
+}
+
+#[inline]
+fn corner<const D: usize>(hash: usize, _: [f64; D]) -> (f64, [f64; D]) {
+    (hash as f64, [0.0; D])
+}
+
+#[inline]
+fn normalize_derivative(dx: f64) -> f64 {
+    2.0 / PERMUTATION_TABLE_SIZE as f64 * dx
+}
+
+#[inline]
+fn smoothstep_3_with_derivative(t: f64) -> (f64, f64) {
+    (smoothstep_3(t), 6.0 * t * (1.0 - t))
+}
+
 #[inline]
-fn normalize(x: f64) -> f64 {
-    2.0 / PERMUTATION_TABLE_SIZE as f64 * x - 1.0
+fn normalize<T: NoiseFloat>(x: T) -> T {
+    T::cast(2.0 / PERMUTATION_TABLE_SIZE as f64) * x - T::one()
 }
 
 #[inline]
-fn smoothstep_3(t: f64) -> f64 {
-    t * t * (t * (-2.0) + 3.0)
+fn smoothstep_3<T: NoiseFloat>(t: T) -> T {
+    t * t * (t * T::cast(-2.0) + T::cast(3.0))
 }
 
 #[inline]
-fn lerp(a: f64, b: f64, t: f64) -> f64 {
+fn lerp<T: NoiseFloat>(a: T, b: T, t: T) -> T {
     a + t * (b - a)
 }
@@ -85,20 +85,20 @@ pub(crate) fn noise4d(perm: &PermutationTable, point: [f64; 4]) -> f64 {
 
 #[inline]
 fn point1d(perm: &PermutationTable, x0: f64) -> f64 {
-    let x = unsafe { perm.hash1d((x0 as usize).rem_euclid(PERMUTATION_TABLE_SIZE)) };
+    let x = unsafe { perm.hash1d(x0.rem_euclid(PERMUTATION_TABLE_SIZE as f64) as usize) };
     x as f64 / PERMUTATION_TABLE_SIZE as f64
 }
 
 #[inline]
 fn point2d(perm: &PermutationTable, x0: Vec2<f64>) -> Vec2<f64> {
-    let x = unsafe { perm.hash2d_vec(x0.cast().rem_euclid(PERMUTATION_TABLE_SIZE)) };
+    let x = unsafe { perm.hash2d_vec(x0.rem_euclid(PERMUTATION_TABLE_SIZE as f64).cast()) };
     let y = unsafe { perm.hash1d(x) };
     Vec2::from([x, y]).cast() / PERMUTATION_TABLE_SIZE as f64
 }
 
 #[inline]
 fn point3d(perm: &PermutationTable, x0: Vec3<f64>) -> Vec3<f64> {
-    let x = unsafe { perm.hash3d_vec(x0.cast().rem_euclid(PERMUTATION_TABLE_SIZE)) };
+    let x = unsafe { perm.hash3d_vec(x0.rem_euclid(PERMUTATION_TABLE_SIZE as f64).cast()) };
     let y = unsafe { perm.hash1d(x) };
     let z = unsafe { perm.hash1d(y) };
     Vec3::from([x, y, z]).cast() / PERMUTATION_TABLE_SIZE as f64
@@ -106,7 +106,7 @@ fn point3d(perm: &PermutationTable, x0: Vec3<f64>) -> Vec3<f64> {
 
 #[inline]
 fn point4d(perm: &PermutationTable, x0: Vec4<f64>) -> Vec4<f64> {
-    let x = unsafe { perm.hash4d_vec(x0.cast().rem_euclid(PERMUTATION_TABLE_SIZE)) };
+    let x = unsafe { perm.hash4d_vec(x0.rem_euclid(PERMUTATION_TABLE_SIZE as f64).cast()) };
     let y = unsafe { perm.hash1d(x) };
     let z = unsafe { perm.hash1d(y) };
     let w = unsafe { perm.hash1d(z) };
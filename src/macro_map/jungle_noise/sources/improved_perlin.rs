@@ -1,6 +1,9 @@
 use super::functional::{self, constants::PERMUTATION_TABLE_SIZE};
 use crate::macro_map::jungle_noise::{
-    generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D},
+    generator::{
+        Generator, Generator1D, Generator2D, Generator3D, Generator4D, GeneratorF32,
+        GradientGenerator,
+    },
     utils::ptable::{PermutationTable, Seed},
 };
 
@@ -15,13 +18,13 @@ use crate::macro_map::jungle_noise::{
 /// Direct instantiation of this struct:
 ///
 /// ```
-/// # use libnoise::{ImprovedPerlin, Generator};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, sources::ImprovedPerlin};
 /// let generator = ImprovedPerlin::new(42);
 /// let value = generator.sample([0.2, 0.5]);
 /// ```
 ///
-/// [`improved_perlin()`]: crate::Source::improved_perlin
-/// [`Source`]: crate::Source
+/// [`improved_perlin()`]: crate::macro_map::jungle_noise::source::Source::improved_perlin
+/// [`Source`]: crate::macro_map::jungle_noise::source::Source
 #[derive(Clone, Debug)]
 pub struct ImprovedPerlin<const D: usize> {
     permutation_table: PermutationTable,
@@ -68,3 +71,59 @@ impl Generator<4> for ImprovedPerlin<4> {
         functional::improved_perlin::noise4d(&self.permutation_table, point)
     }
 }
+
+impl GradientGenerator<1> for ImprovedPerlin<1> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 1]) -> (f64, [f64; 1]) {
+        functional::improved_perlin::noise1d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GradientGenerator<2> for ImprovedPerlin<2> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        functional::improved_perlin::noise2d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GradientGenerator<3> for ImprovedPerlin<3> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 3]) -> (f64, [f64; 3]) {
+        functional::improved_perlin::noise3d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GradientGenerator<4> for ImprovedPerlin<4> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 4]) -> (f64, [f64; 4]) {
+        functional::improved_perlin::noise4d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<1> for ImprovedPerlin<1> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 1]) -> f32 {
+        functional::improved_perlin::noise1d(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<2> for ImprovedPerlin<2> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 2]) -> f32 {
+        functional::improved_perlin::noise2d(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<3> for ImprovedPerlin<3> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 3]) -> f32 {
+        functional::improved_perlin::noise3d(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<4> for ImprovedPerlin<4> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 4]) -> f32 {
+        functional::improved_perlin::noise4d(&self.permutation_table, point)
+    }
+}
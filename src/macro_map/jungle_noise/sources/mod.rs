@@ -1,3 +1,4 @@
+mod cellular;
 mod checkerboard;
 mod constant;
 mod custom;
@@ -7,7 +8,9 @@ mod perlin;
 mod simplex;
 mod value;
 mod worley;
+pub use cellular::Cellular;
 pub use checkerboard::Checkerboard;
+pub use functional::cellular::{CellularReturn, DistanceMetric};
 pub use constant::Constant;
 pub use custom::Custom;
 pub use improved_perlin::ImprovedPerlin;
@@ -15,3 +18,106 @@ pub use perlin::Perlin;
 pub use simplex::Simplex;
 pub use value::Value;
 pub use worley::Worley;
+
+#[cfg(test)]
+mod tests {
+    use crate::macro_map::jungle_noise::generator::{Generator, GeneratorF32, GradientGenerator};
+    use crate::macro_map::jungle_noise::source::Source;
+
+    fn max_deviation<const D: usize, G: GeneratorF32<D>>(generator: &G) -> f64 {
+        (0..4096)
+            .map(|i| {
+                let point: [f64; D] = std::array::from_fn(|axis| {
+                    ((i * (7 + axis * 13)) % 4096) as f64 * 0.137 - 280.0
+                });
+                let single = generator.sample_f32(point.map(|x| x as f32)) as f64;
+                (single - generator.sample(point)).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn single_precision_matches_double_precision() {
+        macro_rules! check {
+            ($($dim:literal),*) => {$(
+                assert!(max_deviation(&Source::<$dim>::simplex(7)) < 1e-3);
+                assert!(max_deviation(&Source::<$dim>::perlin(7)) < 1e-3);
+                assert!(max_deviation(&Source::<$dim>::improved_perlin(7)) < 1e-3);
+                assert!(max_deviation(&Source::<$dim>::value(7)) < 1e-3);
+                assert!(max_deviation(&Source::<$dim>::constant(0.3)) < 1e-6);
+                assert!(max_deviation(
+                    &Source::<$dim>::simplex(7)
+                        .scale([0.01; $dim])
+                        .translate([3.5; $dim])
+                        .fbm(5, 1.0, 2.0, 0.5)
+                        .abs()
+                        .neg()
+                        .mul(2.0)
+                        .add(1.0)
+                ) < 1e-3);
+            )*};
+        }
+        check!(1, 2, 3, 4);
+    }
+
+    /// The largest deviation of the analytic gradient from central differences, relative to the
+    /// larger of the two. Points are offset from the lattice so that no difference straddles a
+    /// cell boundary, where lattice noise is only continuous up to its first derivative.
+    fn max_gradient_deviation<const D: usize, G: GradientGenerator<D>>(generator: &G) -> f64 {
+        let h = 1e-5;
+        (0..512)
+            .map(|i| {
+                let point: [f64; D] = std::array::from_fn(|axis| {
+                    ((i * (7 + axis * 13)) % 512) as f64 * 0.137 - 35.0 + 0.031
+                });
+                let (_, gradient) = generator.sample_with_gradient(point);
+                (0..D)
+                    .map(|axis| {
+                        let mut ahead = point;
+                        let mut behind = point;
+                        ahead[axis] += h;
+                        behind[axis] -= h;
+                        let difference =
+                            (generator.sample(ahead) - generator.sample(behind)) / (2.0 * h);
+                        (gradient[axis] - difference).abs() / gradient[axis].abs().max(1.0)
+                    })
+                    .fold(0.0, f64::max)
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn gradients_match_finite_differences() {
+        macro_rules! check {
+            ($($dim:literal),*) => {$(
+                assert!(max_gradient_deviation(&Source::<$dim>::simplex(7)) < 1e-5);
+                assert!(max_gradient_deviation(&Source::<$dim>::perlin(7)) < 1e-5);
+                assert!(max_gradient_deviation(&Source::<$dim>::improved_perlin(7)) < 1e-5);
+                assert!(max_gradient_deviation(&Source::<$dim>::value(7)) < 1e-5);
+                assert!(max_gradient_deviation(&Source::<$dim>::simplex(7).fbm(5, 0.3, 2.0, 0.5)) < 1e-5);
+            )*};
+        }
+        check!(1, 2, 3, 4);
+    }
+
+    #[test]
+    fn negative_coordinates_wrap_around_the_permutation_table() {
+        fn repeats<const D: usize, G: Generator<D>>(generator: &G) -> bool {
+            (0..512).all(|i| {
+                let point: [f64; D] =
+                    std::array::from_fn(|axis| ((i * (7 + axis * 13)) % 512) as f64 * 0.375 + 0.125);
+                generator.sample(point) == generator.sample(point.map(|x| x - 256.0))
+            })
+        }
+        macro_rules! check {
+            ($($dim:literal),*) => {$(
+                assert!(repeats(&Source::<$dim>::perlin(7)));
+                assert!(repeats(&Source::<$dim>::improved_perlin(7)));
+                assert!(repeats(&Source::<$dim>::value(7)));
+                assert!(repeats(&Source::<$dim>::worley(7)));
+            )*};
+        }
+        check!(1, 2, 3, 4);
+        assert!(repeats(&Source::<1>::simplex(7)));
+    }
+}
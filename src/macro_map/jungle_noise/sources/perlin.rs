@@ -1,6 +1,9 @@
 use super::functional::{self, constants::PERMUTATION_TABLE_SIZE};
 use crate::macro_map::jungle_noise::{
-    generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D},
+    generator::{
+        Generator, Generator1D, Generator2D, Generator3D, Generator4D, GeneratorF32,
+        GradientGenerator,
+    },
     utils::ptable::{PermutationTable, Seed},
 };
 
@@ -15,13 +18,13 @@ use crate::macro_map::jungle_noise::{
 /// Direct instantiation of this struct:
 ///
 /// ```
-/// # use libnoise::{Perlin, Generator};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, sources::Perlin};
 /// let generator = Perlin::new(42);
 /// let value = generator.sample([0.2, 0.5]);
 /// ```
 ///
-/// [`perlin()`]: crate::Source::perlin
-/// [`Source`]: crate::Source
+/// [`perlin()`]: crate::macro_map::jungle_noise::source::Source::perlin
+/// [`Source`]: crate::macro_map::jungle_noise::source::Source
 #[derive(Clone, Debug)]
 pub struct Perlin<const D: usize> {
     permutation_table: PermutationTable,
@@ -68,3 +71,59 @@ impl Generator<4> for Perlin<4> {
         functional::perlin::noise4d(&self.permutation_table, point)
     }
 }
+
+impl GradientGenerator<1> for Perlin<1> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 1]) -> (f64, [f64; 1]) {
+        functional::perlin::noise1d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GradientGenerator<2> for Perlin<2> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        functional::perlin::noise2d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GradientGenerator<3> for Perlin<3> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 3]) -> (f64, [f64; 3]) {
+        functional::perlin::noise3d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GradientGenerator<4> for Perlin<4> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 4]) -> (f64, [f64; 4]) {
+        functional::perlin::noise4d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<1> for Perlin<1> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 1]) -> f32 {
+        functional::perlin::noise1d(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<2> for Perlin<2> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 2]) -> f32 {
+        functional::perlin::noise2d(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<3> for Perlin<3> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 3]) -> f32 {
+        functional::perlin::noise3d(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<4> for Perlin<4> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 4]) -> f32 {
+        functional::perlin::noise4d(&self.permutation_table, point)
+    }
+}
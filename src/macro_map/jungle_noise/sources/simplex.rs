@@ -1,6 +1,9 @@
 use super::functional::{self, constants::PERMUTATION_TABLE_SIZE};
 use crate::macro_map::jungle_noise::{
-    generator::{Generator, Generator1D, Generator2D, Generator3D, Generator4D},
+    generator::{
+        Generator, Generator1D, Generator2D, Generator3D, Generator4D, GeneratorF32,
+        GradientGenerator,
+    },
     utils::ptable::{PermutationTable, Seed},
 };
 
@@ -15,13 +18,13 @@ use crate::macro_map::jungle_noise::{
 /// Direct instantiation of this struct:
 ///
 /// ```
-/// # use libnoise::{Simplex, Generator};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, sources::Simplex};
 /// let generator = Simplex::new(42);
 /// let value = generator.sample([0.2, 0.5]);
 /// ```
 ///
-/// [`simplex()`]: crate::Source::simplex
-/// [`Source`]: crate::Source
+/// [`simplex()`]: crate::macro_map::jungle_noise::source::Source::simplex
+/// [`Source`]: crate::macro_map::jungle_noise::source::Source
 #[derive(Clone, Debug)]
 pub struct Simplex<const D: usize> {
     permutation_table: PermutationTable,
@@ -68,3 +71,59 @@ impl Generator<4> for Simplex<4> {
         functional::simplex::noise4d(&self.permutation_table, point)
     }
 }
+
+impl GradientGenerator<1> for Simplex<1> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 1]) -> (f64, [f64; 1]) {
+        functional::simplex::noise1d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GradientGenerator<2> for Simplex<2> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        functional::simplex::noise2d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GradientGenerator<3> for Simplex<3> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 3]) -> (f64, [f64; 3]) {
+        functional::simplex::noise3d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GradientGenerator<4> for Simplex<4> {
+    #[inline]
+    fn sample_with_gradient(&self, point: [f64; 4]) -> (f64, [f64; 4]) {
+        functional::simplex::noise4d_with_gradient(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<1> for Simplex<1> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 1]) -> f32 {
+        functional::simplex::noise1d(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<2> for Simplex<2> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 2]) -> f32 {
+        functional::simplex::noise2d(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<3> for Simplex<3> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 3]) -> f32 {
+        functional::simplex::noise3d(&self.permutation_table, point)
+    }
+}
+
+impl GeneratorF32<4> for Simplex<4> {
+    #[inline]
+    fn sample_f32(&self, point: [f32; 4]) -> f32 {
+        functional::simplex::noise4d(&self.permutation_table, point)
+    }
+}
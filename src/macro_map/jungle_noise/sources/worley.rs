@@ -15,13 +15,13 @@ use crate::macro_map::jungle_noise::{
 /// Direct instantiation of this struct:
 ///
 /// ```
-/// # use libnoise::{Worley, Generator};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, sources::Worley};
 /// let generator = Worley::new(42);
 /// let value = generator.sample([0.2, 0.5]);
 /// ```
 ///
-/// [`worley()`]: crate::Source::worley
-/// [`Source`]: crate::Source
+/// [`worley()`]: crate::macro_map::jungle_noise::source::Source::worley
+/// [`Source`]: crate::macro_map::jungle_noise::source::Source
 #[derive(Clone, Debug)]
 pub struct Worley<const D: usize> {
     permutation_table: PermutationTable,
@@ -0,0 +1,44 @@
+use super::vector::NumCast;
+use num_traits::{Euclid, Float, Pow};
+use std::ops::{AddAssign, MulAssign};
+
+/// A floating point type in which noise can be computed.
+///
+/// This is implemented for [`f64`], the precision used throughout the crate, and for [`f32`],
+/// which trades precision for throughput when generating large amounts of noise. Constants such
+/// as gradient lookup tables are stored as [`f64`] and converted using [`cast()`] where needed,
+/// so that both precisions share the same tables.
+///
+/// [`cast()`]: NoiseFloat::cast
+pub(crate) trait NoiseFloat:
+    Float + Euclid + Pow<i32, Output = Self> + AddAssign + MulAssign + NumCast<f64> + NumCast<usize>
+{
+    /// Converts an [`f64`] constant to this type. For [`f64`] this is the identity.
+    #[inline]
+    fn cast(value: f64) -> Self {
+        <Self as NumCast<f64>>::from(value)
+    }
+
+    /// Converts a hash value to this type.
+    #[inline]
+    fn from_hash(hash: usize) -> Self {
+        <Self as NumCast<usize>>::from(hash)
+    }
+
+    /// Converts a non-negative lattice coordinate to an index into the permutation table.
+    fn to_index(self) -> usize;
+}
+
+impl NoiseFloat for f32 {
+    #[inline]
+    fn to_index(self) -> usize {
+        self as usize
+    }
+}
+
+impl NoiseFloat for f64 {
+    #[inline]
+    fn to_index(self) -> usize {
+        self as usize
+    }
+}
@@ -1,2 +1,4 @@
+mod float;
 mod vector;
+pub(crate) use float::NoiseFloat;
 pub(crate) use vector::{Vec2, Vec3, Vec4};
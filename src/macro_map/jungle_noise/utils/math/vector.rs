@@ -1,3 +1,4 @@
+use super::NoiseFloat;
 use num_traits::{identities::Zero, Euclid, Float, Pow};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
@@ -56,6 +57,11 @@ macro_rules! impl_vector {
                 $name::new($(T2::from(self.$x),)+)
             }
 
+            #[inline]
+            pub(crate) fn to_index(self) -> $name<usize> where T: NoiseFloat {
+                $name::new($(self.$x.to_index(),)+)
+            }
+
             #[inline]
             pub(crate) fn map<F>(self, f: F) -> Self where F: Fn(T) -> T {
                 Self { $($x: f(self.$x)),+ }
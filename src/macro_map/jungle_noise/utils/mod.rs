@@ -1,5 +1,5 @@
 pub(crate) mod math;
 pub mod noisebuf;
 pub(crate) mod ptable;
-#[cfg(feature = "image")]
+pub mod range;
 pub mod visualizer;
@@ -1,5 +1,6 @@
 use crate::macro_map::jungle_noise::generator::Generator;
 use itertools::Itertools;
+use rayon::prelude::*;
 use std::ops::{Index, IndexMut};
 
 /// A struct for generating an n-dimensional array and efficiently filling it with noise values.
@@ -24,7 +25,7 @@ use std::ops::{Index, IndexMut};
 /// that the buffer samples the generator on points of a hypergrid:
 ///
 /// ```
-/// # use libnoise::{Source, Generator, NoiseBuffer};
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source, utils::noisebuf::NoiseBuffer};
 /// // create a generator
 /// let generator = Source::simplex(42);
 ///
@@ -36,12 +37,19 @@ use std::ops::{Index, IndexMut};
 ///
 /// The scale or position of the
 /// grid can be modified by calling adapters such as [`scale()`], [`translate()`], or [`rotate()`]
-/// on the generator before using it to create a [`NoiseBuffer`].
+/// on the generator before using it to create a [`NoiseBuffer`], or by providing an origin and
+/// step along each axis to [`new_with_transform()`].
+///
+/// For large buffers, [`new_parallel()`] and [`new_with_transform_parallel()`] fill the buffer
+/// on multiple threads. The resulting values are identical to those of the sequential methods.
 ///
 /// [`new()`]: NoiseBuffer::new
+/// [`new_with_transform()`]: NoiseBuffer::new_with_transform
+/// [`new_parallel()`]: NoiseBuffer::new_parallel
+/// [`new_with_transform_parallel()`]: NoiseBuffer::new_with_transform_parallel
 /// [`scale()`]: Generator::scale
 /// [`translate()`]: Generator::translate
-/// [`rotate()`]: crate::Generator2D::rotate
+/// [`rotate()`]: crate::macro_map::jungle_noise::generator::Generator2D::rotate
 #[derive(Clone, Debug)]
 pub struct NoiseBuffer<const D: usize> {
     /// Stores the length of the n-dimensional array along each dimension.
@@ -78,11 +86,18 @@ macro_rules! impl_new {
             /// by the given `generator`. For further detail see the
             /// [Creating a noise buffer](#creating-a-noise-buffer) section.
             pub fn new<G: Generator<$dim>>(shape: [usize; $dim], generator: &G) -> Self {
-                let mut noisebuf = Self::new_empty(shape);
-                for point in noisebuf.tensor_indices() {
-                    noisebuf[point] = generator.sample(point.map(|x| x as f64));
-                }
-                noisebuf
+                Self::new_with_transform(shape, [0.0; $dim], [1.0; $dim], generator)
+            }
+
+            /// Creates a new noise buffer with the given `shape`, filled in parallel with noise
+            /// generated by the given `generator`. The result is identical to that of [`new()`].
+            ///
+            /// [`new()`]: NoiseBuffer::new
+            pub fn new_parallel<G: Generator<$dim> + Sync>(
+                shape: [usize; $dim],
+                generator: &G,
+            ) -> Self {
+                Self::new_with_transform_parallel(shape, [0.0; $dim], [1.0; $dim], generator)
             }
         }
     };
@@ -99,6 +114,81 @@ impl_new!(3);
 impl_new!(4);
 
 impl<const D: usize> NoiseBuffer<D> {
+    /// Creates a new noise buffer with the given `shape` and filled with noise generated by the
+    /// given `generator`, where the buffer index `i` along each axis corresponds to the point
+    /// `origin + i * step` along that axis.
+    ///
+    /// With an `origin` of 0 and a `step` of 1 along every axis, this is equivalent to
+    /// [`new()`]. Otherwise, it avoids wrapping the generator in [`scale()`] and [`translate()`]
+    /// adapters just to position the sampled grid.
+    ///
+    /// ```
+    /// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source, utils::noisebuf::NoiseBuffer};
+    /// let generator = Source::simplex(42);
+    ///
+    /// let buf = NoiseBuffer::<2>::new_with_transform([30, 20], [-1.0, 4.0], [0.1, 0.5], &generator);
+    ///
+    /// assert_eq!(buf[[17, 9]], generator.sample([-1.0 + 17.0 * 0.1, 4.0 + 9.0 * 0.5]));
+    /// ```
+    ///
+    /// [`new()`]: NoiseBuffer::new
+    /// [`scale()`]: Generator::scale
+    /// [`translate()`]: Generator::translate
+    pub fn new_with_transform<G: Generator<D>>(
+        shape: [usize; D],
+        origin: [f64; D],
+        step: [f64; D],
+        generator: &G,
+    ) -> Self {
+        let mut noisebuf = Self::new_empty(shape);
+        let row_len = noisebuf.row_len();
+        if row_len > 0 {
+            let mut points = Vec::with_capacity(row_len);
+            for (row, values) in noisebuf.buffer.chunks_mut(row_len).enumerate() {
+                row_points(&shape, row, &origin, &step, &mut points);
+                generator.sample_many(&points, values);
+            }
+        }
+        noisebuf
+    }
+
+    /// Creates a new noise buffer like [`new_with_transform()`], but fills the buffer in
+    /// parallel. The result is identical to that of [`new_with_transform()`].
+    ///
+    /// [`new_with_transform()`]: NoiseBuffer::new_with_transform
+    pub fn new_with_transform_parallel<G: Generator<D> + Sync>(
+        shape: [usize; D],
+        origin: [f64; D],
+        step: [f64; D],
+        generator: &G,
+    ) -> Self {
+        let mut noisebuf = Self::new_empty(shape);
+        let row_len = noisebuf.row_len();
+        if row_len > 0 {
+            noisebuf
+                .buffer
+                .par_chunks_mut(row_len)
+                .enumerate()
+                .for_each_init(
+                    || Vec::with_capacity(row_len),
+                    |points, (row, values)| {
+                        row_points(&shape, row, &origin, &step, points);
+                        generator.sample_many(points, values);
+                    },
+                );
+        }
+        noisebuf
+    }
+
+    /// The length of a contiguous row in the flat buffer, that is, along the last axis.
+    fn row_len(&self) -> usize {
+        if self.buffer.is_empty() {
+            0
+        } else {
+            self.shape[D - 1]
+        }
+    }
+
     fn new_empty(shape: [usize; D]) -> Self {
         let bufsize = shape.iter().product();
         Self {
@@ -116,7 +206,8 @@ impl<const D: usize> NoiseBuffer<D> {
             .sum()
     }
 
-    pub(crate) fn tensor_indices(&self) -> impl Iterator<Item = [usize; D]> {
+    /// Iterates over every index of the buffer, with the last axis varying fastest.
+    pub fn tensor_indices(&self) -> impl Iterator<Item = [usize; D]> {
         self.shape
             .iter()
             .map(|&dim_size| 0..dim_size)
@@ -125,6 +216,28 @@ impl<const D: usize> NoiseBuffer<D> {
     }
 }
 
+/// Computes the points sampled for the given `row` of a buffer with the given `shape`, that is,
+/// all points whose indices only differ along the last axis.
+fn row_points<const D: usize>(
+    shape: &[usize; D],
+    row: usize,
+    origin: &[f64; D],
+    step: &[f64; D],
+    points: &mut Vec<[f64; D]>,
+) {
+    let mut index = [0; D];
+    let mut rest = row;
+    for axis in (0..D - 1).rev() {
+        index[axis] = rest % shape[axis];
+        rest /= shape[axis];
+    }
+    points.clear();
+    points.extend((0..shape[D - 1]).map(|i| {
+        index[D - 1] = i;
+        std::array::from_fn(|axis| origin[axis] + index[axis] as f64 * step[axis])
+    }));
+}
+
 pub(crate) fn precompute_flat_index_offsets(shape: &[usize]) -> Vec<usize> {
     let offsets = shape
         .iter()
@@ -137,3 +250,46 @@ pub(crate) fn precompute_flat_index_offsets(shape: &[usize]) -> Vec<usize> {
         .collect::<Vec<usize>>();
     offsets.into_iter().rev().collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::macro_map::jungle_noise::source::Source;
+
+    #[test]
+    fn parallel_fill_matches_sequential_fill() {
+        let generator = Source::simplex(42).fbm(3, 0.05, 2.0, 0.5);
+        let sequential = NoiseBuffer::<3>::new([13, 7, 9], &generator);
+        let parallel = NoiseBuffer::<3>::new_parallel([13, 7, 9], &generator);
+        assert_eq!(sequential.buffer, parallel.buffer);
+        for point in sequential.tensor_indices() {
+            assert_eq!(sequential[point], generator.sample(point.map(|x| x as f64)));
+        }
+    }
+
+    #[test]
+    fn transform_positions_the_grid() {
+        let generator = Source::simplex(42).into_dyn();
+        let (origin, step) = ([-1.0, 4.0], [0.1, 0.5]);
+        let sequential = NoiseBuffer::<2>::new_with_transform([30, 20], origin, step, &generator);
+        let parallel =
+            NoiseBuffer::<2>::new_with_transform_parallel([30, 20], origin, step, &generator);
+        assert_eq!(sequential.buffer, parallel.buffer);
+        for point in sequential.tensor_indices() {
+            let expected = generator.sample([
+                origin[0] + point[0] as f64 * step[0],
+                origin[1] + point[1] as f64 * step[1],
+            ]);
+            assert_eq!(sequential[point], expected);
+        }
+    }
+
+    #[test]
+    fn empty_shapes_produce_empty_buffers() {
+        let generator = Source::constant(2.0);
+        assert!(NoiseBuffer::<2>::new([0, 5], &generator).buffer.is_empty());
+        assert!(NoiseBuffer::<2>::new_parallel([5, 0], &generator)
+            .buffer
+            .is_empty());
+    }
+}
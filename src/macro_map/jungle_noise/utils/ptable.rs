@@ -5,7 +5,10 @@ use rand_chacha::{rand_core::SeedableRng, ChaCha12Rng};
 /// A trait attached to valid seed types for noise sources.
 ///
 /// This trait is implemented for `u64` and `[u8; 32]`. In doing so, both
-/// approaches to seed the underlying RNG are exposed.
+/// approaches to seed the underlying RNG are exposed. It is also implemented for
+/// [`WorldSeed`], so derived sub-seeds can seed noise sources directly.
+///
+/// [`WorldSeed`]: crate::macro_map::seed::WorldSeed
 pub trait Seed {
     fn construct_rng(self) -> ChaCha12Rng;
 }
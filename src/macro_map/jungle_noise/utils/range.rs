@@ -0,0 +1,139 @@
+use crate::macro_map::jungle_noise::generator::Generator;
+use crate::macro_map::jungle_noise::utils::noisebuf::NoiseBuffer;
+
+/// Empirical statistics on the range of values produced by a generator.
+///
+/// Most generators only guarantee loose theoretical bounds on their output. Fractal adapters
+/// such as [`fbm()`], for instance, rarely get close to the [-1, 1] range they are normalized
+/// to. A [`RangeAnalysis`] samples a generator on a regular grid spanning a region of the input
+/// space and records the minimum, maximum, mean, and percentiles of the sampled values. These can
+/// then be used to map the output of the generator into a known range using the [`normalize()`]
+/// or [`remap()`] adapters:
+///
+/// ```
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source, utils::range::RangeAnalysis};
+/// let generator = Source::simplex(42).fbm(6, 0.01, 2.0, 0.5);
+///
+/// // sample the generator on a 128x128 grid spanning the region [0, 1024]^2
+/// let range = RangeAnalysis::new(&generator, [0.0; 2], [1024.0; 2], 128);
+///
+/// // map the observed range onto [0, 1]
+/// let normalized = generator.normalize(&range);
+///
+/// // alternatively, map the central 98% of values onto [0, 1], clamping outliers
+/// let robust = Source::<2>::simplex(42)
+///     .fbm(6, 0.01, 2.0, 0.5)
+///     .remap([range.percentile(1.0), range.percentile(99.0)], [0.0, 1.0]);
+///
+/// assert!((0.0..=1.0).contains(&normalized.sample([300.0, 700.0])));
+/// assert!((0.0..=1.0).contains(&robust.sample([300.0, 700.0])));
+/// ```
+///
+/// As the analysis is empirical, values outside of the observed range may occur in regions which
+/// were not sampled. Adapters built from a [`RangeAnalysis`] clamp their output for this reason.
+///
+/// [`fbm()`]: Generator::fbm
+/// [`normalize()`]: Generator::normalize
+/// [`remap()`]: Generator::remap
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeAnalysis {
+    /// The smallest sampled value.
+    pub min: f64,
+    /// The largest sampled value.
+    pub max: f64,
+    /// The arithmetic mean of all sampled values.
+    pub mean: f64,
+    /// The value below which `i` percent of the samples lie, for each `i` in 0 to 100.
+    percentiles: Vec<f64>,
+}
+
+impl RangeAnalysis {
+    /// Sample the given `generator` on a grid of `resolution` points along each axis, spanning
+    /// the region from `origin` to `origin + extent`, and collect statistics on the results.
+    pub fn new<const D: usize, G: Generator<D>>(
+        generator: &G,
+        origin: [f64; D],
+        extent: [f64; D],
+        resolution: usize,
+    ) -> Self {
+        let step = extent.map(|length| length / resolution.max(1) as f64);
+        let noisebuf = NoiseBuffer::new_with_transform([resolution; D], origin, step, generator);
+        Self::from_values(noisebuf.buffer)
+    }
+
+    /// Collect statistics on the given `values`. NaN values are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no values which are not NaN are given.
+    pub fn from_values(mut values: Vec<f64>) -> Self {
+        values.retain(|value| !value.is_nan());
+        assert!(
+            !values.is_empty(),
+            "a range analysis requires at least one value"
+        );
+        values.sort_by(f64::total_cmp);
+        let last = values.len() - 1;
+        let percentiles = (0..=100)
+            .map(|percent| {
+                // linear interpolation between the closest ranks
+                let rank = percent as f64 / 100.0 * last as f64;
+                let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+                let t = rank - lower as f64;
+                values[lower] + t * (values[upper] - values[lower])
+            })
+            .collect();
+        Self {
+            min: values[0],
+            max: values[last],
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+            percentiles,
+        }
+    }
+
+    /// Returns the value below which the given `percent` of samples lie. Between whole percents,
+    /// the result is linearly interpolated. `percent` is clamped to the [0, 100] range.
+    pub fn percentile(&self, percent: f64) -> f64 {
+        let percent = percent.clamp(0.0, 100.0);
+        let lower = percent.floor() as usize;
+        let upper = percent.ceil() as usize;
+        let t = percent - lower as f64;
+        self.percentiles[lower] + t * (self.percentiles[upper] - self.percentiles[lower])
+    }
+
+    /// Returns the median of the sampled values.
+    pub fn median(&self) -> f64 {
+        self.percentiles[50]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::macro_map::jungle_noise::source::Source;
+
+    #[test]
+    fn percentiles_interpolate_between_ranks() {
+        let range = RangeAnalysis::from_values((0..=10).rev().map(|x| x as f64).collect());
+        assert_eq!((range.min, range.max, range.mean), (0.0, 10.0, 5.0));
+        assert_eq!(range.median(), 5.0);
+        assert_eq!(range.percentile(25.0), 2.5);
+        assert_eq!(range.percentile(12.5), 1.25);
+        assert_eq!(range.percentile(-5.0), 0.0);
+    }
+
+    #[test]
+    fn normalize_maps_the_sampled_region_onto_the_unit_interval() {
+        let generator = Source::<2>::simplex(42).fbm(4, 0.05, 2.0, 0.5);
+        let range = RangeAnalysis::new(&generator, [-10.0, 5.0], [100.0, 100.0], 64);
+        assert!(range.min < range.mean && range.mean < range.max);
+        let normalized = RangeAnalysis::new(
+            &generator.normalize(&range),
+            [-10.0, 5.0],
+            [100.0, 100.0],
+            64,
+        );
+        assert!(normalized.min.abs() < 1e-12);
+        assert!((normalized.max - 1.0).abs() < 1e-12);
+    }
+}
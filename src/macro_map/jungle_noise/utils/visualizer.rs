@@ -1,20 +1,22 @@
-use crate::core::generator::Generator;
-use crate::core::utils::noisebuf::NoiseBuffer;
+use crate::macro_map::jungle_noise::generator::Generator;
+use crate::macro_map::jungle_noise::utils::noisebuf::NoiseBuffer;
 use image::{
     codecs::gif::{GifEncoder, Repeat},
-    ColorType, GrayImage, ImageError,
+    ExtendedColorType, GrayImage, ImageError, RgbImage,
 };
 use itertools::Itertools;
 use std::{
-    fs::OpenOptions,
+    fmt,
+    fs::{self, OpenOptions},
     io::Error,
     ops::{Index, IndexMut},
 };
 
+/// The number of bins of the value histogram collected by a [`Visualizer`].
+pub const HISTOGRAM_BINS: usize = 64;
+
 /// A struct for visualizing the output of a generator.
 ///
-/// The `image` feature must be enabled for writing images of visualizations.
-///
 /// This struct represents a simple way to quickly visualize the output of a [`Generator`] by
 /// building a [`NoiseBuffer`] of a given size, populating it with data, and creating an PNG or
 /// GIF file visualizing said data.
@@ -35,15 +37,13 @@ use std::{
 /// The usage of this struct is simple and analogous to that of [`NoiseBuffer`]:
 ///
 /// ```
-/// # use libnoise::{Source, NoiseBuffer, Visualizer};
-/// # use tempdir::TempDir;
+/// # use fungal_jungle::macro_map::jungle_noise::{source::Source, utils::noisebuf::NoiseBuffer, utils::visualizer::Visualizer};
 /// // create a generator
 /// let generator = Source::simplex(42);
 ///
 /// // create a visualizer and use it to visualize the output of the generator
 /// let path = "output.png";
-/// # let tmp_dir = TempDir::new("libnoise").unwrap();
-/// # let path = &tmp_dir.path().join(path).into_os_string().into_string().unwrap();
+/// # let path = &std::env::temp_dir().join(path).into_os_string().into_string().unwrap();
 /// Visualizer::<3>::new([30, 20, 25], &generator).write_to_file(path).unwrap();
 /// ```
 ///
@@ -51,8 +51,7 @@ use std::{
 /// to a [`Visualizer`]:
 ///
 /// ```
-/// # use libnoise::{Source, NoiseBuffer, Visualizer};
-/// # use tempdir::TempDir;
+/// # use fungal_jungle::macro_map::jungle_noise::{source::Source, utils::noisebuf::NoiseBuffer, utils::visualizer::Visualizer};
 /// // create a generator
 /// let generator = Source::simplex(42);
 ///
@@ -61,10 +60,28 @@ use std::{
 ///
 /// // create a visualizer and use it to visualize the output of the generator
 /// let path = "output.png";
-/// # let tmp_dir = TempDir::new("libnoise").unwrap();
-/// # let path = &tmp_dir.path().join(path).into_os_string().into_string().unwrap();
+/// # let path = &std::env::temp_dir().join(path).into_os_string().into_string().unwrap();
 /// Visualizer::from(buf).write_to_file(path);
 /// ```
+///
+/// 2D buffers can additionally be written in colour by mapping values through a [`Palette`].
+/// Independent of the dimensionality, a visualizer collects [`Statistics`] on the values it was
+/// created from, which can be inspected directly, or written as a text report and as a histogram
+/// image:
+///
+/// ```
+/// # use fungal_jungle::macro_map::jungle_noise::{generator::Generator, source::Source};
+/// # use fungal_jungle::macro_map::jungle_noise::utils::visualizer::{Palette, Visualizer};
+/// # let path = |name: &str| std::env::temp_dir().join(name).into_os_string().into_string().unwrap();
+/// let generator = Source::<2>::simplex(42).scale([0.05; 2]);
+/// let visualizer = Visualizer::<2>::new([64, 64], &generator);
+/// visualizer.write_to_file_with_palette(&path("terrain.png"), &Palette::terrain()).unwrap();
+/// visualizer.write_histogram_to_file(&path("histogram.png")).unwrap();
+/// visualizer.write_statistics_to_file(&path("statistics.txt")).unwrap();
+///
+/// let statistics = visualizer.statistics();
+/// assert!(statistics.min <= statistics.mean && statistics.mean <= statistics.max);
+/// ```
 #[derive(Clone, Debug)]
 pub struct Visualizer<const D: usize> {
     /// Stores the length of the underlying n-dimensional array along each dimension.
@@ -73,6 +90,8 @@ pub struct Visualizer<const D: usize> {
     offsets: [usize; D],
     /// The underlying flat vector storing the noise values as `u8` integers.
     pixel_buffer: Vec<u8>,
+    /// Statistics on the noise values, collected before they were quantized.
+    statistics: Statistics,
 }
 
 impl<const D: usize> Index<&[usize]> for Visualizer<D> {
@@ -95,12 +114,42 @@ impl<const D: usize> From<NoiseBuffer<D>> for Visualizer<D> {
         Self {
             shape: noisebuf.shape,
             offsets: noisebuf.offsets,
+            statistics: Statistics::new(&noisebuf.buffer),
             pixel_buffer: noisebuf.buffer.into_iter().map(norm_to_u8).collect(),
         }
     }
 }
 
 impl<const D: usize> Visualizer<D> {
+    /// Returns statistics on the values this visualizer was created from.
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Write a text file to the given `path`, reporting the minimum, maximum, and mean of the
+    /// visualized values along with their histogram.
+    pub fn write_statistics_to_file(&self, path: &str) -> Result<(), Error> {
+        fs::write(path, self.statistics.to_string())
+    }
+
+    /// Write a PNG file to the given `path`, showing the histogram of the visualized values as
+    /// a bar chart. Each bar covers an equal part of the [-1, 1] range, from left to right.
+    pub fn write_histogram_to_file(&self, path: &str) -> Result<(), ImageError> {
+        let (bar_width, height) = (4, 128);
+        let tallest = self.statistics.histogram.into_iter().max().unwrap().max(1);
+        let image = GrayImage::from_fn((HISTOGRAM_BINS * bar_width) as u32, height, |x, y| {
+            let count = self.statistics.histogram[x as usize / bar_width];
+            let bar_height = (count as f64 / tallest as f64 * height as f64).round() as u32;
+            if height - y <= bar_height {
+                image::Luma([255])
+            } else {
+                image::Luma([0])
+            }
+        });
+        image.save(path)?;
+        Ok(())
+    }
+
     fn flat_index(&self, index: &[usize]) -> usize {
         index
             .iter()
@@ -136,6 +185,8 @@ impl Visualizer<2> {
         NoiseBuffer::<2>::new(shape, generator).into()
     }
 
+    /// Write a PNG file to the given `path`, visualizing the output of the provided
+    /// generator. For further detail see the [struct-level documentation](Visualizer).
     pub fn write_to_file(&self, path: &str) -> Result<(), ImageError> {
         let image = GrayImage::from_raw(
             self.shape[1] as u32,
@@ -146,6 +197,23 @@ impl Visualizer<2> {
         image.save(path)?;
         Ok(())
     }
+
+    /// Write a PNG file to the given `path`, visualizing the output of the provided
+    /// generator in colour by mapping each value through the given `palette`.
+    pub fn write_to_file_with_palette(
+        &self,
+        path: &str,
+        palette: &Palette,
+    ) -> Result<(), ImageError> {
+        let buf = self
+            .pixel_buffer
+            .iter()
+            .flat_map(|&pixel| palette.colour(u8_to_norm(pixel)))
+            .collect();
+        let image = RgbImage::from_raw(self.shape[1] as u32, self.shape[0] as u32, buf).unwrap();
+        image.save(path)?;
+        Ok(())
+    }
 }
 
 impl Visualizer<3> {
@@ -189,7 +257,11 @@ impl Visualizer<4> {
     /// Write a GIF file to the given `path`, visualizing the output of the provided
     /// generator. For further detail see the [struct-level documentation](Visualizer).
     pub fn write_to_file(&self, path: &str) -> Result<(), Error> {
-        let file_out = OpenOptions::new().write(true).create(true).open(path)?;
+        let file_out = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
 
         let mut encoder = GifEncoder::new(file_out);
         encoder.set_repeat(Repeat::Infinite).unwrap();
@@ -203,7 +275,7 @@ impl Visualizer<4> {
                     if let Some(buf_idx) =
                         xyz_screen_to_buff_indices(p[0], p[1], z_idx, center.0, center.1, scale)
                     {
-                        buf[p[0] * self.shape[0] + p[1]] =
+                        buf[p[0] * self.shape[1] + p[1]] =
                             self[&[buf_idx.0, buf_idx.1, buf_idx.2, t]];
                     }
                 }
@@ -211,15 +283,15 @@ impl Visualizer<4> {
 
             buf = buf
                 .into_iter()
-                .flat_map(|val| std::iter::repeat(val).take(3))
+                .flat_map(|val| std::iter::repeat_n(val, 3))
                 .collect();
 
             encoder
                 .encode(
                     &buf,
-                    self.shape[0] as u32,
                     self.shape[1] as u32,
-                    ColorType::Rgb8,
+                    self.shape[0] as u32,
+                    ExtendedColorType::Rgb8,
                 )
                 .unwrap();
         }
@@ -227,10 +299,137 @@ impl Visualizer<4> {
     }
 }
 
+/// Statistics on a set of noise values, as collected by a [`Visualizer`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    /// The smallest value.
+    pub min: f64,
+    /// The largest value.
+    pub max: f64,
+    /// The arithmetic mean of all values.
+    pub mean: f64,
+    /// The number of values falling into each of [`HISTOGRAM_BINS`] equally sized bins spanning
+    /// the [-1, 1] range. Values outside of this range are counted in the outermost bins.
+    pub histogram: [usize; HISTOGRAM_BINS],
+}
+
+impl Statistics {
+    /// Collect statistics on the given `values`. NaN values are ignored.
+    pub fn new(values: &[f64]) -> Self {
+        let mut statistics = Self {
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            mean: 0.0,
+            histogram: [0; HISTOGRAM_BINS],
+        };
+        let mut count = 0;
+        for &value in values.iter().filter(|value| !value.is_nan()) {
+            statistics.min = statistics.min.min(value);
+            statistics.max = statistics.max.max(value);
+            statistics.mean += value;
+            let bin = ((value + 1.0) * 0.5 * HISTOGRAM_BINS as f64) as usize;
+            statistics.histogram[bin.min(HISTOGRAM_BINS - 1)] += 1;
+            count += 1;
+        }
+        statistics.mean /= count.max(1) as f64;
+        statistics
+    }
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "min: {}", self.min)?;
+        writeln!(f, "max: {}", self.max)?;
+        writeln!(f, "mean: {}", self.mean)?;
+        writeln!(f, "histogram:")?;
+        let bin_width = 2.0 / HISTOGRAM_BINS as f64;
+        for (bin, count) in self.histogram.iter().enumerate() {
+            let lower = -1.0 + bin as f64 * bin_width;
+            writeln!(f, "[{:+.4}, {:+.4}): {}", lower, lower + bin_width, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// A colour gradient used to map noise values onto colours.
+///
+/// A palette consists of colour stops, each assigning a colour to a value in the [-1, 1] range.
+/// Values between two stops are mapped to a linear interpolation of their colours, while values
+/// beyond the outermost stops are mapped to the colour of the nearest stop.
+#[derive(Clone, Debug)]
+pub struct Palette {
+    stops: Vec<(f64, [u8; 3])>,
+}
+
+impl Palette {
+    /// Create a new palette from the given colour `stops`, which need not be sorted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no colour stops are given.
+    pub fn new(stops: &[(f64, [u8; 3])]) -> Self {
+        assert!(
+            !stops.is_empty(),
+            "a palette requires at least one colour stop"
+        );
+        let mut stops = stops.to_vec();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops }
+    }
+
+    /// A palette fading from black to white.
+    pub fn grayscale() -> Self {
+        Self::new(&[(-1.0, [0, 0, 0]), (1.0, [255, 255, 255])])
+    }
+
+    /// A palette resembling a height map, going from deep water over beaches, grassland and
+    /// mountains to snow.
+    pub fn terrain() -> Self {
+        Self::new(&[
+            (-1.0, [0, 0, 96]),
+            (-0.25, [30, 90, 190]),
+            (0.0, [220, 210, 150]),
+            (0.1, [60, 150, 50]),
+            (0.5, [110, 90, 60]),
+            (0.8, [140, 140, 140]),
+            (1.0, [255, 255, 255]),
+        ])
+    }
+
+    /// A palette going from cold blue over white to hot red, suited for signed quantities.
+    pub fn heat() -> Self {
+        Self::new(&[
+            (-1.0, [40, 60, 200]),
+            (0.0, [255, 255, 255]),
+            (1.0, [200, 40, 30]),
+        ])
+    }
+
+    /// Map the given `value` onto a colour.
+    pub fn colour(&self, value: f64) -> [u8; 3] {
+        let k = self.stops.partition_point(|stop| stop.0 <= value);
+        if k == 0 {
+            return self.stops[0].1;
+        }
+        if k == self.stops.len() {
+            return self.stops[k - 1].1;
+        }
+        let ((lower, from), (upper, to)) = (self.stops[k - 1], self.stops[k]);
+        let t = (value - lower) / (upper - lower);
+        std::array::from_fn(|i| {
+            (from[i] as f64 + t * (to[i] as f64 - from[i] as f64)).round() as u8
+        })
+    }
+}
+
 pub(crate) fn norm_to_u8(x: f64) -> u8 {
     (127.5 + x * 127.5) as u8
 }
 
+fn u8_to_norm(x: u8) -> f64 {
+    x as f64 / 127.5 - 1.0
+}
+
 fn xyz_screen_to_buff_indices(
     x: usize,
     y: usize,
@@ -260,3 +459,53 @@ fn tensor_indices(shape: &[usize]) -> impl Iterator<Item = Vec<usize>> {
         .map(|&dim_size| 0..dim_size)
         .multi_cartesian_product()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::macro_map::jungle_noise::source::Source;
+
+    #[test]
+    fn statistics_summarize_values() {
+        let statistics = Statistics::new(&[-1.0, -0.5, 0.5, 1.0, f64::NAN]);
+        assert_eq!(statistics.min, -1.0);
+        assert_eq!(statistics.max, 1.0);
+        assert_eq!(statistics.mean, 0.0);
+        assert_eq!(statistics.histogram.iter().sum::<usize>(), 4);
+        assert_eq!(statistics.histogram[0], 1);
+        assert_eq!(statistics.histogram[HISTOGRAM_BINS - 1], 1);
+    }
+
+    #[test]
+    fn palette_interpolates_between_stops() {
+        let palette = Palette::new(&[(1.0, [200, 0, 0]), (-1.0, [0, 0, 100])]);
+        assert_eq!(palette.colour(-2.0), [0, 0, 100]);
+        assert_eq!(palette.colour(0.0), [100, 0, 50]);
+        assert_eq!(palette.colour(2.0), [200, 0, 0]);
+    }
+
+    #[test]
+    fn writes_debug_output() {
+        let dir = std::env::temp_dir().join(format!("jungle_noise_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name).into_os_string().into_string().unwrap();
+        let visualizer = Visualizer::<2>::new([24, 16], &Source::simplex(42));
+        visualizer
+            .write_to_file_with_palette(&path("terrain.png"), &Palette::terrain())
+            .unwrap();
+        visualizer
+            .write_histogram_to_file(&path("histogram.png"))
+            .unwrap();
+        visualizer
+            .write_statistics_to_file(&path("statistics.txt"))
+            .unwrap();
+        Visualizer::<4>::new([6, 4, 4, 2], &Source::simplex(42))
+            .write_to_file(&path("animation.gif"))
+            .unwrap();
+        let image = image::open(path("terrain.png")).unwrap();
+        assert_eq!((image.width(), image.height()), (16, 24));
+        let report = fs::read_to_string(path("statistics.txt")).unwrap();
+        assert!(report.starts_with("min: "));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
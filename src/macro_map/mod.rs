@@ -2,5 +2,7 @@ pub(crate) mod tiling_strategy;
 pub mod macromap;
 pub mod jungle_noise;
 pub mod terrain;
+pub mod planet;
+pub mod seed;
 pub mod generation;
 pub mod rendering;
\ No newline at end of file
@@ -0,0 +1,240 @@
+use std::f64::consts::{FRAC_PI_2, PI, SQRT_2, TAU};
+use crate::macro_map::jungle_noise::generator::{Generator, Generator3D};
+use crate::macro_map::jungle_noise::source::Source;
+
+/// A position on the surface of the planet, in radians.
+///
+/// Longitude lies in `[-π, π)` and increases eastwards, latitude lies in `[-π/2, π/2]` with the
+/// north pole at `π/2`.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct LatLon {
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+impl LatLon {
+    pub fn new(longitude: f64, latitude: f64) -> Self {
+        Self { longitude, latitude }
+    }
+
+    /// The point on the unit sphere with the poles on the z-axis.
+    pub fn to_unit_sphere(self) -> [f64; 3] {
+        let (sin_lon, cos_lon) = self.longitude.sin_cos();
+        let (sin_lat, cos_lat) = self.latitude.sin_cos();
+        [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat]
+    }
+
+    pub fn from_unit_sphere(point: [f64; 3]) -> Self {
+        let [x, y, z] = point;
+        Self {
+            longitude: y.atan2(x),
+            latitude: (z / (x * x + y * y + z * z).sqrt()).clamp(-1.0, 1.0).asin(),
+        }
+    }
+
+    /// Great-circle distance to `other` on the unit sphere.
+    pub fn distance(&self, other: &LatLon) -> f64 {
+        let d_lat = (other.latitude - self.latitude) / 2.0;
+        let d_lon = (other.longitude - self.longitude) / 2.0;
+        let a = d_lat.sin().powi(2)
+            + self.latitude.cos() * other.latitude.cos() * d_lon.sin().powi(2);
+        2.0 * a.sqrt().min(1.0).asin()
+    }
+}
+
+/// How the surface of the planet is laid out on a flat map.
+///
+/// All projections map the full longitude range onto the width of the map, so the east and west
+/// edges meet at the antimeridian.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Projection {
+    /// Longitude and latitude map linearly onto x and y. Simple, but rows near the poles cover
+    /// far less surface than rows at the equator.
+    #[default]
+    Equirectangular,
+    /// Lambert cylindrical equal-area projection: every pixel covers the same surface area, so
+    /// the poles are not over-represented.
+    LambertEqualArea,
+    /// Mollweide projection: equal-area and elliptical. Pixels outside the ellipse are not on
+    /// the planet.
+    Mollweide,
+}
+
+impl Projection {
+    /// Maps normalized map coordinates `u, v` in `[0, 1]`, with `v = 0` at the top (north) of the
+    /// map, to a position on the planet. Returns `None` for coordinates which aren't on the
+    /// planet.
+    pub fn unproject(&self, u: f64, v: f64) -> Option<LatLon> {
+        match self {
+            Projection::Equirectangular => {
+                Some(LatLon::new((u - 0.5) * TAU, (0.5 - v) * PI))
+            }
+            Projection::LambertEqualArea => {
+                Some(LatLon::new((u - 0.5) * TAU, (1.0 - 2.0 * v).clamp(-1.0, 1.0).asin()))
+            }
+            Projection::Mollweide => {
+                let x = (u - 0.5) * 4.0 * SQRT_2;
+                let y = (0.5 - v) * 2.0 * SQRT_2;
+                if (x / (2.0 * SQRT_2)).powi(2) + (y / SQRT_2).powi(2) > 1.0 {
+                    return None;
+                }
+                let theta = (y / SQRT_2).clamp(-1.0, 1.0).asin();
+                let latitude = ((2.0 * theta + (2.0 * theta).sin()) / PI).clamp(-1.0, 1.0).asin();
+                let longitude = if theta.cos() == 0.0 {
+                    0.0
+                } else {
+                    PI * x / (2.0 * SQRT_2 * theta.cos())
+                };
+                Some(LatLon::new(longitude, latitude))
+            }
+        }
+    }
+
+    /// Maps a position on the planet to normalized map coordinates `u, v` in `[0, 1]`.
+    pub fn project(&self, lat_lon: LatLon) -> (f64, f64) {
+        let longitude = (lat_lon.longitude + PI).rem_euclid(TAU) - PI;
+        let latitude = lat_lon.latitude.clamp(-FRAC_PI_2, FRAC_PI_2);
+        match self {
+            Projection::Equirectangular => {
+                (longitude / TAU + 0.5, 0.5 - latitude / PI)
+            }
+            Projection::LambertEqualArea => {
+                (longitude / TAU + 0.5, (1.0 - latitude.sin()) / 2.0)
+            }
+            Projection::Mollweide => {
+                let theta = mollweide_theta(latitude);
+                let x = 2.0 * SQRT_2 / PI * longitude * theta.cos();
+                let y = SQRT_2 * theta.sin();
+                (x / (4.0 * SQRT_2) + 0.5, 0.5 - y / (2.0 * SQRT_2))
+            }
+        }
+    }
+}
+
+/// Solves `2θ + sin 2θ = π sin φ` for the auxiliary angle of the Mollweide projection.
+fn mollweide_theta(latitude: f64) -> f64 {
+    if (FRAC_PI_2 - latitude.abs()).abs() < 1e-9 {
+        return latitude;
+    }
+    let target = PI * latitude.sin();
+    let mut theta = latitude;
+    for _ in 0..32 {
+        let delta = (2.0 * theta + (2.0 * theta).sin() - target) / (2.0 + 2.0 * (2.0 * theta).cos());
+        theta -= delta;
+        if delta.abs() < 1e-12 {
+            break;
+        }
+    }
+    theta
+}
+
+/// A flat map of the whole planet, `width` by `height` pixels, laid out with `projection`.
+///
+/// Pixel coordinates are the world coordinates used by `MacroChunk` and `MesoMap`, with `(0, 0)`
+/// in the north-west corner. Each pixel is sampled at its centre.
+#[derive(Clone, Copy, Debug)]
+pub struct PlanetMap {
+    pub width: usize,
+    pub height: usize,
+    pub projection: Projection,
+}
+
+impl PlanetMap {
+    pub fn new(width: usize, height: usize, projection: Projection) -> Self {
+        Self { width, height, projection }
+    }
+
+    /// The position on the planet under the pixel at `x, y`, or `None` if the pixel isn't on
+    /// the planet. `x` wraps around, so pixels left of 0 or right of the width continue across
+    /// the antimeridian.
+    pub fn lat_lon(&self, x: f64, y: f64) -> Option<LatLon> {
+        let u = ((x + 0.5) / self.width as f64).rem_euclid(1.0);
+        let v = (y + 0.5) / self.height as f64;
+        if !(0.0..=1.0).contains(&v) {
+            return None;
+        }
+        self.projection.unproject(u, v)
+    }
+
+    /// The pixel containing `lat_lon`.
+    pub fn pixel(&self, lat_lon: LatLon) -> (f64, f64) {
+        let (u, v) = self.projection.project(lat_lon);
+        (u * self.width as f64 - 0.5, v * self.height as f64 - 0.5)
+    }
+
+    /// The radius of the planet in pixels, at which distances along the equator keep their length on the map.
+    pub fn radius(&self) -> f64 {
+        self.width as f64 / TAU
+    }
+
+    /// A generator over the pixels of the map which samples `generator` on the surface of the planet. The
+    /// sphere is scaled to the radius of the planet, so features keep their size in pixels along the equator
+    /// and the poles aren't stretched. Pixels which aren't on the planet sample 0.
+    pub fn surface<G: Generator<3>>(self, generator: G) -> impl Generator<2> {
+        let sphere = generator.scale([self.radius(); 3]).sphere();
+        Source::custom(move |[x, y]| {
+            self.lat_lon(x, y)
+                .map_or(0.0, |lat_lon| sphere.sample([lat_lon.longitude, lat_lon.latitude]))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECTIONS: [Projection; 3] = [
+        Projection::Equirectangular,
+        Projection::LambertEqualArea,
+        Projection::Mollweide,
+    ];
+
+    #[test]
+    fn projections_round_trip() {
+        for projection in PROJECTIONS {
+            for i in 1..20 {
+                for j in 1..20 {
+                    let lat_lon = LatLon::new(i as f64 * 0.3 - 3.0, j as f64 * 0.15 - 1.5);
+                    let (u, v) = projection.project(lat_lon);
+                    let back = projection.unproject(u, v).unwrap();
+                    assert!((back.longitude - lat_lon.longitude).abs() < 1e-9, "{projection:?}");
+                    assert!((back.latitude - lat_lon.latitude).abs() < 1e-9, "{projection:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn map_is_continuous_across_the_antimeridian() {
+        for projection in [Projection::Equirectangular, Projection::LambertEqualArea] {
+            let map = PlanetMap::new(1024, 512, projection);
+            let surface = map.surface(Source::simplex(42).scale([1.0 / 40.0; 3]));
+            for y in 0..512 {
+                let west = surface.sample([0.0, y as f64]);
+                let east = surface.sample([1023.0, y as f64]);
+                let wrapped = surface.sample([1024.0, y as f64]);
+                assert!((west - wrapped).abs() < 1e-9);
+                assert!((west - east).abs() < 0.2);
+            }
+        }
+    }
+
+    #[test]
+    fn equal_area_rows_cover_equal_area() {
+        // the band between two latitudes covers sin(a) - sin(b) of the sphere's surface
+        let map = PlanetMap::new(64, 64, Projection::LambertEqualArea);
+        let band = |y: f64| {
+            let north = map.projection.unproject(0.5, y / 64.0).unwrap().latitude;
+            let south = map.projection.unproject(0.5, (y + 1.0) / 64.0).unwrap().latitude;
+            north.sin() - south.sin()
+        };
+        assert!((band(0.0) - band(32.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn mollweide_corners_are_off_the_planet() {
+        let map = PlanetMap::new(200, 100, Projection::Mollweide);
+        assert!(map.lat_lon(0.0, 0.0).is_none());
+        assert!(map.lat_lon(100.0, 50.0).is_some());
+    }
+}
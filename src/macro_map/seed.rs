@@ -0,0 +1,134 @@
+use std::fmt;
+use std::str::FromStr;
+use rand_chacha::ChaCha12Rng;
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use crate::macro_map::jungle_noise::utils::ptable::Seed;
+
+/// The seed of a world, from which independent but reproducible seeds for each layer, chunk or
+/// other part of the world are derived.
+///
+/// Sub-seeds are derived by name, and names can be chained into paths separated by `/`, so
+/// `seed.derive("chunk/3/7")` is the same as `seed.derive("chunk").derive("3").derive("7")`.
+/// Each name selects its own ChaCha stream keyed by the parent seed, so renaming or reseeding one
+/// part of the world never changes the seeds of the others.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WorldSeed([u8; 32]);
+
+impl WorldSeed {
+    pub fn new(seed: u64) -> Self {
+        Self::from_rng(ChaCha12Rng::seed_from_u64(seed))
+    }
+
+    /// Seeds entered by players: whole numbers are used as is, so `"42"` matches `new(42)`, and
+    /// any other text is derived from its contents.
+    pub fn from_phrase(phrase: &str) -> Self {
+        match phrase.trim().parse::<u64>() {
+            Ok(seed) => Self::new(seed),
+            Err(_) => Self::default().derive_name(phrase.trim()),
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn to_bytes(self) -> [u8; 32] {
+        self.0
+    }
+
+    /// The sub-seed at `path`, a name or several names separated by `/`. Empty names are skipped.
+    pub fn derive(&self, path: &str) -> Self {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .fold(*self, |seed, name| seed.derive_name(name))
+    }
+
+    /// The sub-seed of the chunk at `x`, `y`, i.e. `derive("chunk/x/y")`.
+    pub fn chunk(&self, x: i32, y: i32) -> Self {
+        self.derive_name("chunk").derive_name(&x.to_string()).derive_name(&y.to_string())
+    }
+
+    /// The seed folded into a `u64`, for APIs which cannot take a full seed.
+    pub fn to_u64(self) -> u64 {
+        ChaCha12Rng::from_seed(self.0).next_u64()
+    }
+
+    fn derive_name(&self, name: &str) -> Self {
+        let mut rng = ChaCha12Rng::from_seed(self.0);
+        rng.set_stream(fnv1a(name.as_bytes()));
+        Self::from_rng(rng)
+    }
+
+    fn from_rng(mut rng: ChaCha12Rng) -> Self {
+        let mut bytes = [0; 32];
+        rng.fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+}
+
+impl Seed for WorldSeed {
+    fn construct_rng(self) -> ChaCha12Rng {
+        ChaCha12Rng::from_seed(self.0)
+    }
+}
+
+impl From<u64> for WorldSeed {
+    fn from(seed: u64) -> Self {
+        Self::new(seed)
+    }
+}
+
+impl FromStr for WorldSeed {
+    type Err = std::convert::Infallible;
+
+    fn from_str(phrase: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_phrase(phrase))
+    }
+}
+
+impl fmt::Display for WorldSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+/// 64-bit FNV-1a, used to turn names into stream numbers. Unlike `std`'s hashers its output is
+/// guaranteed not to change between Rust releases, which keeps derived seeds stable.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_match_chained_derivation() {
+        let seed = WorldSeed::new(42);
+        assert_eq!(seed.derive("chunk/3/7"), seed.derive("chunk").derive("3").derive("7"));
+        assert_eq!(seed.derive("chunk/3/7"), seed.chunk(3, 7));
+        assert_eq!(seed.derive("/temperature/"), seed.derive("temperature"));
+    }
+
+    #[test]
+    fn sub_seeds_are_independent() {
+        let seed = WorldSeed::new(42);
+        let names = ["temperature", "altitude", "continentalness", "chunk/3/7", "chunk/7/3"];
+        for (i, a) in names.iter().enumerate() {
+            for b in &names[i + 1..] {
+                assert_ne!(seed.derive(a), seed.derive(b));
+            }
+            assert_ne!(seed.derive(a), WorldSeed::new(43).derive(a));
+        }
+    }
+
+    #[test]
+    fn phrases_are_human_readable() {
+        assert_eq!(WorldSeed::from_phrase(" 42 "), WorldSeed::new(42));
+        assert_eq!("mossy caves".parse(), Ok(WorldSeed::from_phrase("mossy caves")));
+        assert_ne!(WorldSeed::from_phrase("mossy caves"), WorldSeed::from_phrase("mossy cave"));
+        assert_eq!(WorldSeed::new(42).to_string().len(), 64);
+    }
+}
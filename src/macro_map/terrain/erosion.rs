@@ -0,0 +1,449 @@
+use crate::macro_map::jungle_noise::generator::Generator;
+use crate::macro_map::jungle_noise::source::Source;
+use crate::macro_map::jungle_noise::utils::noisebuf::NoiseBuffer;
+use crate::macro_map::terrain::tiling::Tile;
+use std::collections::HashMap;
+
+/// Grid based hydraulic erosion of a heightmap.
+///
+/// Every iteration, rain falls on each cell and flows to lower neighbours, proportionally to how
+/// far their water surface lies below that of the cell. Flowing water can hold sediment up to a
+/// capacity given by the amount of water moving and the steepness of the terrain. Water holding
+/// less sediment than it could erodes the terrain beneath it, water holding more deposits the
+/// excess, and part of the water evaporates. Sediment still suspended after the last iteration is
+/// deposited where it is, so the total amount of material is preserved.
+///
+/// Each iteration only looks at the direct neighbours of a cell and at their previous state, so
+/// after `n` iterations a cell depends on nothing further away than [`margin()`] cells. Regions
+/// eroded through [`erode_region()`] are therefore identical to the same cells of one large
+/// eroded map, which keeps adjacent chunks seamless. No randomness is involved, so the result is
+/// fully determined by the heightmap, and thereby by the seed it was generated from.
+///
+/// [`margin()`]: HydraulicErosion::margin
+/// [`erode_region()`]: HydraulicErosion::erode_region
+#[derive(Clone, Copy, Debug)]
+pub struct HydraulicErosion {
+    /// Number of simulation steps.
+    pub iterations: usize,
+    /// Water added to every cell per iteration.
+    pub rain: f64,
+    /// Sediment which a unit of water can carry down a unit drop in height.
+    pub capacity: f64,
+    /// Fraction of the unused capacity which is eroded from the terrain per iteration.
+    pub erosion: f64,
+    /// Fraction of the sediment exceeding the capacity which is deposited per iteration.
+    pub deposition: f64,
+    /// Fraction of the water which evaporates per iteration.
+    pub evaporation: f64,
+}
+
+impl Default for HydraulicErosion {
+    fn default() -> Self {
+        Self {
+            iterations: 24,
+            rain: 0.01,
+            capacity: 8.0,
+            erosion: 0.3,
+            deposition: 0.3,
+            evaporation: 0.05,
+        }
+    }
+}
+
+/// Offsets of the four neighbours of a cell, so that neighbour `k` of a cell sees the cell as its
+/// neighbour `k ^ 1`.
+const NEIGHBOURS: [[isize; 2]; 4] = [[-1, 0], [1, 0], [0, -1], [0, 1]];
+
+impl HydraulicErosion {
+    /// The number of cells around a region which influence the erosion of that region.
+    pub fn margin(&self) -> usize {
+        2 * self.iterations
+    }
+
+    /// Erodes `heightmap` in place.
+    ///
+    /// No water flows across the border of the heightmap, so cells within [`margin()`] of the
+    /// border differ from how they would be eroded as part of a larger map.
+    ///
+    /// [`margin()`]: HydraulicErosion::margin
+    pub fn erode(&self, heightmap: &mut NoiseBuffer<2>) {
+        let cells = heightmap.buffer.len();
+        let neighbours = neighbours(heightmap.shape);
+
+        let mut terrain = heightmap.buffer.clone();
+        let mut water = vec![0.0; cells];
+        let mut sediment = vec![0.0; cells];
+        let mut outflow = vec![[0.0; 4]; cells];
+        for _ in 0..self.iterations {
+            water.iter_mut().for_each(|w| *w += self.rain);
+
+            // decide where the water goes, and erode or deposit according to the capacity
+            let previous = terrain.clone();
+            for cell in 0..cells {
+                let surface = previous[cell] + water[cell];
+                let drops = neighbours[cell].map(|neighbour| {
+                    neighbour.map_or(0.0, |n| (surface - previous[n] - water[n]).max(0.0))
+                });
+                let total_drop: f64 = drops.iter().sum();
+                // moving more than half of the drop would make the water slosh back and forth
+                let moved = water[cell].min(total_drop * 0.5);
+                outflow[cell] = drops.map(|drop| {
+                    if total_drop > 0.0 {
+                        moved * drop / total_drop
+                    } else {
+                        0.0
+                    }
+                });
+
+                let steepest = neighbours[cell]
+                    .iter()
+                    .flatten()
+                    .map(|&n| previous[cell] - previous[n])
+                    .fold(0.0, f64::max);
+                let capacity = self.capacity * moved * steepest;
+                if sediment[cell] > capacity {
+                    let deposited = self.deposition * (sediment[cell] - capacity);
+                    terrain[cell] += deposited;
+                    sediment[cell] -= deposited;
+                } else {
+                    // never dig deeper than the lowest neighbour, which would leave a pit
+                    let eroded = (self.erosion * (capacity - sediment[cell])).min(steepest);
+                    terrain[cell] -= eroded;
+                    sediment[cell] += eroded;
+                }
+            }
+
+            // move water and the sediment it carries
+            let carried = |cell: usize, k: usize| {
+                if water[cell] > 0.0 {
+                    sediment[cell] * outflow[cell][k] / water[cell]
+                } else {
+                    0.0
+                }
+            };
+            let (next_water, next_sediment): (Vec<f64>, Vec<f64>) = (0..cells)
+                .map(|cell| {
+                    let mut w = water[cell];
+                    let mut s = sediment[cell];
+                    for (k, neighbour) in neighbours[cell].iter().enumerate() {
+                        w -= outflow[cell][k];
+                        s -= carried(cell, k);
+                        if let Some(n) = *neighbour {
+                            w += outflow[n][k ^ 1];
+                            s += carried(n, k ^ 1);
+                        }
+                    }
+                    (w * (1.0 - self.evaporation), s)
+                })
+                .unzip();
+            water = next_water;
+            sediment = next_sediment;
+        }
+
+        for (height, suspended) in terrain.iter_mut().zip(sediment) {
+            *height += suspended;
+        }
+        heightmap.buffer = terrain;
+    }
+
+    /// Samples `generator` on the grid of `shape` cells starting at `origin`, with a spacing of
+    /// one, and erodes it.
+    ///
+    /// The grid is extended by [`margin()`] on every side before eroding and cropped again
+    /// afterwards, so the result is the same as if the region were cut out of a larger eroded map.
+    ///
+    /// [`margin()`]: HydraulicErosion::margin
+    pub fn erode_region<G: Generator<2>>(
+        &self,
+        generator: &G,
+        origin: [f64; 2],
+        shape: [usize; 2],
+    ) -> NoiseBuffer<2> {
+        with_margin(generator, origin, shape, self.margin(), |heightmap, _| {
+            self.erode(heightmap)
+        })
+    }
+}
+
+/// Thermal weathering of a heightmap.
+///
+/// Wherever the height difference between a cell and one of its neighbours exceeds the talus of
+/// the cell, part of the material above the talus slides down to that neighbour. This rounds off
+/// sharp ridges and peaks, while leaving slopes gentler than the talus untouched. The talus is
+/// given per cell, so that e.g. soft jungle soil slumps much earlier than the rock of a plateau.
+///
+/// Like [`HydraulicErosion`], every iteration only looks at direct neighbours, so regions eroded
+/// through [`erode_region()`] are seamless, and the result is fully determined by its inputs.
+///
+/// [`erode_region()`]: ThermalErosion::erode_region
+#[derive(Clone, Debug)]
+pub struct ThermalErosion {
+    /// Number of simulation steps.
+    pub iterations: usize,
+    /// Fraction of the material above the talus which slides down per iteration.
+    pub rate: f64,
+    /// Largest height difference between neighbouring cells which stays stable, for tiles
+    /// without an entry in `biome_talus`.
+    pub talus: f64,
+    /// Talus overrides for individual tiles.
+    pub biome_talus: HashMap<Tile, f64>,
+}
+
+impl Default for ThermalErosion {
+    fn default() -> Self {
+        Self {
+            iterations: 16,
+            rate: 0.5,
+            talus: 0.02,
+            biome_talus: HashMap::from([
+                (Tile::Forest, 0.012),
+                (Tile::Plains, 0.015),
+                (Tile::Desert, 0.015),
+                (Tile::Mountain, 0.03),
+                (Tile::Plateau, 0.04),
+            ]),
+        }
+    }
+}
+
+impl ThermalErosion {
+    /// The number of cells around a region which influence the erosion of that region.
+    pub fn margin(&self) -> usize {
+        2 * self.iterations
+    }
+
+    /// The talus of cells of the given tile.
+    pub fn talus_of(&self, tile: Tile) -> f64 {
+        self.biome_talus.get(&tile).copied().unwrap_or(self.talus)
+    }
+
+    /// Erodes `heightmap` in place, using the talus of each cell from `talus`, which has to have
+    /// the same shape.
+    ///
+    /// No material moves across the border of the heightmap, so cells within [`margin()`] of the
+    /// border differ from how they would be eroded as part of a larger map.
+    ///
+    /// [`margin()`]: ThermalErosion::margin
+    pub fn erode(&self, heightmap: &mut NoiseBuffer<2>, talus: &NoiseBuffer<2>) {
+        assert_eq!(heightmap.shape, talus.shape);
+        let neighbours = neighbours(heightmap.shape);
+
+        let mut terrain = heightmap.buffer.clone();
+        let mut outflow = vec![[0.0; 4]; terrain.len()];
+        for _ in 0..self.iterations {
+            for (cell, outflow) in outflow.iter_mut().enumerate() {
+                let stable = terrain[cell] - talus.buffer[cell];
+                let excess = neighbours[cell]
+                    .map(|neighbour| neighbour.map_or(0.0, |n| (stable - terrain[n]).max(0.0)));
+                let total_excess: f64 = excess.iter().sum();
+                let steepest = excess.iter().copied().fold(0.0, f64::max);
+                // moving half of the steepest excess levels out a single steep neighbour
+                let moved = self.rate * steepest * 0.5;
+                *outflow = excess.map(|excess| {
+                    if total_excess > 0.0 {
+                        moved * excess / total_excess
+                    } else {
+                        0.0
+                    }
+                });
+            }
+
+            for (cell, height) in terrain.iter_mut().enumerate() {
+                for (k, neighbour) in neighbours[cell].iter().enumerate() {
+                    *height -= outflow[cell][k];
+                    if let Some(n) = *neighbour {
+                        *height += outflow[n][k ^ 1];
+                    }
+                }
+            }
+        }
+        heightmap.buffer = terrain;
+    }
+
+    /// Samples `generator` and `talus` on the grid of `shape` cells starting at `origin`, with a
+    /// spacing of one, and erodes the former using the latter.
+    ///
+    /// The grid is extended by [`margin()`] on every side before eroding and cropped again
+    /// afterwards, so the result is the same as if the region were cut out of a larger eroded map.
+    ///
+    /// [`margin()`]: ThermalErosion::margin
+    pub fn erode_region<G: Generator<2>, T: Generator<2>>(
+        &self,
+        generator: &G,
+        talus: &T,
+        origin: [f64; 2],
+        shape: [usize; 2],
+    ) -> NoiseBuffer<2> {
+        with_margin(
+            generator,
+            origin,
+            shape,
+            self.margin(),
+            |heightmap, origin| {
+                let talus =
+                    NoiseBuffer::new_with_transform(heightmap.shape, origin, [1.0; 2], talus);
+                self.erode(heightmap, &talus)
+            },
+        )
+    }
+}
+
+/// The indices of the four neighbours of every cell of a grid of the given shape, in the order
+/// of [`NEIGHBOURS`].
+fn neighbours([width, height]: [usize; 2]) -> Vec<[Option<usize>; 4]> {
+    (0..width * height)
+        .map(|index| {
+            let cell = [index / height, index % height];
+            NEIGHBOURS.map(|offset| {
+                let x = cell[0]
+                    .checked_add_signed(offset[0])
+                    .filter(|&x| x < width)?;
+                let y = cell[1]
+                    .checked_add_signed(offset[1])
+                    .filter(|&y| y < height)?;
+                Some(x * height + y)
+            })
+        })
+        .collect()
+}
+
+/// Samples `generator` on the grid of `shape` cells starting at `origin` extended by `margin` on
+/// every side, applies `erode` to it and crops the margin off again.
+///
+/// `erode` is passed the origin of the extended grid. This allows chaining several erosion passes
+/// over a region, by passing the sum of their margins.
+pub fn with_margin<G: Generator<2>>(
+    generator: &G,
+    origin: [f64; 2],
+    shape: [usize; 2],
+    margin: usize,
+    erode: impl FnOnce(&mut NoiseBuffer<2>, [f64; 2]),
+) -> NoiseBuffer<2> {
+    let origin = origin.map(|o| o - margin as f64);
+    let mut extended = NoiseBuffer::new_with_transform(
+        shape.map(|len| len + 2 * margin),
+        origin,
+        [1.0; 2],
+        generator,
+    );
+    erode(&mut extended, origin);
+
+    let cropped = Source::custom(|[x, y]| extended[[x as usize + margin, y as usize + margin]]);
+    NoiseBuffer::<2>::new(shape, &cropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HydraulicErosion, ThermalErosion};
+    use crate::macro_map::jungle_noise::generator::Generator;
+    use crate::macro_map::jungle_noise::source::Source;
+    use crate::macro_map::jungle_noise::utils::noisebuf::NoiseBuffer;
+
+    fn erosion() -> HydraulicErosion {
+        HydraulicErosion {
+            iterations: 8,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn erosion_preserves_material() {
+        let generator = Source::<2>::simplex(11).fbm(4, 0.05, 2.0, 0.5);
+        let mut heightmap = NoiseBuffer::<2>::new([40, 30], &generator);
+        let before: f64 = heightmap.buffer.iter().sum();
+        erosion().erode(&mut heightmap);
+        let after: f64 = heightmap.buffer.iter().sum();
+
+        assert!((before - after).abs() < 1e-9);
+        let original = NoiseBuffer::<2>::new([40, 30], &generator);
+        assert_ne!(heightmap.buffer, original.buffer);
+    }
+
+    #[test]
+    fn regions_match_a_single_eroded_map() {
+        let generator = Source::<2>::simplex(11).fbm(4, 0.05, 2.0, 0.5);
+        let erosion = erosion();
+        let margin = erosion.margin();
+        let mut whole = NoiseBuffer::<2>::new_with_transform(
+            [16 + 2 * margin, 8 + 2 * margin],
+            [-(margin as f64); 2],
+            [1.0; 2],
+            &generator,
+        );
+        erosion.erode(&mut whole);
+
+        for (x_offset, y_offset) in [(0, 0), (8, 0)] {
+            let region =
+                erosion.erode_region(&generator, [x_offset as f64, y_offset as f64], [8, 8]);
+            for x in 0..8 {
+                for y in 0..8 {
+                    let expected = whole[[x + x_offset + margin, y + y_offset + margin]];
+                    assert_eq!(region[[x, y]], expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn thermal_erosion_flattens_steep_slopes() {
+        let generator = Source::<2>::simplex(11).fbm(4, 0.2, 2.0, 0.5);
+        let thermal = ThermalErosion::default();
+        let talus = NoiseBuffer::<2>::new([40, 30], &Source::constant(thermal.talus));
+        let steepest = |heightmap: &NoiseBuffer<2>| {
+            let mut steepest: f64 = 0.0;
+            for x in 0..39 {
+                for y in 0..29 {
+                    let height = heightmap[[x, y]];
+                    steepest = steepest
+                        .max((height - heightmap[[x + 1, y]]).abs())
+                        .max((height - heightmap[[x, y + 1]]).abs());
+                }
+            }
+            steepest
+        };
+
+        let mut heightmap = NoiseBuffer::<2>::new([40, 30], &generator);
+        let before: f64 = heightmap.buffer.iter().sum();
+        let steepest_before = steepest(&heightmap);
+        thermal.erode(&mut heightmap, &talus);
+        let after: f64 = heightmap.buffer.iter().sum();
+
+        assert!((before - after).abs() < 1e-9);
+        assert!(steepest(&heightmap) < steepest_before);
+    }
+
+    #[test]
+    fn thermal_regions_match_a_single_eroded_map() {
+        let generator = Source::<2>::simplex(11).fbm(4, 0.2, 2.0, 0.5);
+        let talus = Source::<2>::value(3).scale([0.1; 2]).abs().mul(0.05);
+        let thermal = ThermalErosion {
+            iterations: 8,
+            ..Default::default()
+        };
+        let margin = thermal.margin();
+        let mut whole = NoiseBuffer::<2>::new_with_transform(
+            [8 + 2 * margin, 16 + 2 * margin],
+            [-(margin as f64); 2],
+            [1.0; 2],
+            &generator,
+        );
+        let whole_talus = NoiseBuffer::<2>::new_with_transform(
+            whole.shape,
+            [-(margin as f64); 2],
+            [1.0; 2],
+            &talus,
+        );
+        thermal.erode(&mut whole, &whole_talus);
+
+        for (x_offset, y_offset) in [(0, 0), (0, 8)] {
+            let origin = [x_offset as f64, y_offset as f64];
+            let region = thermal.erode_region(&generator, &talus, origin, [8, 8]);
+            for x in 0..8 {
+                for y in 0..8 {
+                    let expected = whole[[x + x_offset + margin, y + y_offset + margin]];
+                    assert_eq!(region[[x, y]], expected);
+                }
+            }
+        }
+    }
+}
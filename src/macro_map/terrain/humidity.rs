@@ -0,0 +1,161 @@
+/// Humidity and precipitation from moisture carried by the wind.
+///
+/// Air picks up moisture over the ocean and carries it along the wind. Some of it rains out
+/// everywhere, and much more where the air is forced to rise over higher ground, so the far side
+/// of a mountain range lies in a dry rain shadow.
+///
+/// The moisture at a cell is found by following the wind [`steps`] times upwind, and then carrying
+/// the air back along that path. Each cell only depends on the terrain and winds along its own
+/// path, so the result does not depend on how the world is split into chunks, as long as the wind
+/// and altitude are known within [`margin()`] of each cell.
+///
+/// [`steps`]: MoistureModel::steps
+/// [`margin()`]: MoistureModel::margin
+#[derive(Clone, Copy, Debug)]
+pub struct MoistureModel {
+    /// Number of steps by which the path of the air is followed upwind.
+    pub steps: usize,
+    /// Length of each step, in cells.
+    pub step_length: f64,
+    /// Fraction of the missing moisture which the air picks up per step over the ocean.
+    pub evaporation: f64,
+    /// Fraction of the moisture which rains out per step, regardless of the terrain.
+    pub precipitation: f64,
+    /// Fraction of the moisture which rains out per unit of altitude the air rises.
+    pub orographic_precipitation: f64,
+    /// Altitude below which the air passes over the ocean.
+    pub sea_level: f64,
+}
+
+impl Default for MoistureModel {
+    fn default() -> Self {
+        Self {
+            steps: 16,
+            step_length: 4.0,
+            evaporation: 0.3,
+            precipitation: 0.02,
+            orographic_precipitation: 5.0,
+            sea_level: 0.0,
+        }
+    }
+}
+
+/// The moisture of the air at a cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Moisture {
+    /// Relative humidity, in the range [0, 1].
+    pub humidity: f64,
+    /// The fraction of the moisture carried by the air which rains out at the cell.
+    pub precipitation: f64,
+}
+
+impl MoistureModel {
+    /// The number of cells around a cell over which its moisture is traced upwind, which is how far
+    /// the wind and the altitude have to be known around it.
+    pub fn margin(&self) -> usize {
+        (self.steps as f64 * self.step_length).ceil() as usize
+    }
+
+    /// The moisture at `x`, `y`, given the wind and altitude around it.
+    pub fn sample(
+        &self,
+        x: f64,
+        y: f64,
+        wind: impl Fn(f64, f64) -> [f64; 2],
+        altitude: impl Fn(f64, f64) -> f64,
+    ) -> Moisture {
+        // follow the wind upwind, stopping where it is calm
+        let mut path = vec![[x, y]];
+        for _ in 0..self.steps {
+            let [x, y] = *path.last().unwrap();
+            let [wind_x, wind_y] = wind(x, y);
+            let speed = wind_x.hypot(wind_y);
+            if speed == 0.0 {
+                break;
+            }
+            let step = self.step_length / speed;
+            path.push([x - wind_x * step, y - wind_y * step]);
+        }
+
+        // carry the air back downwind
+        let mut humidity = 0.0;
+        let mut precipitation = 0.0;
+        let mut previous_altitude: Option<f64> = None;
+        for &[x, y] in path.iter().rev() {
+            let height = altitude(x, y);
+            if height < self.sea_level {
+                humidity += self.evaporation * (1.0 - humidity);
+            }
+            // the air moves over the surface of the ocean, not its floor
+            let height = height.max(self.sea_level);
+            let rise = previous_altitude.map_or(0.0, |previous| (height - previous).max(0.0));
+            let rain = (self.precipitation + self.orographic_precipitation * rise).min(1.0);
+            precipitation = humidity * rain;
+            humidity -= precipitation;
+            previous_altitude = Some(height);
+        }
+
+        Moisture {
+            humidity,
+            precipitation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MoistureModel;
+
+    /// Ocean west of x = 0, then a ridge rising up to x = 40 and falling again up to x = 60.
+    fn coast(x: f64, _: f64) -> f64 {
+        if x < 0.0 {
+            -0.5
+        } else if x < 40.0 {
+            x * 0.01
+        } else {
+            (0.4 - (x - 40.0) * 0.02).max(0.0)
+        }
+    }
+
+    #[test]
+    fn ranges_cast_a_rain_shadow() {
+        let model = MoistureModel::default();
+        let eastwards = |_: f64, _: f64| [1.0, 0.0];
+        let shore = model.sample(2.0, 0.0, eastwards, coast);
+        let slope = model.sample(36.0, 0.0, eastwards, coast);
+        let shadow = model.sample(52.0, 0.0, eastwards, coast);
+
+        assert!(shore.humidity > slope.humidity);
+        assert!(slope.humidity > shadow.humidity);
+        assert!(slope.precipitation > shadow.precipitation);
+        assert!((0.0..=1.0).contains(&shore.humidity));
+    }
+
+    #[test]
+    fn calm_air_over_land_stays_dry() {
+        let model = MoistureModel::default();
+        let moisture = model.sample(20.0, 0.0, |_, _| [0.0, 0.0], coast);
+        assert_eq!(moisture.humidity, 0.0);
+
+        let moisture = model.sample(-20.0, 0.0, |_, _| [0.0, 0.0], coast);
+        assert!(moisture.humidity > 0.0);
+    }
+
+    #[test]
+    fn paths_stay_within_the_margin() {
+        let model = MoistureModel::default();
+        let farthest = std::cell::Cell::new(0.0f64);
+        let reach = |x: f64, y: f64| farthest.set(farthest.get().max((x - 10.0).hypot(y - 20.0)));
+        let swirl = |x: f64, y: f64| {
+            reach(x, y);
+            [3.0 + (y * 0.3).sin(), (x * 0.2).cos()]
+        };
+        model.sample(10.0, 20.0, swirl, |x, y| {
+            reach(x, y);
+            coast(x, y)
+        });
+
+        assert!(farthest.get() > 0.0);
+        assert!(farthest.get() <= model.margin() as f64 + 1e-9);
+    }
+}
@@ -0,0 +1,297 @@
+use crate::macro_map::jungle_noise::generator::Generator;
+use crate::macro_map::jungle_noise::source::Source;
+use crate::macro_map::jungle_noise::utils::noisebuf::NoiseBuffer;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, VecDeque};
+
+/// Depression filling of a heightmap, which turns closed basins into lakes.
+///
+/// A priority flood starts at the border of the heightmap and at every cell below sea level, and
+/// works its way inwards, always continuing from the lowest cell reached so far. Cells lower than the cell they are reached
+/// from lie in a closed basin, and are raised to the level at which the basin spills over. Each
+/// raised cell lies [`epsilon`] above the cell it was reached from, so the filled surface still
+/// slopes towards the spill point, and water flowing over it always finds a way out.
+///
+/// Cells which are filled deeper than [`min_depth`] are lakes. Flooding starts at the border of
+/// the region extended by [`margin()`], so basins smaller than the margin are filled identically
+/// in adjacent regions, while larger basins may spill over the border of the extended region.
+///
+/// [`epsilon`]: DepressionFilling::epsilon
+/// [`min_depth`]: DepressionFilling::min_depth
+/// [`margin()`]: DepressionFilling::margin
+#[derive(Clone, Copy, Debug)]
+pub struct DepressionFilling {
+    /// Number of cells around a region which are flooded along with it.
+    pub reach: usize,
+    /// Height by which a filled cell lies above the cell it drains into.
+    pub epsilon: f64,
+    /// Depth from which a filled cell counts as a lake.
+    pub min_depth: f64,
+}
+
+impl Default for DepressionFilling {
+    fn default() -> Self {
+        Self {
+            reach: 32,
+            epsilon: 1e-6,
+            min_depth: 0.005,
+        }
+    }
+}
+
+/// A lake, consisting of the connected cells of a region which share its label.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lake {
+    /// Height of the water surface.
+    pub surface: f64,
+    /// Number of cells covered by the lake.
+    pub area: usize,
+}
+
+/// The result of filling the depressions of a region.
+#[derive(Clone, Debug)]
+pub struct LakeRegion {
+    /// The heightmap, with every depression filled up to its spill point.
+    pub altitude: NoiseBuffer<2>,
+    /// The depth of the lake covering each cell, or zero outside of lakes.
+    pub depth: NoiseBuffer<2>,
+    /// The index into `lakes` of the lake covering each cell, in the same layout as `altitude`.
+    pub labels: Vec<Option<usize>>,
+    /// The lakes within the region.
+    pub lakes: Vec<Lake>,
+}
+
+const NEIGHBOURS: [[isize; 2]; 8] = [
+    [-1, -1],
+    [-1, 0],
+    [-1, 1],
+    [0, -1],
+    [0, 1],
+    [1, -1],
+    [1, 0],
+    [1, 1],
+];
+
+/// A cell in the priority queue, ordered by height and then by index.
+struct Flooded(f64, usize);
+
+impl PartialEq for Flooded {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Flooded {}
+
+impl PartialOrd for Flooded {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Flooded {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+impl DepressionFilling {
+    /// The number of cells around a region which influence the lakes of that region.
+    pub fn margin(&self) -> usize {
+        self.reach
+    }
+
+    /// Fills the depressions of `heightmap` in place.
+    ///
+    /// Every cell on the border of the heightmap and every cell below `sea_level` is treated as an
+    /// outlet, so that basins draining into the sea are left to it rather than filled.
+    pub fn fill(&self, heightmap: &mut NoiseBuffer<2>, sea_level: f64) {
+        let [width, height] = heightmap.shape;
+        let mut closed = vec![false; heightmap.buffer.len()];
+        let mut open = BinaryHeap::new();
+        for (cell, closed) in closed.iter_mut().enumerate() {
+            let [x, y] = [cell / height, cell % height];
+            let border = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+            if border || heightmap.buffer[cell] < sea_level {
+                *closed = true;
+                open.push(Reverse(Flooded(heightmap.buffer[cell], cell)));
+            }
+        }
+
+        while let Some(Reverse(Flooded(level, cell))) = open.pop() {
+            for neighbour in neighbours([width, height], cell) {
+                if closed[neighbour] {
+                    continue;
+                }
+                closed[neighbour] = true;
+                let filled = heightmap.buffer[neighbour].max(level + self.epsilon);
+                heightmap.buffer[neighbour] = filled;
+                open.push(Reverse(Flooded(filled, neighbour)));
+            }
+        }
+    }
+
+    /// Samples `generator` on the grid of `shape` cells starting at `origin`, with a spacing of
+    /// one, fills its depressions above `sea_level` and labels the resulting lakes.
+    ///
+    /// The grid is extended by [`margin()`] on every side before filling.
+    ///
+    /// [`margin()`]: DepressionFilling::margin
+    pub fn generate_region<G: Generator<2>>(
+        &self,
+        generator: &G,
+        origin: [f64; 2],
+        shape: [usize; 2],
+        sea_level: f64,
+    ) -> LakeRegion {
+        let margin = self.margin();
+        let mut extended = NoiseBuffer::new_with_transform(
+            shape.map(|len| len + 2 * margin),
+            origin.map(|o| o - margin as f64),
+            [1.0; 2],
+            generator,
+        );
+        self.fill(&mut extended, sea_level);
+
+        let crop = |[x, y]: [f64; 2]| extended[[x as usize + margin, y as usize + margin]];
+        let altitude = NoiseBuffer::<2>::new(shape, &Source::custom(crop));
+        let original = NoiseBuffer::<2>::new(
+            shape,
+            &Source::custom(|[x, y]| generator.sample([origin[0] + x, origin[1] + y])),
+        );
+        let depth = NoiseBuffer::<2>::new(
+            shape,
+            &Source::custom(|[x, y]| {
+                let point = [x as usize, y as usize];
+                let depth = altitude[point] - original[point];
+                if depth > self.min_depth {
+                    depth
+                } else {
+                    0.0
+                }
+            }),
+        );
+
+        // label connected lake cells
+        let mut labels = vec![None; depth.buffer.len()];
+        let mut lakes = Vec::new();
+        for start in 0..labels.len() {
+            if depth.buffer[start] == 0.0 || labels[start].is_some() {
+                continue;
+            }
+            let label = lakes.len();
+            let mut lake = Lake {
+                surface: f64::MIN,
+                area: 0,
+            };
+            labels[start] = Some(label);
+            let mut queue = VecDeque::from([start]);
+            while let Some(cell) = queue.pop_front() {
+                lake.surface = lake.surface.max(altitude.buffer[cell]);
+                lake.area += 1;
+                for neighbour in neighbours(shape, cell) {
+                    if depth.buffer[neighbour] > 0.0 && labels[neighbour].is_none() {
+                        labels[neighbour] = Some(label);
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+            lakes.push(lake);
+        }
+
+        LakeRegion {
+            altitude,
+            depth,
+            labels,
+            lakes,
+        }
+    }
+}
+
+/// The indices of the neighbours of `cell` within a grid of the given shape.
+fn neighbours([width, height]: [usize; 2], cell: usize) -> impl Iterator<Item = usize> {
+    let [x, y] = [cell / height, cell % height];
+    NEIGHBOURS.iter().filter_map(move |offset| {
+        let x = x.checked_add_signed(offset[0]).filter(|&x| x < width)?;
+        let y = y.checked_add_signed(offset[1]).filter(|&y| y < height)?;
+        Some(x * height + y)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DepressionFilling;
+    use crate::macro_map::jungle_noise::generator::Generator;
+    use crate::macro_map::jungle_noise::source::Source;
+
+    /// Two bowls of different depth on a plane sloping down towards `x = 0`.
+    fn bowls([x, y]: [f64; 2]) -> f64 {
+        let bowl = |centre: f64, depth: f64| {
+            depth * (-((x - centre).powi(2) + (y - 8.0).powi(2)) / 8.0).exp()
+        };
+        0.01 * x - bowl(8.0, 0.4) - bowl(20.0, 0.2)
+    }
+
+    #[test]
+    fn basins_are_filled_to_their_spill_point() {
+        let filling = DepressionFilling::default();
+        let region = filling.generate_region(&Source::custom(bowls), [0.0; 2], [29, 17], f64::MIN);
+
+        assert_eq!(region.lakes.len(), 2);
+        assert_eq!(region.labels[8 * 17 + 8], Some(0));
+        assert_eq!(region.labels[20 * 17 + 8], Some(1));
+        assert_eq!(region.labels[0], None);
+        // each bowl spills over its lower side, so neither is filled to the level of the plane
+        assert!(region.lakes[0].surface < 0.08);
+        assert!(region.lakes[1].surface < 0.2);
+        assert!(region.depth[[8, 8]] > region.depth[[20, 8]]);
+
+        // the filled surface drains everywhere
+        for x in 1..28 {
+            for y in 1..16 {
+                let level = region.altitude[[x, y]];
+                let lowest = (x - 1..=x + 1)
+                    .flat_map(|x| (y - 1..=y + 1).map(move |y| [x, y]))
+                    .map(|point| region.altitude[point])
+                    .fold(f64::MAX, f64::min);
+                assert!(lowest < level);
+            }
+        }
+    }
+
+    #[test]
+    fn basins_connected_to_the_sea_stay_unfilled() {
+        // the deeper bowl reaches below sea level, so it is part of the sea rather than a lake
+        let filling = DepressionFilling::default();
+        let region = filling.generate_region(&Source::custom(bowls), [0.0; 2], [29, 17], -0.1);
+
+        assert_eq!(region.lakes.len(), 1);
+        assert_eq!(region.labels[20 * 17 + 8], Some(0));
+        for x in 0..14 {
+            for y in 0..17 {
+                assert_eq!(region.depth[[x, y]], 0.0);
+                assert_eq!(region.altitude[[x, y]], bowls([x as f64, y as f64]));
+            }
+        }
+    }
+
+    #[test]
+    fn small_basins_match_across_boundaries() {
+        // tilting the terrain keeps its basins small, so they fit into the margin
+        let noise = Source::<2>::simplex(3).fbm(4, 0.1, 2.0, 0.5);
+        let generator = Source::custom(|[x, y]| noise.sample([x, y]) + 0.05 * x);
+        let filling = DepressionFilling::default();
+        let whole = filling.generate_region(&generator, [0.0; 2], [16, 32], f64::MIN);
+        let regions =
+            [0, 16].map(|y| filling.generate_region(&generator, [0.0, y as f64], [16, 16], f64::MIN));
+
+        for (region, y_offset) in regions.iter().zip([0, 16]) {
+            for x in 0..16 {
+                for y in 0..16 {
+                    assert_eq!(region.depth[[x, y]], whole.depth[[x, y + y_offset]]);
+                }
+            }
+        }
+        assert!(!whole.lakes.is_empty());
+    }
+}
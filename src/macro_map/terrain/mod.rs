@@ -1,3 +1,9 @@
 pub mod terrain_chunks;
 pub mod noise_layers;
+pub mod erosion;
+pub mod lakes;
+pub mod plates;
+pub mod rivers;
+pub mod wind;
+pub mod humidity;
 mod tiling;
\ No newline at end of file
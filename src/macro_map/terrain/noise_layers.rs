@@ -1,20 +1,51 @@
-use image::{DynamicImage, GenericImage, Pixel};
-use noise::{NoiseFn, OpenSimplex};
-use crate::macro_map::terrain::tiling::TilingStrategy;
+use image::{DynamicImage, GenericImage, Pixel, Rgb};
+use crate::macro_map::jungle_noise::dyn_generator::DynGenerator;
+use crate::macro_map::jungle_noise::generator::{Generator, Generator3D};
+use crate::macro_map::jungle_noise::source::Source;
+use crate::macro_map::planet::{LatLon, PlanetMap};
+use crate::macro_map::seed::WorldSeed;
+use crate::macro_map::jungle_noise::utils::noisebuf::NoiseBuffer;
+use crate::macro_map::terrain::erosion::{self, HydraulicErosion, ThermalErosion};
+use crate::macro_map::terrain::humidity::{Moisture, MoistureModel};
+use crate::macro_map::terrain::lakes::{DepressionFilling, LakeRegion};
+use crate::macro_map::terrain::plates::TectonicPlates;
+use crate::macro_map::terrain::rivers::{River, RiverNetwork, RiverRegion};
+use crate::macro_map::terrain::tiling::{Tile, TilingStrategy};
+use crate::macro_map::terrain::wind::WindField;
 
 #[derive(Default, Clone)]
 pub struct NoiseValues {
     pub(crate) continentalness: f64,
+    pub(crate) erosion: f64,
+    pub(crate) peaks_and_valleys: f64,
     pub(crate) temperature: f64,
-    pub(crate) altitude: f64
+    pub(crate) altitude: f64,
+    /// Flow of water relative to the discharge of the river network; only known once rivers are generated
+    pub(crate) river: f64,
+    /// Depth of the lake covering the cell; only known once depressions are filled
+    pub(crate) lake: f64,
+    /// Identifier of the tectonic plate the cell lies on
+    pub(crate) plate: usize,
+    /// Direction and speed of the prevailing wind; only known once the wind is sampled
+    pub(crate) wind: [f64; 2],
+    /// Relative humidity of the air, from 0 to 1; only known once the wind is sampled
+    pub(crate) humidity: f64,
+    /// Fraction of the moisture in the air which rains out; only known once the wind is sampled
+    pub(crate) precipitation: f64
 }
 
 #[derive(Default)]
 pub struct NoiseLayers {
     pub(crate) aggregate: DynamicImage,
     pub(crate) continentalness: DynamicImage,
+    pub(crate) erosion: DynamicImage,
+    pub(crate) peaks_and_valleys: DynamicImage,
     pub(crate) temperature: DynamicImage,
-    pub(crate) altitude: DynamicImage
+    pub(crate) altitude: DynamicImage,
+    pub(crate) plates: DynamicImage,
+    pub(crate) wind: DynamicImage,
+    pub(crate) humidity: DynamicImage,
+    pub(crate) precipitation: DynamicImage
 }
 
 impl NoiseLayers {
@@ -22,188 +53,819 @@ impl NoiseLayers {
         Self {
             aggregate: DynamicImage::new_rgb8(size as u32, size as u32),
             continentalness: DynamicImage::new_rgb8(size as u32, size as u32),
+            erosion: DynamicImage::new_rgb8(size as u32, size as u32),
+            peaks_and_valleys: DynamicImage::new_rgb8(size as u32, size as u32),
             temperature: DynamicImage::new_rgb8(size as u32, size as u32),
             altitude: DynamicImage::new_rgb8(size as u32, size as u32),
+            plates: DynamicImage::new_rgb8(size as u32, size as u32),
+            wind: DynamicImage::new_rgb8(size as u32, size as u32),
+            humidity: DynamicImage::new_rgb8(size as u32, size as u32),
+            precipitation: DynamicImage::new_rgb8(size as u32, size as u32),
         }
     }
 }
 
 impl NoiseLayers {
-    pub(crate) fn add_at_index(&mut self, x: usize, y: usize, noise_values: &NoiseValues, tiling_strategy: &TilingStrategy) {
+    /// Tiles use the physical `noise_values`, while the grayscale layers use their `normalized` counterparts
+    pub(crate) fn add_at_index(&mut self, x: usize, y: usize, noise_values: &NoiseValues, normalized: &NoiseValues, tiling_strategy: &TilingStrategy) {
         self.aggregate.put_pixel(x as u32, y as u32, tiling_strategy.get_tile(noise_values).rbg_colour().to_rgba());
-        self.continentalness.put_pixel(x as u32, y as u32, tiling_strategy.get_grayscale_tile(noise_values.continentalness).to_rgba());
-        self.temperature.put_pixel(x as u32, y as u32, tiling_strategy.get_grayscale_tile(noise_values.temperature).to_rgba());
-        self.altitude.put_pixel(x as u32, y as u32, tiling_strategy.get_grayscale_tile(noise_values.altitude).to_rgba());
+        self.continentalness.put_pixel(x as u32, y as u32, tiling_strategy.get_grayscale_tile(normalized.continentalness).to_rgba());
+        self.erosion.put_pixel(x as u32, y as u32, tiling_strategy.get_grayscale_tile(normalized.erosion).to_rgba());
+        self.peaks_and_valleys.put_pixel(x as u32, y as u32, tiling_strategy.get_grayscale_tile(normalized.peaks_and_valleys).to_rgba());
+        self.temperature.put_pixel(x as u32, y as u32, tiling_strategy.get_grayscale_tile(normalized.temperature).to_rgba());
+        self.altitude.put_pixel(x as u32, y as u32, tiling_strategy.get_grayscale_tile(normalized.altitude).to_rgba());
+        self.plates.put_pixel(x as u32, y as u32, tiling_strategy.get_plate_tile(noise_values.plate).to_rgba());
+        let [wind_x, wind_y] = normalized.wind;
+        self.wind.put_pixel(x as u32, y as u32, tiling_strategy.get_grayscale_tile(wind_x.hypot(wind_y)).to_rgba());
+        self.humidity.put_pixel(x as u32, y as u32, tiling_strategy.get_grayscale_tile(normalized.humidity).to_rgba());
+        self.precipitation.put_pixel(x as u32, y as u32, tiling_strategy.get_grayscale_tile(normalized.precipitation).to_rgba());
+    }
+
+    /// Draws an arrow along the wind every `spacing` cells over the wind layer, once all cells are added
+    pub(crate) fn add_wind_arrows(&mut self, size: usize, spacing: usize, noise_values: &[NoiseValues]) {
+        let arrow = Rgb([220, 20, 60]).to_rgba();
+        for y in (spacing / 2..size).step_by(spacing) {
+            for x in (spacing / 2..size).step_by(spacing) {
+                let [wind_x, wind_y] = noise_values[y * size + x].wind;
+                let speed = wind_x.hypot(wind_y);
+                if speed == 0.0 {
+                    continue;
+                }
+                // longer arrows for stronger winds, but always within the cell block
+                let length = (spacing as f64 / 2.0 - 1.0) * speed.min(1.0);
+                for step in 0..=(length * 2.0) as usize {
+                    let distance = step as f64 / 2.0;
+                    let pixel_x = (x as f64 + wind_x / speed * distance).round();
+                    let pixel_y = (y as f64 + wind_y / speed * distance).round();
+                    if (0.0..size as f64).contains(&pixel_x) && (0.0..size as f64).contains(&pixel_y) {
+                        self.wind.put_pixel(pixel_x as u32, pixel_y as u32, arrow);
+                    }
+                }
+            }
+        }
     }
 }
 
 pub struct NoiseStrategies {
     pub continentalness_strategy: ContinentalnessStrategy,
+    pub erosion_strategy: ErosionStrategy,
+    pub peaks_and_valleys_strategy: PeaksAndValleysStrategy,
     pub temperature_strategy: TemperatureStrategy,
-    pub altitude_strategy: AltitudeStrategy
+    pub altitude_strategy: AltitudeStrategy,
+    pub hydraulic_erosion: HydraulicErosion,
+    pub thermal_erosion: ThermalErosion,
+    pub depression_filling: DepressionFilling,
+    pub river_network: RiverNetwork,
+    pub wind_field: WindField,
+    pub moisture_model: MoistureModel
 }
 
 impl NoiseStrategies {
-    pub fn generate(&self, x: f64, y: f64, detail_level: u32) -> NoiseValues {
+    /// The values of the cell at `x`, `y` of the map `landscape` and `climate` cover. Each layer is sampled once,
+    /// the altitude is the final one of the landscape, and the temperature follows it
+    pub fn cell(&self, landscape: &Landscape, climate: &ClimateGrid, x: usize, y: usize) -> NoiseValues {
+        let (world_x, world_y) = (x as f64, y as f64);
+        let altitude = landscape.altitude(x, y);
+        let moisture = self.moisture(climate, world_x, world_y);
+        NoiseValues {
+            continentalness: self.continentalness_strategy.generate(world_x, world_y, 0),
+            erosion: self.erosion_strategy.generate(world_x, world_y, 0),
+            peaks_and_valleys: self.peaks_and_valleys_strategy.generate(world_x, world_y, 0),
+            temperature: self.temperature_strategy.at_altitude(world_x, world_y, altitude, 0),
+            altitude,
+            river: landscape.river(x, y),
+            lake: landscape.lake(x, y),
+            plate: self.altitude_strategy.plates().plate([world_x, world_y]).id,
+            wind: self.wind(climate, world_x, world_y),
+            humidity: moisture.humidity,
+            precipitation: moisture.precipitation,
+        }
+    }
+
+    /// The temperature at sea level and the altitude of `landscape` over the whole map, which `landscape` covers
+    pub fn climate_grid(&self, landscape: &Landscape) -> ClimateGrid {
+        let shape = landscape.shape();
+        let temperature = Source::custom(|[x, y]| self.temperature_strategy.generate(x, y, 0));
+        let altitude = Source::custom(|[x, y]| landscape.altitude(x as usize, y as usize));
+
+        ClimateGrid {
+            temperature: NoiseBuffer::<2>::new_parallel(shape, &temperature),
+            altitude: NoiseBuffer::<2>::new_parallel(shape, &altitude),
+        }
+    }
+
+    /// The prevailing wind, driven by the temperature at sea level and deflected by the terrain of `climate`
+    pub fn wind(&self, climate: &ClimateGrid, x: f64, y: f64) -> [f64; 2] {
+        self.wind_field.sample(x, y, |x, y| climate.temperature(x, y), |x, y| climate.altitude(x, y))
+    }
+
+    /// The humidity and precipitation brought by the prevailing wind from the ocean, which rains out over rising ground
+    pub fn moisture(&self, climate: &ClimateGrid, x: f64, y: f64) -> Moisture {
+        self.moisture_model.sample(x, y, |x, y| self.wind(climate, x, y), |x, y| climate.altitude(x, y))
+    }
+
+    /// The tile a cell at `altitude` falls into before erosion, lakes, rivers and moisture shape it.
+    /// Besides the altitude, the tiles only look at the continentalness and the temperature there, as the humidity
+    /// is only known once the terrain is final
+    fn uneroded_tile(&self, tiling_strategy: &TilingStrategy, x: f64, y: f64, altitude: f64) -> Tile {
+        tiling_strategy.get_terrain_tile(&NoiseValues {
+            continentalness: self.continentalness_strategy.generate(x, y, 0),
+            temperature: self.temperature_strategy.at_altitude(x, y, altitude, 0),
+            altitude,
+            ..Default::default()
+        })
+    }
+
+    /// The altitude of the `shape` cells starting at `origin`, after thermal and then hydraulic erosion.
+    /// The talus of each cell depends on the tile its uneroded altitude falls into
+    pub fn eroded_altitude(&self, tiling_strategy: &TilingStrategy, origin: [f64; 2], shape: [usize; 2]) -> NoiseBuffer<2> {
+        let altitude = Source::custom(|[x, y]| self.altitude_strategy.generate(x, y, 0));
+        let margin = self.thermal_erosion.margin() + self.hydraulic_erosion.margin();
+
+        erosion::with_margin(&altitude, origin, shape, margin, |heightmap, origin| {
+            let talus = Source::custom(|[x, y]| {
+                let altitude = heightmap[[x as usize, y as usize]];
+                self.thermal_erosion.talus_of(self.uneroded_tile(tiling_strategy, origin[0] + x, origin[1] + y, altitude))
+            });
+            let talus = NoiseBuffer::<2>::new(heightmap.shape, &talus);
+            self.thermal_erosion.erode(heightmap, &talus);
+            self.hydraulic_erosion.erode(heightmap);
+        })
+    }
+
+    /// The lakes of the `shape` cells starting at `origin`, along with the eroded altitude filled up to their surface
+    pub fn lakes(&self, tiling_strategy: &TilingStrategy, origin: [f64; 2], shape: [usize; 2]) -> LakeRegion {
+        let margin = self.depression_filling.margin();
+        let extended = self.eroded_altitude(tiling_strategy, origin.map(|o| o - margin as f64), shape.map(|len| len + 2 * margin));
+        let altitude = Source::custom(|[x, y]| extended[[(x - origin[0]) as usize + margin, (y - origin[1]) as usize + margin]]);
+
+        self.depression_filling.generate_region(&altitude, origin, shape, tiling_strategy.sea_level())
+    }
+
+    /// The rivers flowing over the filled altitude of `lakes`, on the cells `lakes` covers starting at `origin`
+    pub fn rivers(&self, tiling_strategy: &TilingStrategy, lakes: &LakeRegion, origin: [f64; 2]) -> RiverRegion {
+        let altitude = Source::custom(|[x, y]| lakes.altitude[[(x - origin[0]) as usize, (y - origin[1]) as usize]]);
+
+        self.river_network.generate_region(&altitude, origin, lakes.altitude.shape, tiling_strategy.river_threshold())
+    }
+
+    /// The eroded altitude, lakes and rivers of the map of `shape` cells, which every chunk slices its cells from
+    pub fn landscape(&self, tiling_strategy: &TilingStrategy, shape: [usize; 2]) -> Landscape {
+        let lakes = self.lakes(tiling_strategy, [0.0; 2], shape);
+        let rivers = self.rivers(tiling_strategy, &lakes, [0.0; 2]);
+
+        Landscape { lakes, rivers }
+    }
+
+    /// Maps each layer from the range of its strategy onto [0, 1], e.g. for rendering
+    pub fn normalize(&self, noise_values: &NoiseValues) -> NoiseValues {
         NoiseValues {
-            continentalness: self.continentalness_strategy.generate(x, y, detail_level),
-            temperature: self.temperature_strategy.generate(x, y, detail_level),
-            altitude: self.altitude_strategy.generate(x, y, detail_level),
+            continentalness: normalize(noise_values.continentalness, self.continentalness_strategy.range()),
+            erosion: normalize(noise_values.erosion, self.erosion_strategy.range()),
+            peaks_and_valleys: normalize(noise_values.peaks_and_valleys, self.peaks_and_valleys_strategy.range()),
+            temperature: normalize(noise_values.temperature, self.temperature_strategy.range()),
+            altitude: normalize(noise_values.altitude, self.altitude_strategy.range()),
+            river: noise_values.river,
+            lake: noise_values.lake,
+            plate: noise_values.plate,
+            wind: noise_values.wind.map(|w| w / self.wind_field.max_speed),
+            humidity: noise_values.humidity,
+            precipitation: noise_values.precipitation,
         }
     }
 }
+
+/// The eroded altitude, lakes and rivers of the whole map. Generating them once up front lets every chunk slice
+/// its cells out of the same result, rather than eroding an overlapping window of its own
+pub struct Landscape {
+    lakes: LakeRegion,
+    rivers: RiverRegion,
+}
+
+impl Landscape {
+    /// The altitude of the cell at `x`, `y` after erosion, with its lake filled and its river carved into it
+    pub fn altitude(&self, x: usize, y: usize) -> f64 {
+        self.rivers.altitude[[x, y]]
+    }
+
+    /// The flow of the river through the cell at `x`, `y`, relative to the discharge of the river network
+    pub fn river(&self, x: usize, y: usize) -> f64 {
+        self.rivers.flow[[x, y]]
+    }
+
+    /// The depth of the lake covering the cell at `x`, `y`
+    pub fn lake(&self, x: usize, y: usize) -> f64 {
+        self.lakes.depth[[x, y]]
+    }
+
+    /// The rivers within the `shape` cells starting at `origin`
+    pub fn rivers(&self, origin: [usize; 2], shape: [usize; 2]) -> Vec<River> {
+        self.rivers.clip(origin.map(|o| o as f64), shape)
+    }
+
+    /// The number of cells of the map along each axis
+    pub fn shape(&self) -> [usize; 2] {
+        self.rivers.altitude.shape
+    }
+}
+
+/// The temperature at sea level and the altitude of the whole map, sampled once on a grid, so that the wind and
+/// moisture of every cell take their surroundings from the grid rather than from the strategies. Like the map,
+/// the grid wraps around east–west
+pub struct ClimateGrid {
+    temperature: NoiseBuffer<2>,
+    altitude: NoiseBuffer<2>,
+}
+
+impl ClimateGrid {
+    /// The temperature at sea level at `x`, `y`
+    pub fn temperature(&self, x: f64, y: f64) -> f64 {
+        self.interpolate(&self.temperature, x, y)
+    }
+
+    /// The eroded altitude at `x`, `y`, with lakes filled and rivers carved
+    pub fn altitude(&self, x: f64, y: f64) -> f64 {
+        self.interpolate(&self.altitude, x, y)
+    }
+
+    /// Interpolates `buffer` bilinearly at the world coordinates `x`, `y`. `x` wraps around the width of the
+    /// map, while `y` is clamped to it. Cells are reproduced exactly
+    fn interpolate(&self, buffer: &NoiseBuffer<2>, x: f64, y: f64) -> f64 {
+        let [width, height] = buffer.shape;
+        let x = x.rem_euclid(width as f64);
+        let y = y.clamp(0.0, (height - 1) as f64);
+        let (x0, y0) = ((x as usize).min(width - 1), (y as usize).min(height - 2));
+        let (tx, ty) = (x - x0 as f64, y - y0 as f64);
+        let x1 = (x0 + 1) % width;
+        let lerp = |a: f64, b: f64, t: f64| a * (1.0 - t) + b * t;
+        lerp(lerp(buffer[[x0, y0]], buffer[[x1, y0]], tx),
+             lerp(buffer[[x0, y0 + 1]], buffer[[x1, y0 + 1]], tx), ty)
+    }
+}
+
+fn normalize(value: f64, [min, max]: [f64; 2]) -> f64 {
+    ((value - min) / (max - min)).clamp(0.0, 1.0)
+}
+
+/// The lowest and highest output among the control points of `spline`
+fn spline_range(spline: &[(f64, f64)]) -> [f64; 2] {
+    spline.iter().fold([f64::MAX, f64::MIN], |[min, max], &(_, value)| [min.min(value), max.max(value)])
+}
+
+/// Base height for each continentalness, from deep ocean to inland plateaus
+const CONTINENTALNESS_SPLINE: [(f64, f64); 6] = [(-1.0, -0.8), (-0.4, -0.5), (-0.1, 0.0), (0.05, 0.12), (0.3, 0.25), (1.0, 0.45)];
+/// How strongly peaks and valleys shape the terrain for each erosion
+const EROSION_SPLINE: [(f64, f64); 4] = [(-1.0, 1.0), (-0.3, 0.6), (0.2, 0.2), (1.0, 0.05)];
+/// Relief for each value of peaks and valleys, from the floors of valleys to the tops of peaks
+const PEAKS_AND_VALLEYS_SPLINE: [(f64, f64); 5] = [(-1.0, -0.3), (-0.4, 0.0), (0.2, 0.35), (0.7, 0.9), (1.0, 1.2)];
+/// Amplitude of the noise adding detail to the composed altitude
+const ALTITUDE_DETAIL: f64 = 0.1;
+
+/// Composes the altitude from continentalness, erosion and peaks and valleys through spline curves:
+/// continentalness sets the base height, from deep ocean to inland plateaus, and the relief shaped by
+/// peaks and valleys on top of it is flattened where erosion is high
 pub struct AltitudeStrategy {
-    scale: f64,
-    mountain_scale: f64,
-    noise: OpenSimplex
+    noise: DetailedNoise,
+    plates: TectonicPlates,
 }
 
 impl AltitudeStrategy {
-    pub fn new(seed: u32) -> Self {
+    pub fn new(seed: WorldSeed, planet: PlanetMap, continentalness: ContinentalnessStrategy, erosion: ErosionStrategy,
+               peaks_and_valleys: PeaksAndValleysStrategy) -> Self {
+        let scale = 200.0;
+        let persistence = 0.5;
+        let lacunarity = 2.0;
+        let noise = Source::simplex(seed);
         Self {
-            scale: 200.0,
-            mountain_scale: 50.0,
-            noise: OpenSimplex::new(seed),
+            noise: DetailedNoise::new(|detail_level| {
+                let continentalness = continentalness.clone();
+                let erosion = erosion.clone();
+                let peaks_and_valleys = peaks_and_valleys.clone();
+                let base = Source::custom(move |[x, y]| continentalness.generate(x, y, detail_level))
+                    .curve(&CONTINENTALNESS_SPLINE);
+                let roughness = Source::custom(move |[x, y]| erosion.generate(x, y, detail_level))
+                    .curve(&EROSION_SPLINE);
+                let relief = Source::custom(move |[x, y]| peaks_and_valleys.generate(x, y, detail_level))
+                    .curve(&PEAKS_AND_VALLEYS_SPLINE);
+                // Weighting each octave by the ones before keeps lowlands smooth and highlands rough
+                let terrain = planet.surface(noise.clone()
+                    .hybridmulti(6 + detail_level, 1.0 / scale, lacunarity, persistence));
+                base.sum(roughness.product(relief))
+                    .sum(terrain.mul(ALTITUDE_DETAIL))
+                    .into_dyn()
+            }),
+            plates: TectonicPlates::new(seed.derive("plates"), planet),
         }
     }
+
+    pub fn plates(&self) -> &TectonicPlates {
+        &self.plates
+    }
 }
 
 impl NoiseStrategy for AltitudeStrategy {
     fn generate(&self, x: f64, y: f64, detail_level: u32) -> f64 {
-        let base_octaves = 6 + detail_level;
-        let mountain_octaves = 4 + detail_level;
-        let persistence = 0.5;
-        let lacunarity = 2.0;
-
-        // Generate base terrain
-        let mut amplitude = 1.0;
-        let mut frequency = 1.0;
-        let mut base_noise = 0.0;
-        let mut weight = 0.0;
+        // Mountain ranges, rifts and island arcs follow the plate boundaries
+        self.noise.sample(x, y, detail_level) + self.plates.relief([x, y])
+    }
 
-        for _ in 0..base_octaves {
-            let sample_x = x * frequency / self.scale;
-            let sample_y = y * frequency / self.scale;
+    /// The range of the composed altitude. Monotone splines never leave the range of their control points, and
+    /// the detail noise stays within its amplitude
+    fn range(&self) -> [f64; 2] {
+        let [base_min, base_max] = spline_range(&CONTINENTALNESS_SPLINE);
+        let [roughness_min, roughness_max] = spline_range(&EROSION_SPLINE);
+        let [relief_min, relief_max] = spline_range(&PEAKS_AND_VALLEYS_SPLINE);
+        let products = [roughness_min * relief_min, roughness_min * relief_max,
+                        roughness_max * relief_min, roughness_max * relief_max];
+        let [plates_min, plates_max] = self.plates.relief_range();
+        [base_min + products.iter().copied().fold(f64::MAX, f64::min) - ALTITUDE_DETAIL + plates_min,
+         base_max + products.iter().copied().fold(f64::MIN, f64::max) + ALTITUDE_DETAIL + plates_max]
+    }
+}
 
-            base_noise += self.noise.get([sample_x, sample_y]) * amplitude;
-            weight += amplitude;
 
-            amplitude *= persistence;
-            frequency *= lacunarity;
-        }
-        base_noise /= weight;
+pub trait NoiseStrategy {
+    fn generate(&self, x: f64, y: f64, detail_level: u32) -> f64;
 
-        // Add mountainous features
-        let mut amplitude = 0.5;
-        let mut frequency = 1.0;
-        let mut mountain_noise = 0.0;
-        let mut weight = 0.0;
+    /// The physical range the values returned by `generate` lie in
+    fn range(&self) -> [f64; 2] {
+        [-1.0, 1.0]
+    }
+}
 
-        for _ in 0..mountain_octaves {
-            let sample_x = x * frequency / self.mountain_scale;
-            let sample_y = y * frequency / self.mountain_scale;
+/// The number of detail levels for which the strategies build their noise up front
+const DETAIL_LEVELS: u32 = 4;
 
-            mountain_noise += self.noise.get([sample_x, sample_y]) * amplitude;
-            weight += amplitude;
+/// The noise pipeline of a strategy, built once for each detail level rather than on every sample
+#[derive(Clone)]
+struct DetailedNoise {
+    levels: Vec<DynGenerator<2>>,
+}
 
-            amplitude *= persistence;
-            frequency *= lacunarity;
+impl DetailedNoise {
+    fn new(build: impl Fn(u32) -> DynGenerator<2>) -> Self {
+        Self {
+            levels: (0..DETAIL_LEVELS).map(build).collect(),
         }
-        mountain_noise /= weight;
-
-        // Combine base terrain with mountains using exponential function
-        let combined = base_noise + (mountain_noise * mountain_noise * 2.0);
-        combined.max(-1.0).min(1.0)
     }
-}
-
 
-pub trait NoiseStrategy {
-    fn generate(&self, x: f64, y: f64, detail_level: u32) -> f64;
+    /// Samples the pipeline of `detail_level`, or of the finest one built if it is finer
+    fn sample(&self, x: f64, y: f64, detail_level: u32) -> f64 {
+        self.levels[detail_level.min(DETAIL_LEVELS - 1) as usize].sample([x, y])
+    }
 }
 
+#[derive(Clone)]
 pub struct ContinentalnessStrategy {
-    scale: f64,
-    noise: OpenSimplex,
+    noise: DetailedNoise,
 }
 
 impl ContinentalnessStrategy {
-    pub fn new(seed: u32) -> Self {
+    pub fn new(seed: WorldSeed, planet: PlanetMap) -> Self {
+        let scale = 100.0;
+        let warp_strength = 40.0;
+        let persistence = 0.5;
+        let lacunarity = 2.0;
+        let noise = Source::simplex(seed);
+        // one warp generator per axis of the sphere the map is wrapped around
+        let warp: [DynGenerator<3>; 3] = std::array::from_fn(|axis| {
+            Source::simplex(seed.derive(&format!("warp/{axis}")))
+                .fbm(3, 1.0 / scale, 2.0, 0.5)
+                .into_dyn()
+        });
         Self {
-            scale: 100.0,
-            noise: OpenSimplex::new(seed),
+            // Warping the warp a second time folds coastlines into bays and peninsulas
+            noise: DetailedNoise::new(|detail_level| {
+                planet.surface(noise.clone()
+                    .fbm(4 + detail_level, 1.0 / scale, lacunarity, persistence)
+                    .domain_warp(warp.clone(), warp_strength, 2))
+                    .into_dyn()
+            }),
         }
     }
 }
 
 impl NoiseStrategy for ContinentalnessStrategy {
     fn generate(&self, x: f64, y: f64, detail_level: u32) -> f64 {
-        let octaves = 4 + detail_level;
+        self.noise.sample(x, y, detail_level)
+    }
+}
+
+/// How flat the terrain is: low erosion gives rugged mountains, high erosion wide plains
+#[derive(Clone)]
+pub struct ErosionStrategy {
+    noise: DetailedNoise,
+}
+
+impl ErosionStrategy {
+    pub fn new(seed: WorldSeed, planet: PlanetMap) -> Self {
+        let scale = 300.0;
         let persistence = 0.5;
         let lacunarity = 2.0;
+        let noise = Source::simplex(seed);
+        Self {
+            noise: DetailedNoise::new(|detail_level| {
+                planet.surface(noise.clone()
+                    .fbm(3 + detail_level, 1.0 / scale, lacunarity, persistence))
+                    .into_dyn()
+            }),
+        }
+    }
+}
 
-        let mut amplitude = 1.0;
-        let mut frequency = 1.0;
-        let mut noise_value = 0.0;
-        let mut weight = 0.0;
-
-        for _ in 0..octaves {
-            let sample_x = x * frequency / self.scale;
-            let sample_y = y * frequency / self.scale;
+impl NoiseStrategy for ErosionStrategy {
+    fn generate(&self, x: f64, y: f64, detail_level: u32) -> f64 {
+        self.noise.sample(x, y, detail_level)
+    }
+}
 
-            noise_value += self.noise.get([sample_x, sample_y]) * amplitude;
-            weight += amplitude;
+/// Where the terrain peaks and where it drops into valleys, from -1 at the valley floors to 1 at the peaks
+#[derive(Clone)]
+pub struct PeaksAndValleysStrategy {
+    noise: DetailedNoise,
+}
 
-            amplitude *= persistence;
-            frequency *= lacunarity;
+impl PeaksAndValleysStrategy {
+    pub fn new(seed: WorldSeed, planet: PlanetMap) -> Self {
+        let scale = 120.0;
+        let persistence = 0.5;
+        let lacunarity = 2.0;
+        let noise = Source::simplex(seed);
+        Self {
+            noise: DetailedNoise::new(|detail_level| {
+                planet.surface(noise.clone()
+                    .fbm(4 + detail_level, 1.0 / scale, lacunarity, persistence))
+                    .into_dyn()
+            }),
         }
+    }
+}
 
-        noise_value / weight
+impl NoiseStrategy for PeaksAndValleysStrategy {
+    fn generate(&self, x: f64, y: f64, detail_level: u32) -> f64 {
+        let weirdness = self.noise.sample(x, y, detail_level);
+        // Folding the noise twice turns its zero crossings into valleys and the extremes in between into peaks
+        1.0 - (3.0 * weirdness.abs() - 2.0).abs()
     }
 }
 
 pub struct TemperatureStrategy {
-    scale: f64,
-    latitude_influence: f64,
-    noise: OpenSimplex,
+    stellar_influence: f64,
+    noise: DetailedNoise,
+    planet: PlanetMap,
+    /// The point on the planet facing its star, which is fixed as the planet is tidally locked
+    pub substellar_point: LatLon,
+    /// Temperature along the terminator, the ring between the dayside and the nightside
+    pub terminator_temperature: f64,
+    /// How far the temperature at sea level rises above the terminator temperature below the star,
+    /// and drops below it on the far side of the planet
+    pub amplitude: f64,
+    /// Cooling per unit of altitude above `sea_level`
+    pub lapse_rate: f64,
+    pub sea_level: f64,
+    /// The highest altitude the ground reaches, which bounds how far the lapse rate cools it
+    pub max_altitude: f64,
+    /// How strongly the ocean pulls temperatures towards the terminator temperature, from 0 to 1
+    pub ocean_moderation: f64,
 }
 
 impl TemperatureStrategy {
-    pub fn new(seed: u32) -> Self {
+    pub fn new(seed: WorldSeed, planet: PlanetMap) -> Self {
+        let scale = 150.0;
+        let persistence = 0.6;
+        let lacunarity = 2.5;
+        let noise = Source::simplex(seed);
         Self {
-            scale: 150.0,
-            latitude_influence: 0.7,
-            noise: OpenSimplex::new(seed),
+            stellar_influence: 0.7,
+            noise: DetailedNoise::new(|detail_level| {
+                planet.surface(noise.clone()
+                    .fbm(3 + detail_level, 1.0 / scale, lacunarity, persistence))
+                    .into_dyn()
+            }),
+            planet,
+            substellar_point: LatLon::new(0.0, 0.0),
+            terminator_temperature: 10.0,
+            amplitude: 100.0,
+            lapse_rate: 60.0,
+            sea_level: 0.0,
+            max_altitude: 1.5,
+            ocean_moderation: 0.4,
+        }
+    }
+
+    /// The temperature at `x`, `y` on ground at `altitude`: cooler the higher the ground lies above sea
+    /// level, and closer to the terminator temperature over the ocean
+    pub fn at_altitude(&self, x: f64, y: f64, altitude: f64, detail_level: u32) -> f64 {
+        let temperature = self.generate(x, y, detail_level);
+        if altitude < self.sea_level {
+            let terminator = self.terminator_temperature;
+            terminator + (temperature - terminator) * (1.0 - self.ocean_moderation)
+        } else {
+            temperature - self.lapse_rate * (altitude - self.sea_level)
         }
     }
 }
 
 impl NoiseStrategy for TemperatureStrategy {
+    /// The temperature at sea level
     fn generate(&self, x: f64, y: f64, detail_level: u32) -> f64 {
-        let octaves = 3 + detail_level;
-        let persistence = 0.6;
-        let lacunarity = 2.5;
+        let normalized_noise = self.noise.sample(x, y, detail_level);
+        // 1 below the star, -1 on the far side of the planet and 0 along the terminator;
+        // pixels off the planet are treated as lying on the terminator
+        let insolation = self.planet.lat_lon(x, y)
+            .map_or(0.0, |lat_lon| lat_lon.distance(&self.substellar_point).cos());
+
+        (normalized_noise * (1.0 - self.stellar_influence) +
+            insolation * self.stellar_influence) * self.amplitude + self.terminator_temperature
+    }
 
-        let mut amplitude = 1.0;
-        let mut frequency = 1.0;
-        let mut noise_value = 0.0;
-        let mut weight = 0.0;
+    /// The range of `at_altitude`: the ocean only pulls temperatures towards the terminator temperature,
+    /// while the lapse rate cools the highest ground below anything found at sea level
+    fn range(&self) -> [f64; 2] {
+        let lapse = self.lapse_rate * (self.max_altitude - self.sea_level).max(0.0);
+        [self.terminator_temperature - self.amplitude - lapse, self.terminator_temperature + self.amplitude]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
+    use crate::macro_map::planet::Projection;
+    use crate::macro_map::terrain::tiling::TilingConfig;
+
+    #[test]
+    fn layers_wrap_east_west() {
+        let seed = WorldSeed::new(42);
+        let (width, height) = (1024, 512);
+        let period = width as f64;
+        let planet = PlanetMap::new(width, height, Projection::Equirectangular);
+        let continentalness = ContinentalnessStrategy::new(seed.derive("continentalness"), planet);
+        let erosion = ErosionStrategy::new(seed.derive("erosion"), planet);
+        let peaks_and_valleys = PeaksAndValleysStrategy::new(seed.derive("peaks_and_valleys"), planet);
+        let strategies: [Box<dyn NoiseStrategy>; 5] = [
+            Box::new(AltitudeStrategy::new(seed.derive("altitude"), planet, continentalness.clone(),
+                                           erosion.clone(), peaks_and_valleys.clone())),
+            Box::new(continentalness),
+            Box::new(erosion),
+            Box::new(peaks_and_valleys),
+            Box::new(TemperatureStrategy::new(seed.derive("temperature"), planet)),
+        ];
+        for strategy in &strategies {
+            for y in (0..height).step_by(8) {
+                let west = strategy.generate(0.0, y as f64, 0);
+                let wrapped = strategy.generate(period, y as f64, 0);
+                assert!((west - wrapped).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn layers_meet_at_the_poles() {
+        let seed = WorldSeed::new(42);
+        let (width, height) = (1024, 512);
+        let planet = PlanetMap::new(width, height, Projection::Equirectangular);
+        let strategies: [Box<dyn NoiseStrategy>; 4] = [
+            Box::new(ContinentalnessStrategy::new(seed.derive("continentalness"), planet)),
+            Box::new(ErosionStrategy::new(seed.derive("erosion"), planet)),
+            Box::new(PeaksAndValleysStrategy::new(seed.derive("peaks_and_valleys"), planet)),
+            Box::new(TemperatureStrategy::new(seed.derive("temperature"), planet)),
+        ];
+        let spread = |strategy: &dyn NoiseStrategy, y: usize| {
+            let row: Vec<f64> = (0..width).map(|x| strategy.generate(x as f64, y as f64, 0)).collect();
+            row.iter().fold(f64::MIN, |a, &b| a.max(b)) - row.iter().fold(f64::MAX, |a, &b| a.min(b))
+        };
+        for strategy in &strategies {
+            // the top and bottom rows run around the poles within a pixel of them, while the middle one runs
+            // all the way around the equator
+            let equator = spread(strategy.as_ref(), height / 2);
+            assert!(spread(strategy.as_ref(), 0) < 0.1 * equator);
+            assert!(spread(strategy.as_ref(), height - 1) < 0.1 * equator);
+        }
+    }
+
+    /// The strategies of a world with short erosion and lake reaches, which keep small maps quick to generate
+    fn quick_strategies(width: usize, height: usize) -> NoiseStrategies {
+        let seed = WorldSeed::new(42);
+        let planet = PlanetMap::new(width, height, Projection::Equirectangular);
+        let continentalness_strategy = ContinentalnessStrategy::new(seed.derive("continentalness"), planet);
+        let erosion_strategy = ErosionStrategy::new(seed.derive("erosion"), planet);
+        let peaks_and_valleys_strategy = PeaksAndValleysStrategy::new(seed.derive("peaks_and_valleys"), planet);
+        let altitude_strategy = AltitudeStrategy::new(seed.derive("altitude"), planet, continentalness_strategy.clone(),
+                                                      erosion_strategy.clone(), peaks_and_valleys_strategy.clone());
+        NoiseStrategies {
+            continentalness_strategy,
+            erosion_strategy,
+            peaks_and_valleys_strategy,
+            temperature_strategy: TemperatureStrategy::new(seed.derive("temperature"), planet),
+            altitude_strategy,
+            hydraulic_erosion: HydraulicErosion { iterations: 4, ..Default::default() },
+            thermal_erosion: ThermalErosion { iterations: 4, ..Default::default() },
+            depression_filling: DepressionFilling { reach: 4, ..Default::default() },
+            river_network: RiverNetwork::default(),
+            wind_field: WindField::new(seed.derive("wind"), planet),
+            moisture_model: MoistureModel::default(),
+        }
+    }
 
-        for _ in 0..octaves {
-            let sample_x = x * frequency / self.scale;
-            let sample_y = y * frequency / self.scale;
+    #[test]
+    fn chunks_of_the_shared_erosion_are_seamless() {
+        let strategies = quick_strategies(64, 32);
+        let tiling_strategy = TilingStrategy::new(TilingConfig { sea_level: 0.1, river_threshold: 0.1 });
+        // the landscape erodes the map extended by the margin of the lakes in one go
+        let margin = strategies.depression_filling.margin();
+        let shared = strategies.eroded_altitude(&tiling_strategy, [-(margin as f64); 2], [64 + 2 * margin, 32 + 2 * margin]);
+        let landscape = strategies.landscape(&tiling_strategy, [64, 32]);
+
+        for origin in [[0, 0], [16, 0], [48, 16]] {
+            let chunk = strategies.eroded_altitude(&tiling_strategy, origin.map(|o| o as f64), [16, 16]);
+            for x in 0..16 {
+                for y in 0..16 {
+                    assert_eq!(chunk[[x, y]], shared[[origin[0] + x + margin, origin[1] + y + margin]]);
+                }
+            }
+        }
+        // rivers are only ever carved into the eroded altitude, and lakes only fill it up
+        for x in 0..64 {
+            for y in 0..32 {
+                let eroded = shared[[x + margin, y + margin]];
+                let river_depth = strategies.river_network.depth * landscape.river(x, y).sqrt();
+                assert!(landscape.altitude(x, y) >= eroded - river_depth - 1e-9);
+            }
+        }
+    }
 
-            noise_value += self.noise.get([sample_x, sample_y]) * amplitude;
-            weight += amplitude;
+    #[test]
+    fn wind_takes_its_gradients_from_the_climate_grid() {
+        let strategies = quick_strategies(64, 32);
+        let tiling_strategy = TilingStrategy::new(TilingConfig { sea_level: 0.1, river_threshold: 0.1 });
+        let landscape = strategies.landscape(&tiling_strategy, [64, 32]);
+        let climate = strategies.climate_grid(&landscape);
+        let temperature = |x, y| strategies.temperature_strategy.generate(x, y, 0);
+        for x in 16..24 {
+            for y in 8..16 {
+                assert_eq!(climate.temperature(x as f64, y as f64), temperature(x as f64, y as f64));
+                assert_eq!(climate.altitude(x as f64, y as f64), landscape.altitude(x, y));
+            }
+        }
+        let (x, y) = (20.0, 12.0);
+        let altitude = |x, y| climate.altitude(x, y);
+        assert_eq!(strategies.wind(&climate, x, y), strategies.wind_field.sample(x, y, temperature, altitude));
+
+        // between cells, the grid interpolates
+        let corners = [landscape.altitude(18, 9), landscape.altitude(19, 9), landscape.altitude(18, 10), landscape.altitude(19, 10)];
+        let between = climate.altitude(18.5, 9.25);
+        assert!(corners.iter().any(|&corner| corner <= between) && corners.iter().any(|&corner| corner >= between));
+
+        // and wraps around the antimeridian
+        for y in 0..32 {
+            let y = y as f64;
+            assert_eq!(climate.altitude(-1.0, y), climate.altitude(63.0, y));
+            assert_eq!(climate.temperature(64.0, y), climate.temperature(0.0, y));
+            let across = climate.altitude(63.5, y);
+            let (west, east) = (climate.altitude(63.0, y), climate.altitude(0.0, y));
+            assert!((across - (west + east) / 2.0).abs() < 1e-12);
+        }
+    }
 
-            amplitude *= persistence;
-            frequency *= lacunarity;
+    #[test]
+    fn every_cell_of_a_chunk_gets_a_tile() {
+        let strategies = quick_strategies(64, 32);
+        let tiling_strategy = TilingStrategy::new(TilingConfig { sea_level: 0.1, river_threshold: 0.1 });
+        let landscape = strategies.landscape(&tiling_strategy, [64, 32]);
+        let climate = strategies.climate_grid(&landscape);
+        let mut tiles = Vec::new();
+        for x in 0..64 {
+            for y in 0..32 {
+                let cell = strategies.cell(&landscape, &climate, x, y);
+                assert_eq!(cell.altitude, landscape.altitude(x, y));
+                tiles.push(tiling_strategy.get_tile(&cell));
+            }
         }
+        assert!(!tiles.contains(&Tile::Black));
+        // the ocean is sea however far it lies from the coast
+        assert!(tiles.contains(&Tile::Sea));
+    }
 
-        let normalized_noise = noise_value / weight;
-        let latitude_factor = y * 2.0 - 1.0;
+    #[test]
+    fn erosion_stays_within_its_range() {
+        let planet = PlanetMap::new(1024, 512, Projection::Equirectangular);
+        let strategy = ErosionStrategy::new(WorldSeed::new(42).derive("erosion"), planet);
+        let [min, max] = strategy.range();
+        let values: Vec<f64> = (0..1024).step_by(8)
+            .flat_map(|x| (0..512).step_by(8).map(move |y| (x as f64, y as f64)))
+            .map(|(x, y)| strategy.generate(x, y, 0))
+            .collect();
+        assert!(values.iter().all(|value| (min..=max).contains(value)));
+        // both rugged and flat terrain occur
+        assert!(values.iter().any(|&value| value < -0.2) && values.iter().any(|&value| value > 0.2));
+    }
+
+    #[test]
+    fn peaks_and_valleys_fold_the_noise() {
+        let planet = PlanetMap::new(1024, 512, Projection::Equirectangular);
+        let strategy = PeaksAndValleysStrategy::new(WorldSeed::new(42).derive("peaks_and_valleys"), planet);
+        for x in (0..1024).step_by(8) {
+            for y in (0..512).step_by(8) {
+                let (x, y) = (x as f64, y as f64);
+                let weirdness = strategy.noise.sample(x, y, 0);
+                let folded = strategy.generate(x, y, 0);
+                assert_eq!(folded, 1.0 - (3.0 * weirdness.abs() - 2.0).abs());
+                assert!((-1.0..=1.0).contains(&folded));
+            }
+        }
+    }
+
+    #[test]
+    fn altitude_splines_cover_the_range_of_their_layers() {
+        let splines: [&[(f64, f64)]; 3] = [&CONTINENTALNESS_SPLINE, &EROSION_SPLINE, &PEAKS_AND_VALLEYS_SPLINE];
+        for spline in splines {
+            assert_eq!(spline.first().unwrap().0, -1.0);
+            assert_eq!(spline.last().unwrap().0, 1.0);
+            assert!(spline.windows(2).all(|pair| pair[0].0 < pair[1].0));
+            let curve = Source::custom(|[value, _]| value).curve(spline);
+            assert!((curve.sample([-1.0, 0.0]) - spline.first().unwrap().1).abs() < 1e-9);
+            assert!((curve.sample([1.0, 0.0]) - spline.last().unwrap().1).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn altitude_stays_within_its_range() {
+        let planet = PlanetMap::new(1024, 512, Projection::Equirectangular);
+        for seed in [1, 7, 42, 1234].map(WorldSeed::new) {
+            let strategy = AltitudeStrategy::new(seed.derive("altitude"), planet,
+                                                 ContinentalnessStrategy::new(seed.derive("continentalness"), planet),
+                                                 ErosionStrategy::new(seed.derive("erosion"), planet),
+                                                 PeaksAndValleysStrategy::new(seed.derive("peaks_and_valleys"), planet));
+            let [min, max] = strategy.range();
+            for x in (0..1024).step_by(8) {
+                for y in (0..512).step_by(8) {
+                    assert!((min..=max).contains(&strategy.generate(x as f64, y as f64, 0)));
+                }
+            }
+        }
+    }
+
+    /// A temperature strategy driven by the insolation alone, so that its values are known exactly
+    fn insolation_only(planet: PlanetMap) -> TemperatureStrategy {
+        let mut strategy = TemperatureStrategy::new(WorldSeed::new(42), planet);
+        strategy.stellar_influence = 1.0;
+        strategy
+    }
+
+    #[test]
+    fn temperature_peaks_below_the_star_and_meets_the_terminator_temperature_on_the_terminator() {
+        let planet = PlanetMap::new(1024, 512, Projection::Equirectangular);
+        let strategy = insolation_only(planet);
+        let temperature_at = |longitude: f64| {
+            let (x, y) = planet.pixel(LatLon::new(longitude, 0.0));
+            strategy.generate(x, y, 0)
+        };
+        let peak = strategy.terminator_temperature + strategy.amplitude;
+
+        assert!((temperature_at(0.0) - peak).abs() < 1e-6);
+        assert!((temperature_at(FRAC_PI_2) - strategy.terminator_temperature).abs() < 1e-6);
+        assert!((temperature_at(-FRAC_PI_2) - strategy.terminator_temperature).abs() < 1e-6);
+        for x in (0..planet.width).step_by(16) {
+            for y in (0..planet.height).step_by(16) {
+                assert!(strategy.generate(x as f64, y as f64, 0) <= peak + 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn land_cools_with_the_lapse_rate_and_the_ocean_moderates() {
+        let planet = PlanetMap::new(1024, 512, Projection::Equirectangular);
+        let mut strategy = TemperatureStrategy::new(WorldSeed::new(42), planet);
+        strategy.sea_level = 0.1;
+        let (x, y) = (300.0, 200.0);
+        let at_sea_level = strategy.generate(x, y, 0);
+
+        let on_land = strategy.at_altitude(x, y, 0.6, 0);
+        assert!((on_land - (at_sea_level - strategy.lapse_rate * 0.5)).abs() < 1e-9);
+
+        let terminator = strategy.terminator_temperature;
+        let over_ocean = strategy.at_altitude(x, y, -0.5, 0);
+        let moderated = terminator + (at_sea_level - terminator) * (1.0 - strategy.ocean_moderation);
+        assert!((over_ocean - moderated).abs() < 1e-9);
+        assert!((over_ocean - terminator).abs() <= (at_sea_level - terminator).abs());
+    }
 
-        (normalized_noise * (1.0 - self.latitude_influence) +
-            latitude_factor * self.latitude_influence) * 100.0
+    #[test]
+    fn temperature_range_follows_the_parameters() {
+        let planet = PlanetMap::new(1024, 512, Projection::Equirectangular);
+        let mut strategy = insolation_only(planet);
+        strategy.sea_level = 0.1;
+        strategy.terminator_temperature = -20.0;
+        let [min, max] = strategy.range();
+        assert!((max - (strategy.terminator_temperature + strategy.amplitude)).abs() < 1e-9);
+        let lapse = strategy.lapse_rate * (strategy.max_altitude - strategy.sea_level);
+        assert!((min - (strategy.terminator_temperature - strategy.amplitude - lapse)).abs() < 1e-9);
+
+        // the extremes are reached below the star at sea level and on the highest ground on the far side
+        let (x, y) = planet.pixel(LatLon::new(0.0, 0.0));
+        assert!((strategy.at_altitude(x, y, strategy.sea_level, 0) - max).abs() < 1e-6);
+        let (x, y) = planet.pixel(LatLon::new(-PI, 0.0));
+        assert!((strategy.at_altitude(x, y, strategy.max_altitude, 0) - min).abs() < 1e-6);
     }
 }
@@ -0,0 +1,283 @@
+use crate::macro_map::jungle_noise::generator::Generator;
+use crate::macro_map::jungle_noise::sources::{Cellular, CellularReturn, DistanceMetric, Simplex};
+use crate::macro_map::planet::{LatLon, PlanetMap};
+use crate::macro_map::seed::WorldSeed;
+
+/// Whether a plate carries oceanic or continental crust.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlateType {
+    Oceanic,
+    Continental,
+}
+
+/// How two plates move relative to each other along their boundary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BoundaryType {
+    /// The plates move towards each other, raising mountain ranges or island arcs.
+    Convergent,
+    /// The plates move apart, opening rifts on land and ridges in the ocean.
+    Divergent,
+    /// The plates slide past each other.
+    Transform,
+}
+
+/// A tectonic plate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plate {
+    /// An integer identifying the plate.
+    pub id: usize,
+    pub plate_type: PlateType,
+    /// The centre of the plate, in world coordinates.
+    pub centre: [f64; 2],
+    /// The direction and speed in which the plate moves, tangent to the surface of the planet at
+    /// its centre.
+    pub motion: [f64; 3],
+}
+
+/// The boundary nearest to a point, between the plate containing it and the adjacent plate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Boundary {
+    pub plate: Plate,
+    pub neighbour: Plate,
+    pub boundary_type: BoundaryType,
+    /// The speed at which the plates move towards each other, negative if they move apart.
+    pub convergence: f64,
+    /// The distance from the point to the boundary, in world units.
+    pub distance: f64,
+}
+
+/// Tectonic plates, and the relief they raise along their boundaries.
+///
+/// Plates are the cells of a jittered Voronoi diagram. Each plate gets a pseudorandom type and
+/// motion, and every boundary is classified by how the motions of the plates on either side
+/// compare: plates moving towards each other raise mountains where continental crust is pushed
+/// up, and island arcs where one oceanic plate dives beneath another; plates moving apart open
+/// rifts on land and ridges in the ocean.
+///
+/// The diagram is laid out on the surface of the planet, so plates continue across the
+/// antimeridian and meet at the poles. Positions on the surface are scaled to the radius of the
+/// planet, so distances keep their length in world units along the equator.
+#[derive(Clone, Debug)]
+pub struct TectonicPlates {
+    /// Average distance between the centres of adjacent plates, in world units.
+    pub plate_size: f64,
+    /// Fraction of plates carrying oceanic crust.
+    pub oceanic_fraction: f64,
+    /// Distance from a boundary over which its relief fades out, in world units.
+    pub boundary_width: f64,
+    /// Height of the mountains raised by plates converging at unit speed.
+    pub uplift: f64,
+    /// Depth of the rifts and trenches opened by plates diverging at unit speed.
+    pub subsidence: f64,
+    planet: PlanetMap,
+    cells: Cellular<3>,
+    motion: [Simplex<3>; 3],
+}
+
+impl TectonicPlates {
+    pub fn new(seed: WorldSeed, planet: PlanetMap) -> Self {
+        Self {
+            plate_size: 256.0,
+            oceanic_fraction: 0.5,
+            boundary_width: 24.0,
+            uplift: 0.5,
+            subsidence: 0.2,
+            planet,
+            cells: Cellular::new(
+                seed.derive("cells"),
+                DistanceMetric::Euclidean,
+                CellularReturn::CellId,
+                0.9,
+            ),
+            motion: [
+                Simplex::new(seed.derive("motion/x")),
+                Simplex::new(seed.derive("motion/y")),
+                Simplex::new(seed.derive("motion/z")),
+            ],
+        }
+    }
+
+    /// The plate containing `point`.
+    pub fn plate(&self, point: [f64; 2]) -> Plate {
+        let cell = self.cells.cell(self.on_surface(point));
+        self.plate_at(cell.id, cell.value, cell.feature_point)
+    }
+
+    /// The boundary nearest to `point`.
+    pub fn boundary(&self, point: [f64; 2]) -> Boundary {
+        let cell = self.cells.cell(self.on_surface(point));
+        let plate = self.plate_at(cell.id, cell.value, cell.feature_point);
+        let neighbour = self.plate_at(
+            cell.neighbour_id,
+            cell.neighbour_value,
+            cell.neighbour_point,
+        );
+
+        // the boundary is the perpendicular bisector between the two centres
+        let between = [0, 1, 2].map(|axis| cell.neighbour_point[axis] - cell.feature_point[axis]);
+        let separation = between.iter().map(|d| d * d).sum::<f64>().sqrt();
+        let normal = between.map(|d| d / separation);
+        let distance =
+            (cell.f2 * cell.f2 - cell.f1 * cell.f1) / (2.0 * separation) * self.plate_size;
+
+        let relative = [0, 1, 2].map(|axis| plate.motion[axis] - neighbour.motion[axis]);
+        let convergence = dot(relative, normal);
+        let shear = relative
+            .iter()
+            .zip(normal)
+            .map(|(r, n)| (r - convergence * n).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        let boundary_type = if shear > convergence.abs() {
+            BoundaryType::Transform
+        } else if convergence > 0.0 {
+            BoundaryType::Convergent
+        } else {
+            BoundaryType::Divergent
+        };
+
+        Boundary {
+            plate,
+            neighbour,
+            boundary_type,
+            convergence,
+            distance,
+        }
+    }
+
+    /// The change in altitude at `point` caused by the nearest plate boundary.
+    pub fn relief(&self, point: [f64; 2]) -> f64 {
+        let boundary = self.boundary(point);
+        let falloff = |distance: f64| (-(distance / self.boundary_width).powi(2)).exp();
+        let near = falloff(boundary.distance);
+        let speed = boundary.convergence.abs().min(1.0);
+
+        use PlateType::*;
+        match (
+            boundary.boundary_type,
+            boundary.plate.plate_type,
+            boundary.neighbour.plate_type,
+        ) {
+            (BoundaryType::Convergent, Continental, _) => self.uplift * speed * near,
+            // oceanic crust dives beneath continental crust, leaving a trench
+            (BoundaryType::Convergent, Oceanic, Continental) => -self.subsidence * speed * near,
+            // the plate with the lower id dives beneath the other, whose edge rises into an arc
+            // of islands some distance away from the trench
+            (BoundaryType::Convergent, Oceanic, Oceanic) => {
+                if boundary.plate.id < boundary.neighbour.id {
+                    -self.subsidence * speed * near
+                } else {
+                    self.uplift * speed * falloff(boundary.distance - 2.0 * self.boundary_width)
+                }
+            }
+            (BoundaryType::Divergent, Continental, _) => -self.subsidence * speed * near,
+            (BoundaryType::Divergent, Oceanic, _) => 0.5 * self.subsidence * speed * near,
+            (BoundaryType::Transform, _, _) => 0.0,
+        }
+    }
+
+    /// The range `relief` lies in: boundaries sink at most by the subsidence, and rise at most by
+    /// the uplift or the ridges of diverging oceanic plates, whichever is higher.
+    pub fn relief_range(&self) -> [f64; 2] {
+        [-self.subsidence, self.uplift.max(0.5 * self.subsidence)]
+    }
+
+    /// The point of the surface of the planet under the pixel at `point`, in units of the plate
+    /// size. Pixels which aren't on the planet map to the point at latitude and longitude zero.
+    fn on_surface(&self, point: [f64; 2]) -> [f64; 3] {
+        let lat_lon = self.planet.lat_lon(point[0], point[1]).unwrap_or_default();
+        let radius = self.planet.radius() / self.plate_size;
+        lat_lon.to_unit_sphere().map(|x| x * radius)
+    }
+
+    fn plate_at(&self, id: usize, value: f64, feature_point: [f64; 3]) -> Plate {
+        let plate_type = if value * 0.5 + 0.5 < self.oceanic_fraction {
+            PlateType::Oceanic
+        } else {
+            PlateType::Continental
+        };
+        let (x, y) = self.planet.pixel(LatLon::from_unit_sphere(feature_point));
+        // only the part of the motion along the surface moves the plate
+        let up = LatLon::from_unit_sphere(feature_point).to_unit_sphere();
+        let motion = self.motion.each_ref().map(|motion| motion.sample(feature_point));
+        let rise = dot(motion, up);
+        Plate {
+            id,
+            plate_type,
+            centre: [x, y],
+            motion: [0, 1, 2].map(|axis| motion[axis] - rise * up[axis]),
+        }
+    }
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoundaryType, PlateType, TectonicPlates};
+    use crate::macro_map::planet::{PlanetMap, Projection};
+    use crate::macro_map::seed::WorldSeed;
+
+    #[test]
+    fn boundaries_are_symmetric() {
+        let plates = TectonicPlates::new(WorldSeed::new(7), PlanetMap::new(1024, 512, Projection::Equirectangular));
+        let mut checked = 0;
+        for i in 0..2000 {
+            let point = [(i * 37 % 1024) as f64, (i * 91 % 512) as f64];
+            let boundary = plates.boundary(point);
+            assert_eq!(plates.plate(point), boundary.plate);
+            assert!(boundary.distance >= 0.0);
+
+            // seen from the other side, the same boundary converges just as fast. The centre of a
+            // plate lies beneath its feature point, which may fall into another plate on the surface
+            let other = plates.boundary(boundary.neighbour.centre);
+            if other.plate == boundary.neighbour && other.neighbour == boundary.plate {
+                assert!((other.convergence - boundary.convergence).abs() < 1e-9);
+                assert_eq!(other.boundary_type, boundary.boundary_type);
+                checked += 1;
+            }
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn converging_continents_raise_mountains() {
+        let plates = TectonicPlates::new(WorldSeed::new(7), PlanetMap::new(2048, 1024, Projection::Equirectangular));
+        let mut types = Vec::new();
+        let mut mountains = 0;
+        for i in 0..4000 {
+            let point = [(i * 37 % 2048) as f64, (i * 91 % 1024) as f64];
+            let boundary = plates.boundary(point);
+            types.push(boundary.boundary_type);
+            if boundary.boundary_type == BoundaryType::Convergent
+                && boundary.plate.plate_type == PlateType::Continental
+                && boundary.distance < plates.boundary_width
+            {
+                assert!(plates.relief(point) > 0.0);
+                mountains += 1;
+            }
+        }
+        assert!(mountains > 0);
+        assert!(types.contains(&BoundaryType::Divergent));
+        assert!(types.contains(&BoundaryType::Transform));
+    }
+
+    #[test]
+    fn plates_wrap_around_the_planet() {
+        let plates = TectonicPlates::new(WorldSeed::new(7), PlanetMap::new(1024, 512, Projection::Equirectangular));
+        let mut ids = Vec::new();
+        for y in (0..512).step_by(4) {
+            let y = y as f64;
+            assert_eq!(plates.plate([0.0, y]), plates.plate([1024.0, y]));
+            assert!((plates.relief([0.0, y]) - plates.relief([1024.0, y])).abs() < 1e-9);
+            ids.push(plates.plate([0.0, y]).id);
+        }
+        // every pixel of the top row lies next to the north pole, on the same plate
+        let pole = plates.plate([0.0, 0.0]);
+        assert!((0..1024).all(|x| plates.plate([x as f64, 0.0]) == pole));
+        ids.dedup();
+        assert!(ids.len() > 1);
+    }
+}
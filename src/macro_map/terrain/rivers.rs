@@ -0,0 +1,288 @@
+use crate::macro_map::jungle_noise::generator::Generator;
+use crate::macro_map::jungle_noise::utils::noisebuf::NoiseBuffer;
+use std::f64::consts::SQRT_2;
+
+/// River generation from the flow of water over a heightmap.
+///
+/// Water on every cell flows to the neighbour with the steepest descent, out of all eight. The
+/// flow accumulated in a cell is the number of cells draining through it, anywhere upstream
+/// within the region. As water only ever flows downhill, visiting the cells from the highest to
+/// the lowest passes every cell's flow on before its downstream neighbour is visited, so the
+/// whole region is accumulated in one pass. Water flowing in from outside the region is not
+/// counted, so regions should cover whole catchments, such as the whole map.
+///
+/// Cells whose flow, relative to [`discharge`], exceeds a threshold are rivers. They are carved
+/// into the heightmap and traced into polylines, whose width grows with the flow.
+///
+/// [`discharge`]: RiverNetwork::discharge
+#[derive(Clone, Copy, Debug)]
+pub struct RiverNetwork {
+    /// Accumulated cells at which a river reaches its full width and depth.
+    pub discharge: f64,
+    /// Width of a river at full discharge, in cells.
+    pub width: f64,
+    /// Depth which a river at full discharge is carved into the heightmap.
+    pub depth: f64,
+}
+
+impl Default for RiverNetwork {
+    fn default() -> Self {
+        Self {
+            discharge: 2000.0,
+            width: 3.0,
+            depth: 0.05,
+        }
+    }
+}
+
+/// A point along a [`River`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RiverPoint {
+    /// Position of the cell in world coordinates.
+    pub position: [f64; 2],
+    /// Width of the river at this point, in cells.
+    pub width: f64,
+}
+
+/// A river as a polyline, from upstream to downstream.
+///
+/// A river ends where it joins another river, in which case its last point belongs to that river,
+/// or where it runs into a depression or off the edge of the region it was generated for.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct River {
+    pub points: Vec<RiverPoint>,
+}
+
+/// The result of generating rivers for a region.
+#[derive(Clone, Debug)]
+pub struct RiverRegion {
+    /// The heightmap, with the rivers carved into it.
+    pub altitude: NoiseBuffer<2>,
+    /// The flow of every cell relative to the discharge of the network, in the range [0, 1].
+    pub flow: NoiseBuffer<2>,
+    /// The rivers within the region.
+    pub rivers: Vec<River>,
+}
+
+impl RiverRegion {
+    /// The rivers cut to the `shape` cells starting at `origin`.
+    ///
+    /// Every stretch of a river within the bounds becomes a river of its own, which ends with the
+    /// first point past the bounds, so that the polylines of adjacent bounds connect just like
+    /// those of regions generated separately.
+    pub fn clip(&self, origin: [f64; 2], shape: [usize; 2]) -> Vec<River> {
+        let contains = |point: &RiverPoint| {
+            (0..2).all(|axis| {
+                (origin[axis]..origin[axis] + shape[axis] as f64).contains(&point.position[axis])
+            })
+        };
+        let mut clipped = Vec::new();
+        for river in &self.rivers {
+            let mut points: Vec<RiverPoint> = Vec::new();
+            for point in &river.points {
+                if contains(point) {
+                    points.push(*point);
+                } else if !points.is_empty() {
+                    points.push(*point);
+                    clipped.push(River {
+                        points: std::mem::take(&mut points),
+                    });
+                }
+            }
+            if points.len() > 1 {
+                clipped.push(River { points });
+            }
+        }
+        clipped
+    }
+}
+
+const NEIGHBOURS: [[isize; 2]; 8] = [
+    [-1, -1],
+    [-1, 0],
+    [-1, 1],
+    [0, -1],
+    [0, 1],
+    [1, -1],
+    [1, 0],
+    [1, 1],
+];
+
+impl RiverNetwork {
+    /// Samples `generator` on the grid of `shape` cells starting at `origin`, with a spacing of
+    /// one, and generates the rivers on it. Cells whose relative flow is at least `threshold` are
+    /// rivers.
+    pub fn generate_region<G: Generator<2>>(
+        &self,
+        generator: &G,
+        origin: [f64; 2],
+        shape: [usize; 2],
+        threshold: f64,
+    ) -> RiverRegion {
+        let mut heightmap = NoiseBuffer::new_with_transform(shape, origin, [1.0; 2], generator);
+        let height = heightmap.shape[1];
+        let directions = flow_directions(&heightmap);
+
+        // every cell drains into a lower one, so from high to low is a topological order of the flow
+        let mut order: Vec<usize> = (0..heightmap.buffer.len()).collect();
+        order.sort_by(|&a, &b| heightmap.buffer[b].total_cmp(&heightmap.buffer[a]));
+        let mut accumulation = vec![1.0; heightmap.buffer.len()];
+        for cell in order {
+            if let Some(downstream) = directions[cell] {
+                accumulation[downstream] += accumulation[cell];
+            }
+        }
+        let flow: Vec<f64> = accumulation
+            .iter()
+            .map(|accumulated| (accumulated / self.discharge).min(1.0))
+            .collect();
+        let is_river = |cell: usize| flow[cell] >= threshold;
+
+        for (cell, altitude) in heightmap.buffer.iter_mut().enumerate() {
+            if is_river(cell) {
+                *altitude -= self.depth * flow[cell].sqrt();
+            }
+        }
+
+        // trace every river from its upstream end
+        let river_cells: Vec<usize> = (0..flow.len()).filter(|&cell| is_river(cell)).collect();
+        let mut has_upstream = vec![false; flow.len()];
+        for &cell in &river_cells {
+            if let Some(downstream) = directions[cell] {
+                has_upstream[downstream] = true;
+            }
+        }
+        let mut visited = vec![false; flow.len()];
+        let mut rivers = Vec::new();
+        for &source in river_cells.iter().filter(|&&cell| !has_upstream[cell]) {
+            let mut points = Vec::new();
+            let mut cell = source;
+            loop {
+                points.push(RiverPoint {
+                    position: [
+                        origin[0] + (cell / height) as f64,
+                        origin[1] + (cell % height) as f64,
+                    ],
+                    width: self.width * flow[cell].sqrt(),
+                });
+                if visited[cell] {
+                    break;
+                }
+                visited[cell] = true;
+                match directions[cell] {
+                    Some(downstream) if is_river(downstream) => cell = downstream,
+                    _ => break,
+                }
+            }
+            if points.len() > 1 {
+                rivers.push(River { points });
+            }
+        }
+
+        RiverRegion {
+            flow: NoiseBuffer {
+                shape: heightmap.shape,
+                offsets: heightmap.offsets,
+                buffer: flow,
+            },
+            altitude: heightmap,
+            rivers,
+        }
+    }
+}
+
+/// The neighbour every cell drains into, which is the one with the steepest descent, or `None`
+/// if no neighbour is lower.
+fn flow_directions(heightmap: &NoiseBuffer<2>) -> Vec<Option<usize>> {
+    let [width, height] = heightmap.shape;
+    (0..width * height)
+        .map(|index| {
+            let cell = [index / height, index % height];
+            NEIGHBOURS
+                .iter()
+                .filter_map(|offset| {
+                    let x = cell[0]
+                        .checked_add_signed(offset[0])
+                        .filter(|&x| x < width)?;
+                    let y = cell[1]
+                        .checked_add_signed(offset[1])
+                        .filter(|&y| y < height)?;
+                    let distance = if offset[0] != 0 && offset[1] != 0 {
+                        SQRT_2
+                    } else {
+                        1.0
+                    };
+                    let slope = (heightmap.buffer[index] - heightmap[[x, y]]) / distance;
+                    Some((x * height + y, slope))
+                })
+                .filter(|&(_, slope)| slope > 0.0)
+                .fold(None, |steepest, (neighbour, slope)| match steepest {
+                    Some((_, steepest_slope)) if steepest_slope >= slope => steepest,
+                    _ => Some((neighbour, slope)),
+                })
+                .map(|(neighbour, _)| neighbour)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RiverNetwork;
+    use crate::macro_map::jungle_noise::generator::Generator;
+    use crate::macro_map::jungle_noise::source::Source;
+
+    #[test]
+    fn rivers_flow_downhill() {
+        let generator = Source::<2>::simplex(0).fbm(3, 0.01, 2.0, 0.5);
+        let region = RiverNetwork::default().generate_region(&generator, [0.0; 2], [64, 64], 0.1);
+
+        assert!(!region.rivers.is_empty());
+        for river in &region.rivers {
+            for pair in river.points.windows(2) {
+                let step = [0, 1].map(|axis| pair[1].position[axis] - pair[0].position[axis]);
+                assert!(step.iter().all(|s| s.abs() <= 1.0) && step != [0.0; 2]);
+                let [x, y] = pair[1].position;
+                if (0.0..64.0).contains(&x) && (0.0..64.0).contains(&y) {
+                    assert!(region.flow[[x as usize, y as usize]] >= 0.1);
+                }
+            }
+            let first = river.points.first().unwrap();
+            let last = river.points.last().unwrap();
+            assert!(generator.sample(last.position) < generator.sample(first.position));
+        }
+    }
+
+    #[test]
+    fn flow_accumulates_over_the_whole_catchment() {
+        // a slope falling southwards, down which every column drains straight to the southern edge
+        let slope = Source::custom(|[_, y]| -y);
+        let network = RiverNetwork::default();
+        let region = network.generate_region(&slope, [0.0; 2], [16, 256], 1.0);
+        for y in 0..256 {
+            let upstream = (y + 1) as f64;
+            assert_eq!(region.flow[[8, y]], upstream / network.discharge);
+        }
+    }
+
+    #[test]
+    fn clipped_rivers_connect_across_bounds() {
+        let generator = Source::<2>::simplex(0).fbm(3, 0.01, 2.0, 0.5);
+        let whole = RiverNetwork::default().generate_region(&generator, [0.0; 2], [32, 64], 0.1);
+
+        let halves = [0, 32].map(|y_offset| whole.clip([0.0, y_offset as f64], [32, 32]));
+        let mut crossings = 0;
+        for (from, to) in [(0, 1), (1, 0)] {
+            let y_range = (to * 32) as f64..(to * 32 + 32) as f64;
+            for river in &halves[from] {
+                assert!(river.points.len() > 1);
+                assert!(whole.rivers.iter().any(|r| r.points.contains(&river.points[0])));
+                let end = river.points.last().unwrap();
+                if (0.0..32.0).contains(&end.position[0]) && y_range.contains(&end.position[1]) {
+                    assert!(halves[to].iter().any(|r| r.points.contains(end)));
+                    crossings += 1;
+                }
+            }
+        }
+        assert!(crossings > 0);
+    }
+}
@@ -8,9 +8,16 @@ use bevy::render::render_asset::RenderAssetUsages;
 use bevy_ecs_tilemap::FrustumCulling;
 use bevy_ecs_tilemap::map::{TilemapGridSize, TilemapId, TilemapRenderSettings, TilemapSize, TilemapSpacing, TilemapTexture, TilemapTileSize, TilemapType};
 use bevy_ecs_tilemap::prelude::{get_tilemap_center_transform, MaterialTilemap, StandardTilemapMaterial, TileColor, TileFlip, TilePos, TilePosOld, TileStorage, TileTextureIndex, TileVisible};
-use crate::macro_map::terrain::noise_layers::{AltitudeStrategy, ContinentalnessStrategy, NoiseLayers, NoiseStrategies, NoiseStrategy, NoiseValues, TemperatureStrategy};
+use crate::macro_map::terrain::noise_layers::{AltitudeStrategy, ClimateGrid, ContinentalnessStrategy, ErosionStrategy, Landscape, NoiseLayers, NoiseStrategies, NoiseStrategy, NoiseValues, PeaksAndValleysStrategy, TemperatureStrategy};
 use bevy::prelude::BuildChildren;
 use crate::macro_map::terrain::tiling::{TilingConfig, TilingStrategy};
+use crate::macro_map::seed::WorldSeed;
+use crate::macro_map::planet::{PlanetMap, Projection};
+use crate::macro_map::terrain::erosion::{HydraulicErosion, ThermalErosion};
+use crate::macro_map::terrain::humidity::MoistureModel;
+use crate::macro_map::terrain::lakes::DepressionFilling;
+use crate::macro_map::terrain::rivers::{River, RiverNetwork};
+use crate::macro_map::terrain::wind::WindField;
 
 #[derive(Clone, Copy, Hash, Eq, PartialEq, Debug, Default)]
 pub struct ChunkCoord {
@@ -22,32 +29,52 @@ pub struct ChunkCoord {
 pub struct WorldTextures {
     pub(crate) aggregate: TilemapTexture,
     pub(crate) continentalness: TilemapTexture,
-    // pub(crate) erosion: Vec<Handle<Image>>,
-    // pub(crate) peaks_and_valleys: Vec<Handle<Image>>,
+    pub(crate) erosion: TilemapTexture,
+    pub(crate) peaks_and_valleys: TilemapTexture,
     pub(crate) temperature: TilemapTexture,
-    // pub(crate) humidity: Vec<Handle<Image>>,
+    pub(crate) humidity: TilemapTexture,
+    pub(crate) precipitation: TilemapTexture,
     pub(crate) altitude: TilemapTexture,
-    // pub(crate) wind: Vec<Handle<Image>>,
+    pub(crate) plates: TilemapTexture,
+    pub(crate) wind: TilemapTexture,
     // pub(crate) resources: Vec<Handle<Image>>,
     aggregate_handle: Vec<Handle<Image>>,
     continentalness_handle: Vec<Handle<Image>>,
+    erosion_handle: Vec<Handle<Image>>,
+    peaks_and_valleys_handle: Vec<Handle<Image>>,
     temperature_handle: Vec<Handle<Image>>,
-    altitude_handle: Vec<Handle<Image>>
+    altitude_handle: Vec<Handle<Image>>,
+    plates_handle: Vec<Handle<Image>>,
+    wind_handle: Vec<Handle<Image>>,
+    humidity_handle: Vec<Handle<Image>>,
+    precipitation_handle: Vec<Handle<Image>>
 }
 impl WorldTextures {
     pub fn add_layer(&mut self, noise_layers: &NoiseLayers, mut images: &mut ResMut<Assets<Image>>) {
         self.aggregate_handle.push(images.add(Image::from_dynamic(noise_layers.aggregate.fliph(),false, RenderAssetUsages::default())));
-        self.temperature_handle.push(images.add(Image::from_dynamic(noise_layers.aggregate.fliph(),false, RenderAssetUsages::default())));
+        self.temperature_handle.push(images.add(Image::from_dynamic(noise_layers.temperature.fliph(),false, RenderAssetUsages::default())));
         self.continentalness_handle.push(images.add(Image::from_dynamic(noise_layers.continentalness.fliph(),false, RenderAssetUsages::default())));
+        self.erosion_handle.push(images.add(Image::from_dynamic(noise_layers.erosion.fliph(),false, RenderAssetUsages::default())));
+        self.peaks_and_valleys_handle.push(images.add(Image::from_dynamic(noise_layers.peaks_and_valleys.fliph(),false, RenderAssetUsages::default())));
         self.altitude_handle.push(images.add(Image::from_dynamic(noise_layers.altitude.fliph(),false, RenderAssetUsages::default())));
+        self.plates_handle.push(images.add(Image::from_dynamic(noise_layers.plates.fliph(),false, RenderAssetUsages::default())));
+        self.wind_handle.push(images.add(Image::from_dynamic(noise_layers.wind.fliph(),false, RenderAssetUsages::default())));
+        self.humidity_handle.push(images.add(Image::from_dynamic(noise_layers.humidity.fliph(),false, RenderAssetUsages::default())));
+        self.precipitation_handle.push(images.add(Image::from_dynamic(noise_layers.precipitation.fliph(),false, RenderAssetUsages::default())));
     }
 
     pub fn get_texture(&mut self, layer: &str ) -> TilemapTexture {
         match layer {
             "aggregate" => TilemapTexture::Vector(self.aggregate_handle.clone()),
             "continentalness" => TilemapTexture::Vector(self.continentalness_handle.clone()),
+            "erosion" => TilemapTexture::Vector(self.erosion_handle.clone()),
+            "peaks_and_valleys" => TilemapTexture::Vector(self.peaks_and_valleys_handle.clone()),
             "temperature" => TilemapTexture::Vector(self.temperature_handle.clone()),
             "altitude" => TilemapTexture::Vector(self.altitude_handle.clone()),
+            "plates" => TilemapTexture::Vector(self.plates_handle.clone()),
+            "wind" => TilemapTexture::Vector(self.wind_handle.clone()),
+            "humidity" => TilemapTexture::Vector(self.humidity_handle.clone()),
+            "precipitation" => TilemapTexture::Vector(self.precipitation_handle.clone()),
             _ => TilemapTexture::Vector(self.aggregate_handle.clone()),
         }
     }
@@ -61,6 +88,7 @@ pub struct MacroChunk {
     pub max_meso_chunks: usize,
     pub noise_values: Vec<NoiseValues>,
     pub noise_layers: NoiseLayers,
+    pub rivers: Vec<River>,
 }
 
 #[derive(Bundle, Default)]
@@ -77,27 +105,27 @@ pub struct MacroChunkBundle {
 
 impl MacroChunk {
     pub fn new(size: usize,
-                coord: ChunkCoord, tiling_strategy: &TilingStrategy, noise_strategies: &NoiseStrategies) -> Self {
+                coord: ChunkCoord, tiling_strategy: &TilingStrategy, noise_strategies: &NoiseStrategies,
+                landscape: &Landscape, climate: &ClimateGrid) -> Self {
         let mut noise_layers = NoiseLayers::new(size);
         let mut noise_values = vec![NoiseValues::default(); size * size];
 
         for y in 0..size {
             for x in 0..size {
-                let world_x = coord.x as f64 + x as f64 / size as f64;
-                let world_y = coord.y as f64 + y as f64 / size as f64;
-
                 let index = y * size + x;
-                let current_noise_value = noise_strategies.generate(world_x, world_y, 0);
-                noise_layers.add_at_index(x, y, &current_noise_value, tiling_strategy);
+                let current_noise_value = noise_strategies.cell(landscape, climate, coord.x as usize + x, coord.y as usize + y);
+                noise_layers.add_at_index(x, y, &current_noise_value, &noise_strategies.normalize(&current_noise_value), tiling_strategy);
                 noise_values[index] = current_noise_value;
             }
         }
+        noise_layers.add_wind_arrows(size, 8, &noise_values);
 
         Self {
             coord,
             size,
             noise_values,
             noise_layers,
+            rivers: landscape.rivers([coord.x as usize, coord.y as usize], [size, size]),
             max_meso_chunks: 128,
         }
     }
@@ -163,17 +191,20 @@ impl WorldChunks {
         let map_size = TilemapSize { x: chunking_config.map_width as u32, y: chunking_config.map_height as u32 };
         let mut tile_storage = TileStorage::empty(map_size);
 
-        println!("Creating {} chunks", chunks_x * chunks_y);
+        // Eroding the whole map once lets material and rivers move freely between chunks, which slice their cells from it
+        let landscape = noise_strategies.landscape(&tiling_strategy, [chunking_config.map_width, chunking_config.map_height]);
+        // the wind and moisture look around every cell, so they too share one grid over the eroded map
+        let climate = noise_strategies.climate_grid(&landscape);
+
         for y in 0..chunks_y {
             for x in 0..chunks_x {
                 world_x = x * chunking_config.macro_chunk_size;
                 world_y = y * chunking_config.macro_chunk_size;
-                println!("On MacroChunk {} ", x + (chunks_y * y) );
                 let chunk_coord = ChunkCoord{x: (x * chunking_config.macro_chunk_size) as i32,
                                              y: (y * chunking_config.macro_chunk_size) as i32 };
 
                 let tile_pos = TilePos { x: x as u32, y: y as u32 };
-                let macro_chunk = MacroChunk::new(chunking_config.macro_chunk_size, chunk_coord, &tiling_strategy, &noise_strategies);
+                let macro_chunk = MacroChunk::new(chunking_config.macro_chunk_size, chunk_coord, &tiling_strategy, &noise_strategies, &landscape, &climate);
                 world_textures.add_layer(&macro_chunk.noise_layers, &mut images);
 
                 let macro_chunk_entity = commands.spawn((MacroChunkBundle {
@@ -227,25 +258,49 @@ fn generate_world_chunks(
     mut commands: Commands,
     mut images: ResMut<Assets<Image>>
 ) {
-    let x = 1;
+    let chunking_config = ChunkingConfig {
+        macro_chunk_size: 32,
+        meso_chunk_size: 32,
+        map_width: 1024,
+        map_height: 512,
+    };
+    // every layer gets its own sub-seed, so reseeding one leaves the others untouched
+    let seed = WorldSeed::new(42);
+    // every strategy samples its noise on the surface of the planet, so the map wraps east–west and meets itself at the poles
+    let planet = PlanetMap::new(chunking_config.map_width, chunking_config.map_height, Projection::Equirectangular);
+    let sea_level = 0.1;
+    let mut temperature_strategy = TemperatureStrategy::new(seed.derive("temperature"), planet);
+    temperature_strategy.sea_level = sea_level;
+    let continentalness_strategy = ContinentalnessStrategy::new(seed.derive("continentalness"), planet);
+    let erosion_strategy = ErosionStrategy::new(seed.derive("erosion"), planet);
+    let peaks_and_valleys_strategy = PeaksAndValleysStrategy::new(seed.derive("peaks_and_valleys"), planet);
+    // altitude is composed from the other three layers, so it samples the very same noise
+    let altitude_strategy = AltitudeStrategy::new(seed.derive("altitude"), planet, continentalness_strategy.clone(),
+                                                  erosion_strategy.clone(), peaks_and_valleys_strategy.clone());
+    temperature_strategy.max_altitude = altitude_strategy.range()[1];
+    let mut wind_field = WindField::new(seed.derive("wind"), planet);
+    wind_field.terminator_temperature = temperature_strategy.terminator_temperature;
     WorldChunks::new(commands, images,
                      NoiseStrategies {
-                         continentalness_strategy: ContinentalnessStrategy::new(42),
-                         temperature_strategy: TemperatureStrategy::new(42),
-                         altitude_strategy: AltitudeStrategy::new(42)
+                         continentalness_strategy,
+                         erosion_strategy,
+                         peaks_and_valleys_strategy,
+                         temperature_strategy,
+                         altitude_strategy,
+                         hydraulic_erosion: HydraulicErosion::default(),
+                         thermal_erosion: ThermalErosion::default(),
+                         depression_filling: DepressionFilling::default(),
+                         river_network: RiverNetwork::default(),
+                         wind_field,
+                         moisture_model: MoistureModel { sea_level, ..Default::default() }
                      },
                      TilingStrategy::new(
                          TilingConfig{
-                             sea_level: 0.1,
+                             sea_level,
                              river_threshold: 0.1,
                          }
                     ), 
-                    ChunkingConfig {
-                        macro_chunk_size: 32,
-                        meso_chunk_size: 32,
-                        map_width: 1024,
-                        map_height: 512,
-                    });
+                    chunking_config);
 }
 
 
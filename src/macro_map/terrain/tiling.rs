@@ -35,9 +35,12 @@ pub struct TileThresholds {
     pub altitude: ThresholdRange,
     pub temperature: ThresholdRange,
     pub continentalness: ThresholdRange,
+    pub river: ThresholdRange,
+    pub lake: ThresholdRange,
+    pub humidity: ThresholdRange,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Tile {
     Sea,
     White,
@@ -45,10 +48,13 @@ pub enum Tile {
     Mountain,
     Forest,
     Plains,
+    Steppe,
     Basin,
     Plateau,
     Desert,
     Beach,
+    River,
+    Lake,
     Black
 }
 
@@ -65,6 +71,9 @@ impl Tile {
             Tile::Mountain=> 7,
             Tile::Plateau=> 8,
             Tile::Beach=> 9,
+            Tile::River=> 10,
+            Tile::Lake=> 11,
+            Tile::Steppe=> 12,
             _ => 0
         }
     }
@@ -83,6 +92,9 @@ impl Tile {
             Tile::Mountain=> [105,105,105, 255],
             Tile::Plateau=> [139,69,19, 255],
             Tile::Beach=> [222,184,135, 255],
+            Tile::River=> [30,144,255, 255],
+            Tile::Lake=> [70,130,180, 255],
+            Tile::Steppe=> [189,183,107, 255],
             _ => {[0,0,0,255]}
         }
     }
@@ -101,6 +113,9 @@ impl Tile {
             Tile::Mountain=> Rgb([105,105,105]),
             Tile::Plateau=> Rgb([139,69,19]),
             Tile::Beach=> Rgb([222,184,135]),
+            Tile::River=> Rgb([30,144,255]),
+            Tile::Lake=> Rgb([70,130,180]),
+            Tile::Steppe=> Rgb([189,183,107]),
             _ => Rgb([0,0,0])
         }
     }
@@ -109,74 +124,142 @@ impl Tile {
 impl Tile {
     pub fn thresholds(&self, config: &TilingConfig) -> TileThresholds {
         match self {
+            // everything below sea level which isn't frozen over or a basin inland
             Tile::Sea => TileThresholds {
                 altitude: ThresholdRange::new(f64::MIN, config.sea_level),
                 temperature: ThresholdRange::new(f64::MIN, f64::MAX),
-                continentalness: ThresholdRange::new(0.0, config.river_threshold),
+                continentalness: ThresholdRange::new(f64::MIN, f64::MAX),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
+            },
+            Tile::Lake => TileThresholds {
+                altitude: ThresholdRange::new(config.sea_level, f64::MAX),
+                temperature: ThresholdRange::new(f64::MIN, f64::MAX),
+                continentalness: ThresholdRange::new(f64::MIN, f64::MAX),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN_POSITIVE, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
+            },
+            Tile::River => TileThresholds {
+                altitude: ThresholdRange::new(config.sea_level, f64::MAX),
+                temperature: ThresholdRange::new(f64::MIN, f64::MAX),
+                continentalness: ThresholdRange::new(f64::MIN, f64::MAX),
+                river: ThresholdRange::new(config.river_threshold, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
             },
             Tile::Mountain => TileThresholds {
                 altitude: ThresholdRange::new(config.sea_level + 0.7, f64::MAX),
                 temperature: ThresholdRange::new(f64::MIN, f64::MAX),
-                continentalness: ThresholdRange::new(0.0, f64::MAX),
+                continentalness: ThresholdRange::new(f64::MIN, f64::MAX),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
             },
             Tile::Beach => TileThresholds {
                 altitude: ThresholdRange::new(config.sea_level, config.sea_level + 0.1),
                 temperature: ThresholdRange::new(f64::MIN, f64::MAX),
-                continentalness: ThresholdRange::new(0.0, config.river_threshold),
+                continentalness: ThresholdRange::new(f64::MIN, f64::MAX),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
             },
+            // a hot depression below sea level, far enough inland not to be flooded by the sea
             Tile::Basin => TileThresholds {
                 altitude: ThresholdRange::new(f64::MIN, config.sea_level),
                 temperature: ThresholdRange::new(70.0, f64::MAX),
-                continentalness: ThresholdRange::new(f64::MIN, f64::MAX),
+                continentalness: ThresholdRange::new(0.6, f64::MAX),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
             },
             Tile::Snow => TileThresholds {
                 altitude: ThresholdRange::new(config.sea_level, config.sea_level + 0.7),
                 temperature: ThresholdRange::new(f64::MIN, -30.0),
-                continentalness: ThresholdRange::new(0.0, f64::MAX),
+                continentalness: ThresholdRange::new(f64::MIN, f64::MAX),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
             },
             Tile::Forest => TileThresholds {
                 altitude: ThresholdRange::new(config.sea_level + 0.1, config.sea_level + 0.7),
                 temperature: ThresholdRange::new(-30.0, 70.0),
                 continentalness: ThresholdRange::new(0.6, f64::MAX),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(0.1, f64::MAX),
             },
             Tile::Plains => TileThresholds {
                 altitude: ThresholdRange::new(config.sea_level + 0.1, config.sea_level + 0.7),
                 temperature: ThresholdRange::new(-30.0, 70.0),
-                continentalness: ThresholdRange::new(0.0, 0.6),
+                continentalness: ThresholdRange::new(f64::MIN, 0.6),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
+            },
+            // the dry interior of continents, where the air is too dry for forest
+            Tile::Steppe => TileThresholds {
+                altitude: ThresholdRange::new(config.sea_level + 0.1, config.sea_level + 0.7),
+                temperature: ThresholdRange::new(-30.0, 70.0),
+                continentalness: ThresholdRange::new(0.6, f64::MAX),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
             },
             Tile::White => TileThresholds {
                 altitude: ThresholdRange::new(f64::MIN, config.sea_level),
                 temperature: ThresholdRange::new(f64::MIN, -15.0),
                 continentalness: ThresholdRange::new(0.0, 0.6),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
             },
             Tile::Plateau => TileThresholds {
                 altitude: ThresholdRange::new(f64::MIN, config.sea_level + 0.7),
                 temperature: ThresholdRange::new(70.0, f64::MAX),
                 continentalness: ThresholdRange::new(f64::MIN, f64::MAX),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
             },
             Tile::Desert => TileThresholds {
                 altitude: ThresholdRange::new(config.sea_level, f64::MAX),
                 temperature: ThresholdRange::new(70.0, f64::MAX),
                 continentalness: ThresholdRange::new(f64::MIN, f64::MAX),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
             },
             Tile::Black => TileThresholds {
                 altitude: ThresholdRange::new(f64::MIN, f64::MAX),
                 temperature: ThresholdRange::new(f64::MIN, f64::MAX),
                 continentalness: ThresholdRange::new(f64::MIN, f64::MAX),
+                river: ThresholdRange::new(f64::MIN, f64::MAX),
+                lake: ThresholdRange::new(f64::MIN, f64::MAX),
+                humidity: ThresholdRange::new(f64::MIN, f64::MAX),
             }
         }
     }
 
-    pub fn matches(&self, config: &TilingConfig, altitude: f64, temp: f64, cont: f64) -> bool {
+    pub fn matches(&self, config: &TilingConfig, noise_values: &NoiseValues) -> bool {
+        self.matches_terrain(config, noise_values) &&
+            self.thresholds(config).humidity.contains(noise_values.humidity)
+    }
+
+    /// Like `matches`, but ignores the humidity
+    pub fn matches_terrain(&self, config: &TilingConfig, noise_values: &NoiseValues) -> bool {
         let bounds = self.thresholds(config);
-        bounds.altitude.contains(altitude) &&
-            bounds.temperature.contains(temp) &&
-            bounds.continentalness.contains(cont)
+        bounds.altitude.contains(noise_values.altitude) &&
+            bounds.temperature.contains(noise_values.temperature) &&
+            bounds.continentalness.contains(noise_values.continentalness) &&
+            bounds.river.contains(noise_values.river) &&
+            bounds.lake.contains(noise_values.lake)
     }
 
     pub fn all() -> Vec<Tile> {
         use Tile::*;
-        vec![ Sea, White, Snow, Mountain, Forest, Plains, Basin, Plateau, Desert, Beach, Black ]
+        vec![ White, Basin, Sea, Lake, River, Snow, Mountain, Forest, Plains, Steppe, Plateau, Desert, Beach, Black ]
     }
 }
 
@@ -185,10 +268,17 @@ pub struct TilingStrategy {
 }
 
 impl TilingStrategy {
+    /// Maps a layer value in the [0, 1] range onto a shade of gray; values outside of it are clamped
     pub(crate) fn get_grayscale_tile(&self, noise: f64) -> Rgb<u8> {
-        let value = (noise * 255f64) as u8;
+        let value = (noise.clamp(0.0, 1.0) * 255f64).round() as u8;
         Rgb([value, value, value])
     }
+
+    /// Gives every plate its own colour, so that plates and their boundaries can be told apart
+    pub(crate) fn get_plate_tile(&self, plate: usize) -> Rgb<u8> {
+        let hash = (plate as u32).wrapping_mul(0x9E37_79B9);
+        Rgb([(hash >> 24) as u8, (hash >> 16) as u8, (hash >> 8) as u8])
+    }
 }
 
 impl TilingStrategy {
@@ -196,9 +286,46 @@ impl TilingStrategy {
         Self { config }
     }
 
+    pub fn river_threshold(&self) -> f64 {
+        self.config.river_threshold
+    }
+
+    pub fn sea_level(&self) -> f64 {
+        self.config.sea_level
+    }
+
     pub fn get_tile(&self, noise_values: &NoiseValues) -> Tile {
         Tile::all().into_iter()
-            .find(|tile| tile.matches(&self.config, noise_values.altitude, noise_values.temperature, noise_values.continentalness))
+            .find(|tile| tile.matches(&self.config, noise_values))
             .unwrap_or(Tile::Black)
     }
+
+    /// The tile of a cell regardless of its humidity, for shaping the terrain before the humidity, which depends
+    /// on the terrain, is known. Land which could carry forest counts as forest
+    pub fn get_terrain_tile(&self, noise_values: &NoiseValues) -> Tile {
+        Tile::all().into_iter()
+            .find(|tile| tile.matches_terrain(&self.config, noise_values))
+            .unwrap_or(Tile::Black)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dry_continental_interiors_are_steppe() {
+        let tiling_strategy = TilingStrategy::new(TilingConfig { sea_level: 0.1, river_threshold: 0.1 });
+        let interior = |humidity| NoiseValues {
+            continentalness: 0.8,
+            temperature: 20.0,
+            altitude: 0.5,
+            humidity,
+            ..Default::default()
+        };
+        assert_eq!(tiling_strategy.get_tile(&interior(0.5)), Tile::Forest);
+        assert_eq!(tiling_strategy.get_tile(&interior(0.0)), Tile::Steppe);
+        // the terrain is shaped before the humidity is known, as if the forest grew everywhere it can
+        assert_eq!(tiling_strategy.get_terrain_tile(&interior(0.0)), Tile::Forest);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,177 @@
+use crate::macro_map::jungle_noise::dyn_generator::DynGenerator;
+use crate::macro_map::jungle_noise::generator::Generator;
+use crate::macro_map::jungle_noise::source::Source;
+use crate::macro_map::planet::PlanetMap;
+use crate::macro_map::seed::WorldSeed;
+
+/// Prevailing surface winds.
+///
+/// On a tidally locked planet, air rises over the hot dayside, flows towards the cold nightside,
+/// sinks there and returns to the dayside along the ground. Wherever it is warmer than the
+/// terminator, the prevailing flow blows from warm towards cold regions, down the temperature
+/// gradient. Beyond the terminator, the cold air sinking to the ground drains back towards the
+/// dayside as the return flow, up the temperature gradient, which takes over from the prevailing
+/// flow as the air cools by [`transition`] below the terminator temperature.
+///
+/// Where the wind would be pushed uphill, the uphill part is turned aside in proportion to the
+/// steepness of the slope, so that winds flow around mountain ranges rather than over them.
+/// Then noise turns the wind by up to [`turbulence`] to either side. Finally, winds are capped at
+/// [`max_speed`].
+///
+/// [`transition`]: WindField::transition
+/// [`turbulence`]: WindField::turbulence
+/// [`max_speed`]: WindField::max_speed
+#[derive(Clone, Debug)]
+pub struct WindField {
+    /// Speed of the prevailing flow per degree of temperature difference across one cell.
+    pub strength: f64,
+    /// Speed of the return flow relative to that of the prevailing flow.
+    pub return_flow: f64,
+    /// Temperature at sea level along the terminator, below which the return flow sets in.
+    pub terminator_temperature: f64,
+    /// Drop in temperature below the terminator temperature over which the return flow takes
+    /// over from the prevailing flow.
+    pub transition: f64,
+    /// Speed which no wind exceeds.
+    pub max_speed: f64,
+    /// Slope at which the uphill part of the wind is turned aside completely.
+    pub blocking_slope: f64,
+    /// Largest angle, in radians, by which noise turns the wind.
+    pub turbulence: f64,
+    /// Distance between the samples used to estimate gradients, in cells.
+    pub spacing: f64,
+    noise: DynGenerator<2>,
+}
+
+impl WindField {
+    pub fn new(seed: WorldSeed, planet: PlanetMap) -> Self {
+        Self {
+            strength: 2.0,
+            return_flow: 0.8,
+            terminator_temperature: 10.0,
+            transition: 20.0,
+            max_speed: 4.0,
+            blocking_slope: 0.02,
+            turbulence: 0.6,
+            spacing: 4.0,
+            noise: planet.surface(Source::simplex(seed).fbm(3, 1.0 / 100.0, 2.0, 0.5)).into_dyn(),
+        }
+    }
+
+    /// The wind at `x`, `y`, given the temperature at sea level and the altitude around it.
+    pub fn sample(
+        &self,
+        x: f64,
+        y: f64,
+        temperature: impl Fn(f64, f64) -> f64,
+        altitude: impl Fn(f64, f64) -> f64,
+    ) -> [f64; 2] {
+        let warming = self.gradient(x, y, &temperature);
+        let cooling = ((self.terminator_temperature - temperature(x, y)) / self.transition).clamp(0.0, 1.0);
+        // positive where the return flow up the temperature gradient outweighs the prevailing flow down it
+        let flow = cooling * self.return_flow - (1.0 - cooling);
+        let mut wind = warming.map(|d| d * self.strength * flow);
+
+        let slope = self.gradient(x, y, altitude);
+        let steepness = slope[0].hypot(slope[1]);
+        if steepness > 0.0 {
+            let uphill = slope.map(|d| d / steepness);
+            let climb = wind[0] * uphill[0] + wind[1] * uphill[1];
+            if climb > 0.0 {
+                let blocked = climb * (steepness / self.blocking_slope).min(1.0);
+                wind = [0, 1].map(|axis| wind[axis] - blocked * uphill[axis]);
+            }
+        }
+
+        let (sin, cos) = (self.turbulence * self.noise.sample([x, y])).sin_cos();
+        let wind = [wind[0] * cos - wind[1] * sin, wind[0] * sin + wind[1] * cos];
+
+        let speed = wind[0].hypot(wind[1]);
+        if speed > self.max_speed {
+            wind.map(|w| w * self.max_speed / speed)
+        } else {
+            wind
+        }
+    }
+
+    /// The gradient of `f` at `x`, `y`, by central differences.
+    fn gradient(&self, x: f64, y: f64, f: impl Fn(f64, f64) -> f64) -> [f64; 2] {
+        let h = self.spacing;
+        [
+            (f(x + h, y) - f(x - h, y)) / (2.0 * h),
+            (f(x, y + h) - f(x, y - h)) / (2.0 * h),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WindField;
+    use crate::macro_map::planet::{PlanetMap, Projection};
+    use crate::macro_map::seed::WorldSeed;
+
+    fn calm() -> WindField {
+        WindField {
+            turbulence: 0.0,
+            ..WindField::new(WorldSeed::new(5), PlanetMap::new(1024, 512, Projection::Equirectangular))
+        }
+    }
+
+    #[test]
+    fn wind_blows_from_the_dayside_towards_the_nightside() {
+        let wind = calm().sample(10.0, 20.0, |x, _| 100.0 + 0.5 * x, |_, _| 0.0);
+        assert!((wind[0] + 1.0).abs() < 1e-9);
+        assert!(wind[1].abs() < 1e-9);
+    }
+
+    #[test]
+    fn cold_air_returns_towards_the_dayside() {
+        let field = calm();
+        let wind = field.sample(10.0, 20.0, |x, _| -100.0 + 0.5 * x, |_, _| 0.0);
+        assert!((wind[0] - field.return_flow).abs() < 1e-9);
+        assert!(wind[1].abs() < 1e-9);
+
+        // the return flow takes over gradually beyond the terminator
+        let terminator = field.terminator_temperature;
+        let speeds: Vec<f64> = [0.0, 0.25, 0.5, 0.75, 1.0]
+            .map(|share| {
+                let offset = terminator - share * field.transition;
+                field.sample(0.0, 0.0, |x, _| offset + 0.5 * x, |_, _| 0.0)[0]
+            })
+            .to_vec();
+        assert!((speeds[0] + 1.0).abs() < 1e-9);
+        assert!(speeds.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn slopes_turn_the_wind_aside() {
+        let field = calm();
+        // a ridge rising steeply to the east, at an angle to the wind
+        let wind = field.sample(10.0, 20.0, |x, y| 100.0 - 0.5 * (x + y), |x, _| 0.05 * x);
+        assert!(wind[0].abs() < 1e-9);
+        assert!((wind[1] - 1.0).abs() < 1e-9);
+
+        // wind blowing downhill is unaffected
+        let wind = field.sample(10.0, 20.0, |x, _| 100.0 + 0.5 * x, |x, _| 0.05 * x);
+        assert!((wind[0] + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn turbulence_only_changes_the_direction() {
+        let field = WindField::new(WorldSeed::new(5), PlanetMap::new(1024, 512, Projection::Equirectangular));
+        let mut turned = false;
+        for i in 0..100 {
+            let wind = field.sample(i as f64 * 13.0, 256.0, |x, _| 100.0 + 0.5 * x, |_, _| 0.0);
+            assert!((wind[0].hypot(wind[1]) - 1.0).abs() < 1e-9);
+            turned |= wind[1].abs() > 0.1;
+        }
+        assert!(turned);
+    }
+
+    #[test]
+    fn winds_are_capped() {
+        let field = WindField::new(WorldSeed::new(5), PlanetMap::new(1024, 512, Projection::Equirectangular));
+        let wind = field.sample(30.0, 20.0, |x, y| 100.0 + 10.0 * (x - y), |_, _| 0.0);
+        assert!((wind[0].hypot(wind[1]) - field.max_speed).abs() < 1e-9);
+    }
+}